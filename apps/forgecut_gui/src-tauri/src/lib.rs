@@ -55,6 +55,36 @@ fn import_assets(
     Ok(imported)
 }
 
+#[tauri::command]
+async fn import_remote_asset(
+    url: String,
+    format_selector: Option<String>,
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<serde_json::Value, String> {
+    let _ = app.emit("import-progress", serde_json::json!({"url": url, "status": "downloading"}));
+
+    let cache_dir = std::env::temp_dir().join("forgecut-remote-cache");
+    let download_url = url.clone();
+    let asset = tokio::task::spawn_blocking(move || {
+        forgecut_render::probe::import_remote_asset(
+            &download_url,
+            &cache_dir,
+            format_selector.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to import {url}: {e}"))?;
+
+    let json = serde_json::to_value(&asset).map_err(|e| e.to_string())?;
+    let mut project = state.project.lock().unwrap();
+    project.assets.push(asset);
+
+    let _ = app.emit("import-progress", serde_json::json!({"url": url, "status": "complete"}));
+    Ok(json)
+}
+
 #[tauri::command]
 fn get_assets(state: tauri::State<AppState>) -> Result<Vec<serde_json::Value>, String> {
     let project = state.project.lock().unwrap();
@@ -73,10 +103,142 @@ fn remove_asset(id: String, state: tauri::State<AppState>) -> Result<(), String>
     Ok(())
 }
 
+/// Remove assets not referenced by any clip/overlay on the timeline, along with
+/// their cached proxies/thumbnails/waveforms. With `dry_run`, nothing is
+/// mutated -- the would-be-removed asset ids and file paths are just reported
+/// so the frontend can confirm before calling again with `dry_run: false`.
+#[tauri::command]
+fn gc_assets(dry_run: bool, state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
+    let mut project = state.project.lock().unwrap();
+
+    let referenced = project.timeline.all_referenced_asset_ids();
+
+    let orphaned: Vec<uuid::Uuid> = project
+        .assets
+        .iter()
+        .map(|a| a.id)
+        .filter(|id| !referenced.contains(id))
+        .collect();
+
+    let mut removed_files = Vec::new();
+    for asset_id in &orphaned {
+        let proxy_path = std::env::temp_dir()
+            .join("forgecut-proxies")
+            .join(format!("{asset_id}.mp4"));
+        if proxy_path.exists() {
+            removed_files.push(proxy_path.to_string_lossy().to_string());
+        }
+
+        let thumb_dir = std::env::temp_dir()
+            .join("forgecut-thumbnails")
+            .join(asset_id.to_string());
+        if thumb_dir.exists() {
+            removed_files.push(thumb_dir.to_string_lossy().to_string());
+        }
+
+        let waveform_path = std::env::temp_dir()
+            .join("forgecut-waveforms")
+            .join(format!("{asset_id}.json"));
+        if waveform_path.exists() {
+            removed_files.push(waveform_path.to_string_lossy().to_string());
+        }
+    }
+
+    if !dry_run {
+        for path in &removed_files {
+            let p = std::path::PathBuf::from(path);
+            if p.is_dir() {
+                let _ = std::fs::remove_dir_all(&p);
+            } else {
+                let _ = std::fs::remove_file(&p);
+            }
+        }
+        project.assets.retain(|a| referenced.contains(&a.id));
+    }
+
+    Ok(serde_json::json!({
+        "removed_asset_ids": orphaned.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+        "removed_files": removed_files,
+    }))
+}
+
+/// Resolve the timeline that editing commands should operate on: either the
+/// project's top-level timeline, or the nested sequence of a `CompoundClip`
+/// the user has "entered" via [`enter_compound`].
+fn active_timeline_mut<'a>(
+    project: &'a mut forgecut_core::types::Project,
+    compound_path: &[uuid::Uuid],
+) -> Result<&'a mut forgecut_core::types::Timeline, String> {
+    project
+        .timeline
+        .resolve_compound_path_mut(compound_path)
+        .ok_or_else(|| "active compound clip no longer exists".to_string())
+}
+
+fn active_timeline<'a>(
+    project: &'a forgecut_core::types::Project,
+    compound_path: &[uuid::Uuid],
+) -> Result<&'a forgecut_core::types::Timeline, String> {
+    project
+        .timeline
+        .resolve_compound_path(compound_path)
+        .ok_or_else(|| "active compound clip no longer exists".to_string())
+}
+
 #[tauri::command]
 fn get_timeline(state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
     let project = state.project.lock().unwrap();
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let compound_path = state.compound_path.lock().unwrap();
+    let timeline = active_timeline(&project, &compound_path)?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn create_compound_from_selection(
+    item_ids: Vec<String>,
+    state: tauri::State<AppState>,
+) -> Result<serde_json::Value, String> {
+    let uuids = item_ids
+        .iter()
+        .map(|id| uuid::Uuid::parse_str(id).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    timeline
+        .create_compound_from_selection(&uuids)
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn enter_compound(
+    item_id: String,
+    state: tauri::State<AppState>,
+) -> Result<serde_json::Value, String> {
+    let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
+    let project = state.project.lock().unwrap();
+    let mut compound_path = state.compound_path.lock().unwrap();
+
+    let mut candidate_path = compound_path.clone();
+    candidate_path.push(uuid);
+    let nested = project
+        .timeline
+        .resolve_compound_path(&candidate_path)
+        .ok_or("item is not a compound clip")?;
+    let json = serde_json::to_value(nested).map_err(|e| e.to_string())?;
+    *compound_path = candidate_path;
+    Ok(json)
+}
+
+#[tauri::command]
+fn exit_compound(state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
+    let project = state.project.lock().unwrap();
+    let mut compound_path = state.compound_path.lock().unwrap();
+    compound_path.pop();
+    let timeline = active_timeline(&project, &compound_path)?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -90,6 +252,7 @@ fn add_clip_to_timeline(
     let track_uuid = uuid::Uuid::parse_str(&track_id).map_err(|e| e.to_string())?;
 
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
 
     let asset = project
         .assets
@@ -105,14 +268,21 @@ fn add_clip_to_timeline(
         .unwrap_or(forgecut_core::types::TimeUs::from_seconds(5.0));
 
     let item = match asset.kind {
-        forgecut_core::types::AssetKind::Video => forgecut_core::types::Item::VideoClip {
-            id: uuid::Uuid::new_v4(),
-            asset_id: asset_uuid,
-            track_id: track_uuid,
-            timeline_start_us: forgecut_core::types::TimeUs(timeline_start_us),
-            source_in_us: forgecut_core::types::TimeUs::ZERO,
-            source_out_us: duration,
-        },
+        // An animated image (GIF/APNG/animated WebP) loops/trims by frames
+        // like a short video, so it becomes the same clip item as a video.
+        forgecut_core::types::AssetKind::Video | forgecut_core::types::AssetKind::AnimatedImage => {
+            forgecut_core::types::Item::VideoClip {
+                id: uuid::Uuid::new_v4(),
+                asset_id: asset_uuid,
+                track_id: track_uuid,
+                timeline_start_us: forgecut_core::types::TimeUs(timeline_start_us),
+                source_in_us: forgecut_core::types::TimeUs::ZERO,
+                source_out_us: duration,
+                speed: 1.0,
+                fade_in_us: forgecut_core::types::TimeUs::ZERO,
+                fade_out_us: forgecut_core::types::TimeUs::ZERO,
+            }
+        }
         forgecut_core::types::AssetKind::Audio => forgecut_core::types::Item::AudioClip {
             id: uuid::Uuid::new_v4(),
             asset_id: asset_uuid,
@@ -120,7 +290,10 @@ fn add_clip_to_timeline(
             timeline_start_us: forgecut_core::types::TimeUs(timeline_start_us),
             source_in_us: forgecut_core::types::TimeUs::ZERO,
             source_out_us: duration,
+            speed: 1.0,
             volume: 1.0,
+            fade_in_us: forgecut_core::types::TimeUs::ZERO,
+            fade_out_us: forgecut_core::types::TimeUs::ZERO,
         },
         forgecut_core::types::AssetKind::Image => forgecut_core::types::Item::ImageOverlay {
             id: uuid::Uuid::new_v4(),
@@ -136,11 +309,9 @@ fn add_clip_to_timeline(
         },
     };
 
-    project
-        .timeline
-        .add_item(track_uuid, item)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    timeline.add_item(track_uuid, item).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -154,6 +325,8 @@ fn init_default_tracks(state: tauri::State<AppState>) -> Result<serde_json::Valu
                 id: uuid::Uuid::new_v4(),
                 kind: forgecut_core::types::TrackKind::Video,
                 items: vec![],
+                transitions: vec![],
+                subtitles: None,
             });
         project
             .timeline
@@ -162,6 +335,8 @@ fn init_default_tracks(state: tauri::State<AppState>) -> Result<serde_json::Valu
                 id: uuid::Uuid::new_v4(),
                 kind: forgecut_core::types::TrackKind::Audio,
                 items: vec![],
+                transitions: vec![],
+                subtitles: None,
             });
     }
     serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
@@ -172,17 +347,30 @@ fn trim_clip(
     item_id: String,
     trim_type: String,
     new_us: i64,
+    snap_to_keyframe: Option<bool>,
     state: tauri::State<AppState>,
 ) -> Result<serde_json::Value, String> {
     let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
 
     let cmd: Box<dyn forgecut_core::history::Command> = if trim_type == "in" {
-        Box::new(forgecut_core::history::TrimInCommand::new(
-            uuid,
-            forgecut_core::types::TimeUs(new_us),
-        ))
+        let mut new_in_us = forgecut_core::types::TimeUs(new_us);
+        if snap_to_keyframe.unwrap_or(false) {
+            let keyframes_us = active_timeline(&project, &compound_path)?
+                .tracks
+                .iter()
+                .flat_map(|t| &t.items)
+                .find(|i| i.id() == uuid)
+                .and_then(|i| i.asset_id())
+                .and_then(|asset_id| project.assets.iter().find(|a| a.id == asset_id))
+                .and_then(|a| a.probe.as_ref())
+                .map(|p| p.keyframes_us.as_slice())
+                .unwrap_or(&[]);
+            new_in_us = forgecut_render::probe::nearest_keyframe_before(keyframes_us, new_in_us);
+        }
+        Box::new(forgecut_core::history::TrimInCommand::new(uuid, new_in_us))
     } else {
         Box::new(forgecut_core::history::TrimOutCommand::new(
             uuid,
@@ -190,10 +378,26 @@ fn trim_clip(
         ))
     };
 
-    history
-        .execute(cmd, &mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_clip_speed(
+    item_id: String,
+    speed: f64,
+    state: tauri::State<AppState>,
+) -> Result<serde_json::Value, String> {
+    let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
+    let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
+    let mut history = state.history.lock().unwrap();
+
+    let cmd = Box::new(forgecut_core::history::SetSpeedCommand::new(uuid, speed));
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -204,16 +408,16 @@ fn split_clip(
 ) -> Result<serde_json::Value, String> {
     let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
 
     let cmd = Box::new(forgecut_core::history::SplitCommand::new(
         uuid,
         forgecut_core::types::TimeUs(split_time_us),
     ));
-    history
-        .execute(cmd, &mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -223,13 +427,13 @@ fn delete_clip(
 ) -> Result<serde_json::Value, String> {
     let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
 
     let cmd = Box::new(forgecut_core::history::RemoveItemCommand::new(uuid));
-    history
-        .execute(cmd, &mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -240,16 +444,16 @@ fn move_clip(
 ) -> Result<serde_json::Value, String> {
     let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
 
     let cmd = Box::new(forgecut_core::history::MoveItemCommand::new(
         uuid,
         forgecut_core::types::TimeUs(new_start_us),
     ));
-    history
-        .execute(cmd, &mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -262,6 +466,7 @@ fn move_clip_to_track(
     let item_uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
     let track_uuid = uuid::Uuid::parse_str(&new_track_id).map_err(|e| e.to_string())?;
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
 
     let cmd = Box::new(forgecut_core::history::MoveItemToTrackCommand::new(
@@ -269,30 +474,29 @@ fn move_clip_to_track(
         track_uuid,
         forgecut_core::types::TimeUs(new_start_us),
     ));
-    history
-        .execute(cmd, &mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.execute(cmd, timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn undo(state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
-    history
-        .undo(&mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.undo(timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn redo(state: tauri::State<AppState>) -> Result<serde_json::Value, String> {
     let mut project = state.project.lock().unwrap();
+    let compound_path = state.compound_path.lock().unwrap();
     let mut history = state.history.lock().unwrap();
-    history
-        .redo(&mut project.timeline)
-        .map_err(|e| e.to_string())?;
-    serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
+    let timeline = active_timeline_mut(&mut project, &compound_path)?;
+    history.redo(timeline).map_err(|e| e.to_string())?;
+    serde_json::to_value(timeline).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -303,37 +507,22 @@ fn get_clip_at_playhead(
     let project = state.project.lock().unwrap();
     let playhead = forgecut_core::types::TimeUs(playhead_us);
 
-    // Search video tracks first, then audio
-    for track in &project.timeline.tracks {
-        for item in &track.items {
-            let start = item.timeline_start_us();
-            let end = item.timeline_end_us();
-            if playhead >= start && playhead < end {
-                if let Some(asset_id) = item.asset_id() {
-                    if let Some(asset) = project.assets.iter().find(|a| a.id == asset_id) {
-                        // Calculate seek offset into the source file
-                        let offset_in_timeline = forgecut_core::types::TimeUs(playhead.0 - start.0);
-                        let source_in = match item {
-                            forgecut_core::types::Item::VideoClip { source_in_us, .. } => *source_in_us,
-                            forgecut_core::types::Item::AudioClip { source_in_us, .. } => *source_in_us,
-                            _ => forgecut_core::types::TimeUs::ZERO,
-                        };
-                        let seek_us = forgecut_core::types::TimeUs(source_in.0 + offset_in_timeline.0);
-
-                        return Ok(serde_json::json!({
-                            "file_path": asset.path.to_string_lossy(),
-                            "seek_seconds": seek_us.as_seconds(),
-                            "clip_start_us": start.0,
-                            "clip_end_us": end.0,
-                            "source_in_us": source_in.0,
-                        }));
-                    }
-                }
-            }
-        }
-    }
-    // No clip at playhead
-    Ok(serde_json::json!(null))
+    // Recurses into any `CompoundClip`'s nested sequence, so a compound
+    // plays back like a single clip.
+    let Some(resolved) = project.timeline.resolve_clip_at(playhead) else {
+        return Ok(serde_json::json!(null));
+    };
+    let Some(asset) = project.assets.iter().find(|a| a.id == resolved.asset_id) else {
+        return Ok(serde_json::json!(null));
+    };
+
+    Ok(serde_json::json!({
+        "file_path": asset.path.to_string_lossy(),
+        "seek_seconds": resolved.seek_us.as_seconds(),
+        "clip_start_us": resolved.clip_start_us.0,
+        "clip_end_us": resolved.clip_end_us.0,
+        "source_in_us": resolved.source_in_us.0,
+    }))
 }
 
 #[tauri::command]
@@ -458,6 +647,8 @@ fn add_text_overlay(
                 id: track_uuid,
                 kind: forgecut_core::types::TrackKind::OverlayText,
                 items: vec![],
+                transitions: vec![],
+                subtitles: None,
             });
     }
 
@@ -485,38 +676,29 @@ fn get_overlays_at_time(
     let playhead = forgecut_core::types::TimeUs(playhead_us);
     let mut overlays = Vec::new();
 
-    for track in &project.timeline.tracks {
-        if track.kind != forgecut_core::types::TrackKind::OverlayImage
-            && track.kind != forgecut_core::types::TrackKind::OverlayText
-        {
-            continue;
-        }
-        for item in &track.items {
-            let start = item.timeline_start_us();
-            let end = item.timeline_end_us();
-            if playhead >= start && playhead < end {
-                let mut val = serde_json::to_value(item).map_err(|e| e.to_string())?;
-                // For image overlays, attach the file path
-                if let Some(asset_id) = item.asset_id() {
-                    if let Some(asset) = project.assets.iter().find(|a| a.id == asset_id) {
-                        if let serde_json::Value::Object(ref mut map) = val {
-                            // The value is like {"ImageOverlay": {...}}, we need to add file_path inside
-                            for (_key, inner) in map.iter_mut() {
-                                if let serde_json::Value::Object(ref mut inner_map) = inner {
-                                    inner_map.insert(
-                                        "file_path".to_string(),
-                                        serde_json::Value::String(
-                                            asset.path.to_string_lossy().to_string(),
-                                        ),
-                                    );
-                                }
-                            }
+    // Recurses into any `CompoundClip`'s nested sequence, rebasing nested
+    // overlay positions into this timeline's coordinate frame.
+    for item in project.timeline.resolve_overlays_at(playhead) {
+        let mut val = serde_json::to_value(&item).map_err(|e| e.to_string())?;
+        // For image overlays, attach the file path
+        if let Some(asset_id) = item.asset_id() {
+            if let Some(asset) = project.assets.iter().find(|a| a.id == asset_id) {
+                if let serde_json::Value::Object(ref mut map) = val {
+                    // The value is like {"ImageOverlay": {...}}, we need to add file_path inside
+                    for (_key, inner) in map.iter_mut() {
+                        if let serde_json::Value::Object(ref mut inner_map) = inner {
+                            inner_map.insert(
+                                "file_path".to_string(),
+                                serde_json::Value::String(
+                                    asset.path.to_string_lossy().to_string(),
+                                ),
+                            );
                         }
                     }
                 }
-                overlays.push(val);
             }
         }
+        overlays.push(val);
     }
 
     Ok(overlays)
@@ -636,6 +818,9 @@ fn update_item_property(
                         }
                         _ => return Err(format!("Unknown property: {property}")),
                     },
+                    forgecut_core::types::Item::CompoundClip { .. } => {
+                        // CompoundClips don't have editable properties via inspector for now
+                    }
                 }
                 return serde_json::to_value(&project.timeline)
                     .map_err(|e| e.to_string());
@@ -653,12 +838,15 @@ fn add_track(kind: String, state: tauri::State<AppState>) -> Result<serde_json::
         "Audio" => forgecut_core::types::TrackKind::Audio,
         "OverlayImage" => forgecut_core::types::TrackKind::OverlayImage,
         "OverlayText" => forgecut_core::types::TrackKind::OverlayText,
+        "Subtitles" => forgecut_core::types::TrackKind::Subtitles,
         _ => return Err(format!("Unknown track kind: {kind}")),
     };
     project.timeline.tracks.push(forgecut_core::types::Track {
         id: uuid::Uuid::new_v4(),
         kind: track_kind,
         items: vec![],
+        transitions: vec![],
+        subtitles: None,
     });
     serde_json::to_value(&project.timeline).map_err(|e| e.to_string())
 }
@@ -707,6 +895,12 @@ async fn export_project(
                 }
             }
         }
+        if let Some(intro) = &project.intro {
+            max_end = max_end + intro.duration_us;
+        }
+        if let Some(outro) = &project.outro {
+            max_end = max_end + outro.duration_us;
+        }
         max_end
     };
 
@@ -879,6 +1073,98 @@ fn get_waveform(asset_id: String, state: tauri::State<AppState>) -> Result<serde
     serde_json::to_value(&data).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn generate_thumbnails(
+    item_id: String,
+    frame_count: u32,
+    frame_width: u32,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
+    let (asset_path, asset_id, source_in_us, source_out_us) = {
+        let project = state.project.lock().unwrap();
+        let item = project
+            .timeline
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == uuid)
+            .ok_or("Item not found")?;
+        let asset_id = item.asset_id().ok_or("Item has no asset")?;
+        let source_in_us = item.source_in_us().ok_or("Item has no source range")?;
+        let source_out_us = item.source_out_us().ok_or("Item has no source range")?;
+        let asset = project
+            .assets
+            .iter()
+            .find(|a| a.id == asset_id)
+            .ok_or("Asset not found")?;
+        (asset.path.clone(), asset_id.to_string(), source_in_us, source_out_us)
+    };
+
+    let strip_path = tokio::task::spawn_blocking(move || {
+        let cache_dir = std::env::temp_dir().join("forgecut-filmstrips");
+        forgecut_render::thumbnails::generate_filmstrip(
+            &asset_path,
+            &cache_dir,
+            &asset_id,
+            source_in_us,
+            source_out_us,
+            frame_count,
+            frame_width,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    Ok(strip_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn generate_waveform(
+    item_id: String,
+    peak_width: u32,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let uuid = uuid::Uuid::parse_str(&item_id).map_err(|e| e.to_string())?;
+    let (asset_path, asset_id, source_in_us, source_out_us) = {
+        let project = state.project.lock().unwrap();
+        let item = project
+            .timeline
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == uuid)
+            .ok_or("Item not found")?;
+        let asset_id = item.asset_id().ok_or("Item has no asset")?;
+        let source_in_us = item.source_in_us().ok_or("Item has no source range")?;
+        let source_out_us = item.source_out_us().ok_or("Item has no source range")?;
+        let asset = project
+            .assets
+            .iter()
+            .find(|a| a.id == asset_id)
+            .ok_or("Asset not found")?;
+        (asset.path.clone(), asset_id.to_string(), source_in_us, source_out_us)
+    };
+
+    let data = tokio::task::spawn_blocking(move || {
+        let cache_dir = std::env::temp_dir().join("forgecut-waveforms");
+        forgecut_render::waveform::extract_waveform_range(
+            &asset_path,
+            &cache_dir,
+            &asset_id,
+            source_in_us,
+            source_out_us,
+            peak_width,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    serde_json::to_value(&data).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn mpv_start(
     x: i32,
@@ -967,6 +1253,148 @@ fn mpv_update_geometry(
     Ok(())
 }
 
+/// Build the preview clip list active at `playhead_us`, resolving each item's
+/// asset path and geometry for the compositing pipeline.
+fn resolve_preview_clips(
+    project: &forgecut_core::types::Project,
+    playhead_us: forgecut_core::types::TimeUs,
+) -> Vec<forgecut_preview::playback::PreviewClip> {
+    use forgecut_core::types::Item;
+
+    let mut clips = Vec::new();
+    for track in &project.timeline.tracks {
+        for item in &track.items {
+            let start = item.timeline_start_us();
+            let end = item.timeline_end_us();
+            if playhead_us < start || playhead_us >= end {
+                continue;
+            }
+            let Some(asset_id) = item.asset_id() else {
+                continue;
+            };
+            let Some(asset) = project.assets.iter().find(|a| a.id == asset_id) else {
+                continue;
+            };
+
+            let (source_in_us, source_out_us, x, y, width, height, opacity, volume, has_video, has_audio) =
+                match item {
+                    Item::VideoClip {
+                        source_in_us,
+                        source_out_us,
+                        ..
+                    } => (
+                        *source_in_us,
+                        *source_out_us,
+                        0,
+                        0,
+                        project.settings.width,
+                        project.settings.height,
+                        1.0,
+                        1.0,
+                        true,
+                        true,
+                    ),
+                    Item::AudioClip {
+                        source_in_us,
+                        source_out_us,
+                        volume,
+                        ..
+                    } => (
+                        *source_in_us,
+                        *source_out_us,
+                        0,
+                        0,
+                        0,
+                        0,
+                        1.0,
+                        *volume,
+                        false,
+                        true,
+                    ),
+                    Item::ImageOverlay {
+                        x,
+                        y,
+                        width,
+                        height,
+                        opacity,
+                        ..
+                    } => (
+                        forgecut_core::types::TimeUs::ZERO,
+                        end - start,
+                        *x,
+                        *y,
+                        *width,
+                        *height,
+                        *opacity,
+                        0.0,
+                        true,
+                        false,
+                    ),
+                    Item::TextOverlay { .. } | Item::CompoundClip { .. } => continue,
+                };
+
+            clips.push(forgecut_preview::playback::PreviewClip {
+                path: asset.path.clone(),
+                source_in_us,
+                source_out_us,
+                timeline_start_us: start,
+                x,
+                y,
+                width,
+                height,
+                opacity,
+                volume,
+                has_video,
+                has_audio,
+            });
+        }
+    }
+    clips
+}
+
+#[tauri::command]
+fn start_preview(
+    playhead_us: i64,
+    state: tauri::State<AppState>,
+    window: tauri::WebviewWindow,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let project = state.project.lock().unwrap();
+    let clips = resolve_preview_clips(&project, forgecut_core::types::TimeUs(playhead_us));
+
+    let mut playback = state.playback.lock().unwrap();
+    let mut rx = playback.start(&clips, &window)?;
+
+    let app_handle = app.clone();
+    tokio::spawn(async move {
+        loop {
+            if rx.changed().await.is_err() {
+                break;
+            }
+            let position = rx.borrow().clone();
+            let _ = app_handle.emit("preview-position", serde_json::to_value(&position).unwrap());
+            if position.eos {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+fn seek_preview(position_us: i64, state: tauri::State<AppState>) -> Result<(), String> {
+    let playback = state.playback.lock().unwrap();
+    playback.seek(position_us)
+}
+
+#[tauri::command]
+fn stop_preview(state: tauri::State<AppState>) -> Result<(), String> {
+    let mut playback = state.playback.lock().unwrap();
+    playback.stop();
+    Ok(())
+}
+
 fn check_dependencies() {
     let deps = [
         ("ffmpeg", "video rendering/export", "sudo apt install ffmpeg"),
@@ -1020,22 +1448,30 @@ pub fn run() {
             )),
             history: std::sync::Mutex::new(forgecut_core::history::History::new(100)),
             mpv: std::sync::Mutex::new(forgecut_preview::mpv::MpvController::new()),
+            playback: std::sync::Mutex::new(forgecut_preview::playback::PlaybackController::new()),
+            compound_path: std::sync::Mutex::new(Vec::new()),
         })
         .invoke_handler(tauri::generate_handler![
             create_project,
             save_project,
             load_project,
             import_assets,
+            import_remote_asset,
             get_assets,
             remove_asset,
+            gc_assets,
             get_timeline,
             add_clip_to_timeline,
             init_default_tracks,
             trim_clip,
+            set_clip_speed,
             split_clip,
             delete_clip,
             move_clip,
             move_clip_to_track,
+            create_compound_from_selection,
+            enter_compound,
+            exit_compound,
             undo,
             redo,
             get_clip_at_playhead,
@@ -1055,6 +1491,8 @@ pub fn run() {
             get_autosave_path,
             get_clip_thumbnails,
             get_waveform,
+            generate_thumbnails,
+            generate_waveform,
             mpv_start,
             mpv_stop,
             mpv_load_file,
@@ -1065,6 +1503,9 @@ pub fn run() {
             mpv_update_geometry,
             mpv_hide,
             mpv_show,
+            start_preview,
+            seek_preview,
+            stop_preview,
         ])
         .setup(|app| {
             // Set GTK default icon so ALL windows (including file dialogs) show it
@@ -1,11 +1,17 @@
 use forgecut_core::history::History;
 use forgecut_core::types::Project;
 use forgecut_preview::mpv::MpvController;
+use forgecut_preview::playback::PlaybackController;
 use std::sync::Mutex;
+use uuid::Uuid;
 
 pub struct AppState {
     pub project: Mutex<Project>,
     pub history: Mutex<History>,
     pub media_server_port: u16,
     pub mpv: Mutex<MpvController>,
+    pub playback: Mutex<PlaybackController>,
+    /// The chain of `CompoundClip` ids the user has "entered" for editing,
+    /// outermost first. Empty means edits apply to the top-level timeline.
+    pub compound_path: Mutex<Vec<Uuid>>,
 }
@@ -1,38 +1,217 @@
 use crate::error::{CoreError, Result};
+use crate::media;
+use crate::snapping::{collect_snap_points, find_snap_point};
 use crate::types::*;
+use std::path::Path;
 use uuid::Uuid;
 
+/// The clip found underneath a playhead position, with the source seek
+/// offset resolved and its timeline bounds expressed in the coordinate
+/// frame of the [`Timeline`] that [`Timeline::resolve_clip_at`] was called
+/// on (even when the clip came from a nested `CompoundClip` sequence).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayheadClip {
+    pub asset_id: Uuid,
+    pub source_in_us: TimeUs,
+    pub seek_us: TimeUs,
+    pub clip_start_us: TimeUs,
+    pub clip_end_us: TimeUs,
+}
+
+/// Which tracks a ripple edit shifts downstream items on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RippleScope {
+    /// Only shift later items on the edited item's own track.
+    SameTrack,
+    /// Shift later items on every track in lockstep, mirroring how
+    /// Cinelerra's edit list keeps parallel-track positions synchronized
+    /// after a split/insert.
+    AllTracks,
+}
+
+/// The result of [`Timeline::resplice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpliceResult {
+    /// Index into the `new_items` list passed to `resplice` whose id matched
+    /// the playing item or its immediate successor -- the point the
+    /// unchanged old head was joined onto. `None` if no shared id was found,
+    /// in which case the entire new list was appended after the playing item
+    /// instead.
+    pub splice_point: Option<usize>,
+}
+
+/// How close a marker must be to an ideal evenly-spaced cut point for
+/// [`Timeline::split_into_segments`] to snap to it instead.
+const SEGMENT_SPLIT_MARKER_TOLERANCE_US: TimeUs = TimeUs(500_000);
+
 impl Timeline {
-    /// Add a clip/item to a track. Returns error if it would overlap existing items.
-    pub fn add_item(&mut self, track_id: Uuid, item: Item) -> Result<()> {
+    /// Add a clip/item to a track. Returns error if it would overlap existing
+    /// items, unless [`TimelineConfig::overlap_mode`] is
+    /// [`OverlapMode::Crossfade`] and the overlap is a simple tail/head
+    /// crossover, in which case it becomes a [`Transition`] instead.
+    pub fn add_item(&mut self, track_id: Uuid, mut item: Item) -> Result<()> {
+        let overlap_mode = self.config.overlap_mode;
         let track = self
             .tracks
             .iter_mut()
             .find(|t| t.id == track_id)
             .ok_or(CoreError::TrackNotFound(track_id))?;
 
-        for existing in &track.items {
-            if items_overlap(existing, &item) {
-                return Err(CoreError::OverlapDetected);
-            }
+        let overlapping: Vec<usize> = track
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, existing)| items_overlap(existing, &item))
+            .map(|(i, _)| i)
+            .collect();
+
+        if overlapping.is_empty() {
+            track.items.push(item);
+            return Ok(());
+        }
+
+        if overlapping.len() > 1 || overlap_mode == OverlapMode::Reject {
+            return Err(CoreError::OverlapDetected);
         }
 
+        let transition = apply_crossfade(&mut track.items[overlapping[0]], &mut item)
+            .ok_or(CoreError::OverlapDetected)?;
         track.items.push(item);
+        track.transitions.push(transition);
         Ok(())
     }
 
-    /// Remove an item by its id. Returns the removed item.
+    /// Add a `VideoClip` sourced from `path`, probing the file with
+    /// [`media::probe::probe_asset`] so `source_out_us` defaults to the
+    /// clip's full duration instead of requiring the caller to know its
+    /// length up front. `source_in_us` starts at zero; trim the returned
+    /// item afterwards (e.g. via [`trim_in`](Self::trim_in)) for a partial
+    /// clip. Returns the new item's id.
+    pub fn add_video_clip_from_path(
+        &mut self,
+        track_id: Uuid,
+        asset_id: Uuid,
+        path: &Path,
+        timeline_start_us: TimeUs,
+    ) -> Result<Uuid> {
+        let info = media::probe::probe_asset(path)?;
+        let id = Uuid::new_v4();
+        let item = Item::VideoClip {
+            id,
+            asset_id,
+            track_id,
+            timeline_start_us,
+            source_in_us: TimeUs::ZERO,
+            source_out_us: info.duration_us,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        self.add_item(track_id, item)?;
+        Ok(id)
+    }
+
+    /// Remove an item by its id. Returns the removed item. If the item
+    /// belongs to a [`Group`], every grouped sibling is removed along with
+    /// it and the group is dissolved, so an A/V pair never ends up with one
+    /// half orphaned on the timeline.
     pub fn remove_item(&mut self, item_id: Uuid) -> Result<Item> {
+        if let Some(group) = self.group_of(item_id).cloned() {
+            return self.remove_group(&group, item_id);
+        }
+
         for track in &mut self.tracks {
             if let Some(pos) = track.items.iter().position(|i| i.id() == item_id) {
+                drop_transitions_for(track, item_id);
                 return Ok(track.items.remove(pos));
             }
         }
         Err(CoreError::ItemNotFound(item_id))
     }
 
-    /// Move an item to a new timeline position. Checks for overlaps at new position.
-    pub fn move_item(&mut self, item_id: Uuid, new_start_us: TimeUs) -> Result<()> {
+    /// Explicitly create a crossfade [`Transition`] between two clips on the
+    /// same track that are already touching end-to-start, pulling
+    /// `right_id` left by `duration_us` to produce the overlap -- the same
+    /// shape [`OverlapMode::Crossfade`] turns into a transition when a drag
+    /// creates it by accident, but invoked directly. Fails if the clips
+    /// aren't adjacent, aren't on the same track, or `duration_us` doesn't
+    /// fit within both clips.
+    pub fn add_transition(
+        &mut self,
+        left_id: Uuid,
+        right_id: Uuid,
+        duration_us: TimeUs,
+    ) -> Result<()> {
+        let (track_idx, left_idx) = self
+            .find_item_location(left_id)
+            .ok_or(CoreError::ItemNotFound(left_id))?;
+        let (right_track_idx, right_idx) = self
+            .find_item_location(right_id)
+            .ok_or(CoreError::ItemNotFound(right_id))?;
+
+        if track_idx != right_track_idx {
+            return Err(CoreError::InvalidOperation(
+                "transition requires both clips on the same track".into(),
+            ));
+        }
+
+        let mut left_item = self.tracks[track_idx].items[left_idx].clone();
+        let mut right_item = self.tracks[track_idx].items[right_idx].clone();
+
+        if left_item.timeline_end_us() != right_item.timeline_start_us() {
+            return Err(CoreError::InvalidOperation(
+                "transition requires adjacent clips".into(),
+            ));
+        }
+
+        if duration_us <= TimeUs::ZERO
+            || duration_us > left_item.duration_us()
+            || duration_us > right_item.duration_us()
+        {
+            return Err(CoreError::InvalidOperation(
+                "transition duration must be positive and fit within both clips".into(),
+            ));
+        }
+
+        let new_right_start = TimeUs(right_item.timeline_start_us().0 - duration_us.0);
+        set_timeline_start(&mut right_item, new_right_start);
+
+        let transition = apply_crossfade(&mut left_item, &mut right_item)
+            .ok_or_else(|| CoreError::InvalidOperation("clips do not support crossfades".into()))?;
+
+        self.tracks[track_idx].items[left_idx] = left_item;
+        self.tracks[track_idx].items[right_idx] = right_item;
+        self.tracks[track_idx].transitions.push(transition);
+        Ok(())
+    }
+
+    /// Move an item to a new timeline position. Checks for overlaps at new
+    /// position. If the item belongs to a [`Group`], every grouped sibling
+    /// is shifted by the same delta, validated and applied as a single
+    /// transaction (rolled back entirely on overlap) -- like Kdenlive
+    /// moving an A/V group through the model rather than per-clip.
+    ///
+    /// If `snap_radius_us` is `Some`, `new_start_us` is first snapped to the
+    /// nearest other item edge or marker within that radius.
+    pub fn move_item(
+        &mut self,
+        item_id: Uuid,
+        new_start_us: TimeUs,
+        snap_radius_us: Option<TimeUs>,
+    ) -> Result<()> {
+        let new_start_us = self.snapped_position(item_id, new_start_us, snap_radius_us);
+
+        if let Some(group) = self.group_of(item_id).cloned() {
+            let (track_idx, item_idx) = self
+                .find_item_location(item_id)
+                .ok_or(CoreError::ItemNotFound(item_id))?;
+            let original_start = self.tracks[track_idx].items[item_idx].timeline_start_us();
+            let delta = TimeUs(new_start_us.0 - original_start.0);
+            return self.shift_items_by_ids(&group.item_ids, delta);
+        }
+
+        let overlap_mode = self.config.overlap_mode;
+
         // Find and temporarily remove the item
         let (track_idx, item_idx) = self
             .find_item_location(item_id)
@@ -45,36 +224,65 @@ impl Timeline {
         set_timeline_start(&mut item, new_start_us);
 
         // Check for overlaps with remaining items on the same track
-        for existing in &self.tracks[track_idx].items {
-            if items_overlap(existing, &item) {
-                // Rollback: restore original position and re-insert
-                set_timeline_start(&mut item, original_start);
-                self.tracks[track_idx].items.insert(item_idx, item);
-                return Err(CoreError::OverlapDetected);
+        let overlapping: Vec<usize> = self.tracks[track_idx]
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, existing)| items_overlap(existing, &item))
+            .map(|(i, _)| i)
+            .collect();
+
+        if overlapping.is_empty() {
+            self.tracks[track_idx].items.push(item);
+            return Ok(());
+        }
+
+        if overlapping.len() == 1 && overlap_mode == OverlapMode::Crossfade {
+            if let Some(transition) =
+                apply_crossfade(&mut self.tracks[track_idx].items[overlapping[0]], &mut item)
+            {
+                self.tracks[track_idx].items.push(item);
+                self.tracks[track_idx].transitions.push(transition);
+                return Ok(());
             }
         }
 
-        self.tracks[track_idx].items.push(item);
-        Ok(())
+        // Rollback: restore original position and re-insert
+        set_timeline_start(&mut item, original_start);
+        self.tracks[track_idx].items.insert(item_idx, item);
+        Err(CoreError::OverlapDetected)
     }
 
     /// Trim the in-point of a clip.
     /// For VideoClip/AudioClip: new_in_us is the new source_in_us. Adjusts timeline_start_us
     /// so the end position stays the same. Validates source_in < source_out.
     /// For overlays: adjusts timeline_start_us and duration_us to keep end fixed.
-    pub fn trim_in(&mut self, item_id: Uuid, new_in_us: TimeUs) -> Result<()> {
+    /// If the item belongs to a [`Group`], every grouped sibling is shifted
+    /// by the resulting timeline_start delta so the group stays in sync.
+    ///
+    /// If `snap_radius_us` is `Some`, `new_in_us` is first snapped to the
+    /// nearest other item edge or marker within that radius.
+    pub fn trim_in(
+        &mut self,
+        item_id: Uuid,
+        new_in_us: TimeUs,
+        snap_radius_us: Option<TimeUs>,
+    ) -> Result<()> {
+        let new_in_us = self.snapped_position(item_id, new_in_us, snap_radius_us);
         let (track_idx, item_idx) = self
             .find_item_location(item_id)
             .ok_or(CoreError::ItemNotFound(item_id))?;
 
         let item = &mut self.tracks[track_idx].items[item_idx];
         let original_end = item.timeline_end_us();
+        let original_start = item.timeline_start_us();
 
         match item {
             Item::VideoClip {
                 source_in_us,
                 source_out_us,
                 timeline_start_us,
+                speed,
                 ..
             } => {
                 if new_in_us >= *source_out_us {
@@ -84,13 +292,17 @@ impl Timeline {
                 }
                 *source_in_us = new_in_us;
                 // Adjust timeline_start so end stays the same:
-                // new_start = old_end - new_duration
-                *timeline_start_us = TimeUs(original_end.0 - (source_out_us.0 - new_in_us.0));
+                // new_start = old_end - new_duration, where new_duration is the
+                // source-time span mapped through the playback speed.
+                let new_duration_us =
+                    (((source_out_us.0 - new_in_us.0) as f64) / *speed).round() as i64;
+                *timeline_start_us = TimeUs(original_end.0 - new_duration_us);
             }
             Item::AudioClip {
                 source_in_us,
                 source_out_us,
                 timeline_start_us,
+                speed,
                 ..
             } => {
                 if new_in_us >= *source_out_us {
@@ -99,7 +311,9 @@ impl Timeline {
                     ));
                 }
                 *source_in_us = new_in_us;
-                *timeline_start_us = TimeUs(original_end.0 - (source_out_us.0 - new_in_us.0));
+                let new_duration_us =
+                    (((source_out_us.0 - new_in_us.0) as f64) / *speed).round() as i64;
+                *timeline_start_us = TimeUs(original_end.0 - new_duration_us);
             }
             Item::ImageOverlay {
                 timeline_start_us,
@@ -128,44 +342,196 @@ impl Timeline {
                 *duration_us = TimeUs(original_end.0 - new_in_us.0);
                 *timeline_start_us = new_in_us;
             }
+            Item::CompoundClip {
+                source_in_us,
+                source_out_us,
+                timeline_start_us,
+                ..
+            } => {
+                if new_in_us >= *source_out_us {
+                    return Err(CoreError::InvalidOperation(
+                        "source_in must be less than source_out".into(),
+                    ));
+                }
+                *source_in_us = new_in_us;
+                *timeline_start_us = TimeUs(original_end.0 - (source_out_us.0 - new_in_us.0));
+            }
+        }
+
+        // If this item is grouped, shift every sibling by the same
+        // timeline_start delta so the group stays in sync. Validated and
+        // committed as a single transaction -- unlike the trim above, this
+        // part rolls back cleanly on overlap since none of the siblings
+        // have been touched yet.
+        let new_start = self.tracks[track_idx].items[item_idx].timeline_start_us();
+        let delta = TimeUs(new_start.0 - original_start.0);
+        let group_siblings: Vec<Uuid> = self
+            .group_of(item_id)
+            .map(|g| {
+                g.item_ids
+                    .iter()
+                    .copied()
+                    .filter(|&id| id != item_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !group_siblings.is_empty() {
+            self.shift_items_by_ids(&group_siblings, delta)?;
         }
 
-        // Check overlaps after trim
+        // Check overlaps after trim, ignoring overlaps with items already
+        // linked to this one by a transition (handled below by
+        // shrink_transitions_for) or grouped siblings (shifted in lockstep
+        // above).
         let item_clone = self.tracks[track_idx].items[item_idx].clone();
+        let transition_partners = transition_partners_of(&self.tracks[track_idx], item_id);
         for (i, existing) in self.tracks[track_idx].items.iter().enumerate() {
-            if i != item_idx && items_overlap(existing, &item_clone) {
+            if i != item_idx
+                && !transition_partners.contains(&existing.id())
+                && !group_siblings.contains(&existing.id())
+                && items_overlap(existing, &item_clone)
+            {
                 // We don't rollback trim_in for simplicity -- caller should check beforehand
                 return Err(CoreError::OverlapDetected);
             }
         }
 
+        shrink_transitions_for(&mut self.tracks[track_idx], item_id);
         Ok(())
     }
 
     /// Trim the out-point of a clip.
     /// For VideoClip/AudioClip: new_out_us is the new source_out_us. Validates source_in < source_out.
     /// For overlays: adjusts duration_us.
-    pub fn trim_out(&mut self, item_id: Uuid, new_out_us: TimeUs) -> Result<()> {
+    /// If the item belongs to a [`Group`], every grouped sibling has its own
+    /// out-point trimmed by the same resulting end-time delta, validated
+    /// atomically so a sibling overlap rolls back the whole trim.
+    ///
+    /// If `snap_radius_us` is `Some`, `new_out_us` is first snapped to the
+    /// nearest other item edge or marker within that radius.
+    pub fn trim_out(
+        &mut self,
+        item_id: Uuid,
+        new_out_us: TimeUs,
+        snap_radius_us: Option<TimeUs>,
+    ) -> Result<()> {
+        let new_out_us = self.snapped_position(item_id, new_out_us, snap_radius_us);
+
+        if let Some(group) = self.group_of(item_id).cloned() {
+            let (track_idx, item_idx) = self
+                .find_item_location(item_id)
+                .ok_or(CoreError::ItemNotFound(item_id))?;
+            let original_end = self.tracks[track_idx].items[item_idx].timeline_end_us();
+            let delta = TimeUs(new_out_us.0 - original_end.0);
+            self.trim_out_group_by_delta(&group.item_ids, delta)?;
+            shrink_transitions_for(&mut self.tracks[track_idx], item_id);
+            return Ok(());
+        }
+
+        let (track_idx, item_idx) = self
+            .find_item_location(item_id)
+            .ok_or(CoreError::ItemNotFound(item_id))?;
+
+        apply_trim_out(&mut self.tracks[track_idx].items[item_idx], new_out_us)?;
+
+        // Check overlaps after trim, ignoring overlaps with items already
+        // linked to this one by a transition -- those are handled below by
+        // shrink_transitions_for instead of being rejected.
+        let item_clone = self.tracks[track_idx].items[item_idx].clone();
+        let transition_partners = transition_partners_of(&self.tracks[track_idx], item_id);
+        for (i, existing) in self.tracks[track_idx].items.iter().enumerate() {
+            if i != item_idx
+                && !transition_partners.contains(&existing.id())
+                && items_overlap(existing, &item_clone)
+            {
+                return Err(CoreError::OverlapDetected);
+            }
+        }
+
+        shrink_transitions_for(&mut self.tracks[track_idx], item_id);
+        Ok(())
+    }
+
+    /// Change a clip's playback speed. `timeline_start_us` stays fixed and
+    /// `timeline_end_us` shortens or lengthens to match, since
+    /// [`Item::duration_us`] divides the unchanged source range by `speed`.
+    /// Only [`Item::VideoClip`] and [`Item::AudioClip`] carry a speed; any
+    /// other item kind is rejected.
+    pub fn set_speed(&mut self, item_id: Uuid, new_speed: f64) -> Result<()> {
+        if !new_speed.is_finite() || new_speed <= 0.0 {
+            return Err(CoreError::InvalidOperation(
+                "speed must be a positive, finite number".into(),
+            ));
+        }
+
+        let (track_idx, item_idx) = self
+            .find_item_location(item_id)
+            .ok_or(CoreError::ItemNotFound(item_id))?;
+
+        match &mut self.tracks[track_idx].items[item_idx] {
+            Item::VideoClip { speed, .. } | Item::AudioClip { speed, .. } => {
+                *speed = new_speed;
+            }
+            Item::ImageOverlay { .. } | Item::TextOverlay { .. } | Item::CompoundClip { .. } => {
+                return Err(CoreError::InvalidOperation(
+                    "only VideoClip and AudioClip items have a playback speed".into(),
+                ));
+            }
+        }
+
+        // Check overlaps after the duration change, ignoring overlaps with
+        // items already linked to this one by a transition -- those are
+        // handled below by shrink_transitions_for instead of being rejected.
+        // We don't rollback the speed change for simplicity -- caller should
+        // check beforehand.
+        let item_clone = self.tracks[track_idx].items[item_idx].clone();
+        let transition_partners = transition_partners_of(&self.tracks[track_idx], item_id);
+        for (i, existing) in self.tracks[track_idx].items.iter().enumerate() {
+            if i != item_idx
+                && !transition_partners.contains(&existing.id())
+                && items_overlap(existing, &item_clone)
+            {
+                return Err(CoreError::OverlapDetected);
+            }
+        }
+
+        shrink_transitions_for(&mut self.tracks[track_idx], item_id);
+        Ok(())
+    }
+
+    /// Ripple trim: same as [`trim_out`](Self::trim_out), except instead of
+    /// rejecting the overlap the new duration would create downstream, every
+    /// item starting at or after the clip's original end is shifted by the
+    /// resulting signed delta so no gap opens and no overlap forms. The edit
+    /// is rejected -- and the clip's own trim rolled back -- if shifting
+    /// would push a downstream start below [`TimeUs::ZERO`] or recreate an
+    /// overlap.
+    pub fn ripple_trim_out(
+        &mut self,
+        item_id: Uuid,
+        new_out_us: TimeUs,
+        scope: RippleScope,
+    ) -> Result<()> {
         let (track_idx, item_idx) = self
             .find_item_location(item_id)
             .ok_or(CoreError::ItemNotFound(item_id))?;
 
+        let original_item = self.tracks[track_idx].items[item_idx].clone();
         let item = &mut self.tracks[track_idx].items[item_idx];
+        let old_end = item.timeline_end_us();
 
         match item {
             Item::VideoClip {
                 source_in_us,
                 source_out_us,
                 ..
-            } => {
-                if new_out_us <= *source_in_us {
-                    return Err(CoreError::InvalidOperation(
-                        "source_out must be greater than source_in".into(),
-                    ));
-                }
-                *source_out_us = new_out_us;
             }
-            Item::AudioClip {
+            | Item::AudioClip {
+                source_in_us,
+                source_out_us,
+                ..
+            }
+            | Item::CompoundClip {
                 source_in_us,
                 source_out_us,
                 ..
@@ -181,16 +547,8 @@ impl Timeline {
                 timeline_start_us,
                 duration_us,
                 ..
-            } => {
-                let new_dur = TimeUs(new_out_us.0 - timeline_start_us.0);
-                if new_dur.0 <= 0 {
-                    return Err(CoreError::InvalidOperation(
-                        "new out must be after start".into(),
-                    ));
-                }
-                *duration_us = new_dur;
             }
-            Item::TextOverlay {
+            | Item::TextOverlay {
                 timeline_start_us,
                 duration_us,
                 ..
@@ -205,122 +563,497 @@ impl Timeline {
             }
         }
 
-        // Check overlaps after trim
-        let item_clone = self.tracks[track_idx].items[item_idx].clone();
-        for (i, existing) in self.tracks[track_idx].items.iter().enumerate() {
-            if i != item_idx && items_overlap(existing, &item_clone) {
-                return Err(CoreError::OverlapDetected);
-            }
+        let new_end = self.tracks[track_idx].items[item_idx].timeline_end_us();
+        let delta = TimeUs(new_end.0 - old_end.0);
+        if let Err(e) = self.shift_items_after(track_idx, old_end, delta, scope, None) {
+            self.tracks[track_idx].items[item_idx] = original_item;
+            return Err(e);
         }
-
+        shrink_transitions_for(&mut self.tracks[track_idx], item_id);
         Ok(())
     }
 
-    /// Split an item at a given timeline position into two items.
-    /// The position must be strictly between start and end.
-    /// Returns the IDs of (left, right) items.
-    pub fn split_at(&mut self, item_id: Uuid, split_time_us: TimeUs) -> Result<(Uuid, Uuid)> {
+    /// Ripple trim: same as [`trim_in`](Self::trim_in), except instead of
+    /// rejecting the overlap the new start would create downstream, every
+    /// later item on the track is shifted by the resulting signed delta so
+    /// no gap opens and no overlap forms. Rejected under the same conditions
+    /// as [`ripple_trim_out`](Self::ripple_trim_out).
+    pub fn ripple_trim_in(
+        &mut self,
+        item_id: Uuid,
+        new_in_us: TimeUs,
+        scope: RippleScope,
+    ) -> Result<()> {
         let (track_idx, item_idx) = self
             .find_item_location(item_id)
             .ok_or(CoreError::ItemNotFound(item_id))?;
 
-        let item = &self.tracks[track_idx].items[item_idx];
-        let start = item.timeline_start_us();
-        let end = item.timeline_end_us();
-
-        if split_time_us <= start || split_time_us >= end {
-            return Err(CoreError::InvalidOperation(
-                "split position must be strictly between item start and end".into(),
-            ));
-        }
-
-        let right_id = Uuid::new_v4();
-        let left_id = item.id();
+        let original_item = self.tracks[track_idx].items[item_idx].clone();
+        let item = &mut self.tracks[track_idx].items[item_idx];
+        let original_end = item.timeline_end_us();
+        let original_start = item.timeline_start_us();
 
-        let (left, right) = match item.clone() {
+        match item {
             Item::VideoClip {
-                id,
-                asset_id,
-                track_id,
+                source_in_us,
+                source_out_us,
                 timeline_start_us,
+                speed,
+                ..
+            }
+            | Item::AudioClip {
                 source_in_us,
                 source_out_us,
+                timeline_start_us,
+                speed,
+                ..
             } => {
-                // Time elapsed from start to split point
-                let offset = TimeUs(split_time_us.0 - timeline_start_us.0);
-                let split_source = TimeUs(source_in_us.0 + offset.0);
-
-                let left = Item::VideoClip {
-                    id,
-                    asset_id,
-                    track_id,
-                    timeline_start_us,
-                    source_in_us,
-                    source_out_us: split_source,
-                };
-                let right = Item::VideoClip {
-                    id: right_id,
-                    asset_id,
-                    track_id,
-                    timeline_start_us: split_time_us,
-                    source_in_us: split_source,
-                    source_out_us,
-                };
-                (left, right)
+                if new_in_us >= *source_out_us {
+                    return Err(CoreError::InvalidOperation(
+                        "source_in must be less than source_out".into(),
+                    ));
+                }
+                *source_in_us = new_in_us;
+                let new_duration_us =
+                    (((source_out_us.0 - new_in_us.0) as f64) / *speed).round() as i64;
+                *timeline_start_us = TimeUs(original_end.0 - new_duration_us);
             }
-            Item::AudioClip {
-                id,
-                asset_id,
-                track_id,
-                timeline_start_us,
+            Item::CompoundClip {
                 source_in_us,
                 source_out_us,
-                volume,
+                timeline_start_us,
+                ..
             } => {
-                let offset = TimeUs(split_time_us.0 - timeline_start_us.0);
-                let split_source = TimeUs(source_in_us.0 + offset.0);
-
-                let left = Item::AudioClip {
-                    id,
-                    asset_id,
-                    track_id,
-                    timeline_start_us,
-                    source_in_us,
-                    source_out_us: split_source,
-                    volume,
-                };
-                let right = Item::AudioClip {
-                    id: right_id,
-                    asset_id,
-                    track_id,
-                    timeline_start_us: split_time_us,
-                    source_in_us: split_source,
-                    source_out_us,
-                    volume,
-                };
-                (left, right)
+                if new_in_us >= *source_out_us {
+                    return Err(CoreError::InvalidOperation(
+                        "source_in must be less than source_out".into(),
+                    ));
+                }
+                *source_in_us = new_in_us;
+                *timeline_start_us = TimeUs(original_end.0 - (source_out_us.0 - new_in_us.0));
             }
             Item::ImageOverlay {
-                id,
-                asset_id,
-                track_id,
                 timeline_start_us,
-                duration_us: _,
-                x,
-                y,
-                width,
-                height,
-                opacity,
+                duration_us,
+                ..
+            }
+            | Item::TextOverlay {
+                timeline_start_us,
+                duration_us,
+                ..
             } => {
-                let left_dur = TimeUs(split_time_us.0 - timeline_start_us.0);
-                let right_dur = TimeUs(end.0 - split_time_us.0);
+                if new_in_us >= original_end {
+                    return Err(CoreError::InvalidOperation(
+                        "new start must be before end".into(),
+                    ));
+                }
+                *duration_us = TimeUs(original_end.0 - new_in_us.0);
+                *timeline_start_us = new_in_us;
+            }
+        }
 
-                let left = Item::ImageOverlay {
-                    id,
-                    asset_id,
-                    track_id,
-                    timeline_start_us,
-                    duration_us: left_dur,
+        let new_start = self.tracks[track_idx].items[item_idx].timeline_start_us();
+        let delta = TimeUs(new_start.0 - original_start.0);
+        if let Err(e) =
+            self.shift_items_after(track_idx, original_start, delta, scope, Some(item_id))
+        {
+            self.tracks[track_idx].items[item_idx] = original_item;
+            return Err(e);
+        }
+        shrink_transitions_for(&mut self.tracks[track_idx], item_id);
+        Ok(())
+    }
+
+    /// Remove an item and pull every item starting at or after its original
+    /// end left by its duration, closing the gap it would otherwise leave
+    /// behind -- the validated form of [`ripple_delete`](Self::ripple_delete),
+    /// which now delegates here. Rejected under the same conditions as
+    /// [`ripple_trim_out`](Self::ripple_trim_out).
+    pub fn ripple_remove_item(&mut self, item_id: Uuid, scope: RippleScope) -> Result<Item> {
+        let (track_idx, item_idx) = self
+            .find_item_location(item_id)
+            .ok_or(CoreError::ItemNotFound(item_id))?;
+
+        drop_transitions_for(&mut self.tracks[track_idx], item_id);
+        let removed = self.tracks[track_idx].items.remove(item_idx);
+        let old_end = removed.timeline_end_us();
+        let delta = TimeUs(-removed.duration_us().0);
+
+        if let Err(e) = self.shift_items_after(track_idx, old_end, delta, scope, None) {
+            self.tracks[track_idx].items.insert(item_idx, removed);
+            return Err(e);
+        }
+        Ok(removed)
+    }
+
+    /// Ripple delete: remove an item and pull every item starting at or
+    /// after its original end left by its duration, closing the gap it
+    /// would otherwise leave behind.
+    pub fn ripple_delete(&mut self, item_id: Uuid, scope: RippleScope) -> Result<Item> {
+        self.ripple_remove_item(item_id, scope)
+    }
+
+    /// Roll edit: move the boundary between two adjacent clips on the same
+    /// track by `delta` (positive extends `left_item_id` into
+    /// `right_item_id`'s span, negative the reverse), holding their combined
+    /// timeline span fixed. `delta` is rejected rather than silently
+    /// clamped if it would cross either clip's own source limits
+    /// (`source_in < source_out`), consistent with how [`trim_in`] and
+    /// [`trim_out`] reject invalid trims.
+    ///
+    /// [`trim_in`]: Self::trim_in
+    /// [`trim_out`]: Self::trim_out
+    pub fn roll_edit(
+        &mut self,
+        left_item_id: Uuid,
+        right_item_id: Uuid,
+        delta: TimeUs,
+    ) -> Result<()> {
+        let (left_track, left_idx) = self
+            .find_item_location(left_item_id)
+            .ok_or(CoreError::ItemNotFound(left_item_id))?;
+        let (right_track, right_idx) = self
+            .find_item_location(right_item_id)
+            .ok_or(CoreError::ItemNotFound(right_item_id))?;
+
+        if left_track != right_track {
+            return Err(CoreError::InvalidOperation(
+                "roll_edit requires both clips to be on the same track".into(),
+            ));
+        }
+
+        let left = &self.tracks[left_track].items[left_idx];
+        let right = &self.tracks[right_track].items[right_idx];
+        if left.timeline_end_us() != right.timeline_start_us() {
+            return Err(CoreError::InvalidOperation(
+                "roll_edit requires adjacent clips sharing a boundary".into(),
+            ));
+        }
+        if left.duration_us().0 + delta.0 <= 0 || right.duration_us().0 - delta.0 <= 0 {
+            return Err(CoreError::InvalidOperation(
+                "roll edit would invert one of the clips' source ranges".into(),
+            ));
+        }
+
+        if delta.0 == 0 {
+            return Ok(());
+        }
+
+        roll_extend_out(&mut self.tracks[left_track].items[left_idx], delta)?;
+        roll_trim_in(&mut self.tracks[right_track].items[right_idx], delta)?;
+        Ok(())
+    }
+
+    /// Shift every item whose `timeline_start_us` is at or after `boundary`
+    /// by `delta`: only on `track_idx`'s track for
+    /// [`RippleScope::SameTrack`], or across every track for
+    /// [`RippleScope::AllTracks`] so parallel tracks stay synchronized.
+    /// `exclude_id`, if given, is skipped even if its own position satisfies
+    /// `boundary` -- needed by [`ripple_trim_in`](Self::ripple_trim_in),
+    /// whose edited item's already-updated start can land at or past its own
+    /// boundary. Re-validates the no-overlap and non-negative-start
+    /// invariants on every touched track afterward, rolling the shift back
+    /// and returning [`CoreError::OverlapDetected`] if either is violated.
+    fn shift_items_after(
+        &mut self,
+        track_idx: usize,
+        boundary: TimeUs,
+        delta: TimeUs,
+        scope: RippleScope,
+        exclude_id: Option<Uuid>,
+    ) -> Result<()> {
+        if delta.0 == 0 {
+            return Ok(());
+        }
+
+        let track_indices: Vec<usize> = match scope {
+            RippleScope::SameTrack => vec![track_idx],
+            RippleScope::AllTracks => (0..self.tracks.len()).collect(),
+        };
+
+        let mut shifted: Vec<(usize, Uuid)> = Vec::new();
+        for &ti in &track_indices {
+            for item in &mut self.tracks[ti].items {
+                if Some(item.id()) != exclude_id && item.timeline_start_us() >= boundary {
+                    let new_start = TimeUs(item.timeline_start_us().0 + delta.0);
+                    set_timeline_start(item, new_start);
+                    shifted.push((ti, item.id()));
+                }
+            }
+        }
+
+        if track_indices
+            .iter()
+            .any(|&ti| !track_is_valid(&self.tracks[ti]))
+        {
+            for (ti, id) in shifted {
+                if let Some(item) = self.tracks[ti].items.iter_mut().find(|i| i.id() == id) {
+                    let rolled_back = TimeUs(item.timeline_start_us().0 - delta.0);
+                    set_timeline_start(item, rolled_back);
+                }
+            }
+            return Err(CoreError::OverlapDetected);
+        }
+
+        Ok(())
+    }
+
+    /// Insert an item into a track at `insert_at_us`, rippling every later
+    /// item on that track right by the inserted item's duration so nothing
+    /// is overwritten -- a live-assembly insert for on-air editing, distinct
+    /// from the overlap-rejecting [`add_item`](Self::add_item). Rejected if
+    /// `insert_at_us` lands strictly inside an existing item's span: only
+    /// items starting at or after the insertion point get rippled, so
+    /// splicing into the middle of a clip would otherwise leave it straddled
+    /// by and overlapping the newly inserted item.
+    pub fn splice_insert(
+        &mut self,
+        track_id: Uuid,
+        mut item: Item,
+        insert_at_us: TimeUs,
+    ) -> Result<Uuid> {
+        let track = self
+            .tracks
+            .iter_mut()
+            .find(|t| t.id == track_id)
+            .ok_or(CoreError::TrackNotFound(track_id))?;
+
+        if track.items.iter().any(|existing| {
+            insert_at_us > existing.timeline_start_us() && insert_at_us < existing.timeline_end_us()
+        }) {
+            return Err(CoreError::OverlapDetected);
+        }
+
+        let delta = item.duration_us();
+        for existing in &mut track.items {
+            if existing.timeline_start_us() >= insert_at_us {
+                let new_start = TimeUs(existing.timeline_start_us().0 + delta.0);
+                set_timeline_start(existing, new_start);
+            }
+        }
+
+        set_timeline_start(&mut item, insert_at_us);
+        let item_id = item.id();
+        track.items.push(item);
+        Ok(item_id)
+    }
+
+    /// Merge a freshly-authored sequence into a track without disturbing
+    /// whatever is "current", borrowing Futatabi's splice algorithm: find
+    /// the last entry in `new_items` whose id matches the item at
+    /// `playing_index` or its immediate successor, and use it as the splice
+    /// point. Items up to and including the playing one are kept unchanged;
+    /// the track's tail is replaced with the `new_items` suffix starting
+    /// just after the splice point, repositioned (preserving its own
+    /// internal spacing) to start right where the unchanged head ends. If no
+    /// shared id exists, the entire `new_items` list is appended after the
+    /// playing item instead. Returns the chosen splice point so callers can
+    /// tell which edits took effect immediately versus after the current
+    /// clip finishes.
+    pub fn resplice(
+        &mut self,
+        track_id: Uuid,
+        playing_index: usize,
+        new_items: Vec<Item>,
+    ) -> Result<SpliceResult> {
+        let track = self
+            .tracks
+            .iter_mut()
+            .find(|t| t.id == track_id)
+            .ok_or(CoreError::TrackNotFound(track_id))?;
+
+        if playing_index >= track.items.len() {
+            return Err(CoreError::InvalidOperation(
+                "playing_index is out of bounds for this track".into(),
+            ));
+        }
+
+        let playing_id = track.items[playing_index].id();
+        let successor_id = track.items.get(playing_index + 1).map(|i| i.id());
+
+        let splice_point = new_items
+            .iter()
+            .rposition(|i| i.id() == playing_id || Some(i.id()) == successor_id);
+
+        let mut head: Vec<Item> = track.items.drain(..=playing_index).collect();
+        let join_at = head
+            .last()
+            .expect("playing_index is in bounds")
+            .timeline_end_us();
+
+        let mut tail: Vec<Item> = match splice_point {
+            Some(j) => new_items.into_iter().skip(j + 1).collect(),
+            None => new_items,
+        };
+
+        if let Some(min_start) = tail.iter().map(|i| i.timeline_start_us()).min() {
+            let offset = join_at.0 - min_start.0;
+            for item in &mut tail {
+                let new_start = TimeUs(item.timeline_start_us().0 + offset);
+                set_timeline_start(item, new_start);
+            }
+        }
+
+        head.extend(tail);
+        track.items = head;
+
+        Ok(SpliceResult { splice_point })
+    }
+
+    /// Split an item at a given timeline position into two items.
+    /// The position must be strictly between start and end.
+    /// Returns the IDs of (left, right) items.
+    /// If the item belongs to a [`Group`], every grouped sibling is split at
+    /// the same position too (validated up front, before any of them are
+    /// touched) and the right-hand pieces are placed into a new group,
+    /// while the original group keeps the left-hand pieces.
+    ///
+    /// If `snap_radius_us` is `Some`, `split_time_us` is first snapped to
+    /// the nearest other item edge or marker within that radius.
+    pub fn split_at(
+        &mut self,
+        item_id: Uuid,
+        split_time_us: TimeUs,
+        snap_radius_us: Option<TimeUs>,
+    ) -> Result<(Uuid, Uuid)> {
+        let split_time_us = self.snapped_position(item_id, split_time_us, snap_radius_us);
+        if let Some(group) = self.group_of(item_id).cloned() {
+            return self.split_group_at(&group, item_id, split_time_us);
+        }
+        self.split_at_with_right_id(item_id, split_time_us, Uuid::new_v4())
+    }
+
+    /// Same as [`split_at`](Self::split_at), but with the right half's id
+    /// supplied rather than generated. Lets a recorded `EditCommand::Split`
+    /// replay deterministically instead of minting a fresh random id.
+    pub(crate) fn split_at_with_right_id(
+        &mut self,
+        item_id: Uuid,
+        split_time_us: TimeUs,
+        right_id: Uuid,
+    ) -> Result<(Uuid, Uuid)> {
+        let (track_idx, item_idx) = self
+            .find_item_location(item_id)
+            .ok_or(CoreError::ItemNotFound(item_id))?;
+
+        let item = &self.tracks[track_idx].items[item_idx];
+        let start = item.timeline_start_us();
+        let end = item.timeline_end_us();
+
+        if split_time_us <= start || split_time_us >= end {
+            return Err(CoreError::InvalidOperation(
+                "split position must be strictly between item start and end".into(),
+            ));
+        }
+
+        let left_id = item.id();
+
+        let (left, right) = match item.clone() {
+            Item::VideoClip {
+                id,
+                asset_id,
+                track_id,
+                timeline_start_us,
+                source_in_us,
+                source_out_us,
+                speed,
+                fade_in_us,
+                fade_out_us,
+            } => {
+                // Time elapsed from start to split point, mapped from
+                // timeline time back into source time through the speed
+                // factor so both halves still reference contiguous media.
+                let offset = TimeUs(split_time_us.0 - timeline_start_us.0);
+                let source_offset = ((offset.0 as f64) * speed).round() as i64;
+                let split_source = TimeUs(source_in_us.0 + source_offset);
+
+                let left = Item::VideoClip {
+                    id,
+                    asset_id,
+                    track_id,
+                    timeline_start_us,
+                    source_in_us,
+                    source_out_us: split_source,
+                    speed,
+                    fade_in_us,
+                    fade_out_us: TimeUs::ZERO,
+                };
+                let right = Item::VideoClip {
+                    id: right_id,
+                    asset_id,
+                    track_id,
+                    timeline_start_us: split_time_us,
+                    source_in_us: split_source,
+                    source_out_us,
+                    speed,
+                    fade_in_us: TimeUs::ZERO,
+                    fade_out_us,
+                };
+                (left, right)
+            }
+            Item::AudioClip {
+                id,
+                asset_id,
+                track_id,
+                timeline_start_us,
+                source_in_us,
+                source_out_us,
+                volume,
+                speed,
+                fade_in_us,
+                fade_out_us,
+            } => {
+                let offset = TimeUs(split_time_us.0 - timeline_start_us.0);
+                let source_offset = ((offset.0 as f64) * speed).round() as i64;
+                let split_source = TimeUs(source_in_us.0 + source_offset);
+
+                let left = Item::AudioClip {
+                    id,
+                    asset_id,
+                    track_id,
+                    timeline_start_us,
+                    source_in_us,
+                    source_out_us: split_source,
+                    volume,
+                    speed,
+                    fade_in_us,
+                    fade_out_us: TimeUs::ZERO,
+                };
+                let right = Item::AudioClip {
+                    id: right_id,
+                    asset_id,
+                    track_id,
+                    timeline_start_us: split_time_us,
+                    source_in_us: split_source,
+                    source_out_us,
+                    volume,
+                    speed,
+                    fade_in_us: TimeUs::ZERO,
+                    fade_out_us,
+                };
+                (left, right)
+            }
+            Item::ImageOverlay {
+                id,
+                asset_id,
+                track_id,
+                timeline_start_us,
+                duration_us: _,
+                x,
+                y,
+                width,
+                height,
+                opacity,
+            } => {
+                let left_dur = TimeUs(split_time_us.0 - timeline_start_us.0);
+                let right_dur = TimeUs(end.0 - split_time_us.0);
+
+                let left = Item::ImageOverlay {
+                    id,
+                    asset_id,
+                    track_id,
+                    timeline_start_us,
+                    duration_us: left_dur,
                     x,
                     y,
                     width,
@@ -379,15 +1112,112 @@ impl Timeline {
                 };
                 (left, right)
             }
+            Item::CompoundClip {
+                id,
+                track_id,
+                timeline_start_us,
+                source_in_us,
+                source_out_us,
+                sequence,
+            } => {
+                let offset = TimeUs(split_time_us.0 - timeline_start_us.0);
+                let split_source = TimeUs(source_in_us.0 + offset.0);
+
+                let left = Item::CompoundClip {
+                    id,
+                    track_id,
+                    timeline_start_us,
+                    source_in_us,
+                    source_out_us: split_source,
+                    sequence: sequence.clone(),
+                };
+                let right = Item::CompoundClip {
+                    id: right_id,
+                    track_id,
+                    timeline_start_us: split_time_us,
+                    source_in_us: split_source,
+                    source_out_us,
+                    sequence,
+                };
+                (left, right)
+            }
         };
 
         // Replace original with left, insert right after it
         self.tracks[track_idx].items[item_idx] = left;
         self.tracks[track_idx].items.insert(item_idx + 1, right);
 
+        // The split item's old tail/head boundaries no longer exist as they
+        // did, so any transition it participated in is dropped rather than
+        // retargeted to whichever half now owns that edge.
+        drop_transitions_for(&mut self.tracks[track_idx], left_id);
+
         Ok((left_id, right_id))
     }
 
+    /// Repeatedly split `item_id` so no resulting piece exceeds
+    /// `max_duration_us`, the way Av1an's `extra_splits` breaks an over-long
+    /// scene into bounded chunks. Each cut point is first snapped to the
+    /// nearest marker inside the remaining span within
+    /// [`SEGMENT_SPLIT_MARKER_TOLERANCE_US`], falling back to an evenly
+    /// spaced cut if no marker is close enough; a trailing remainder shorter
+    /// than `max_duration_us` is kept whole rather than split again, so no
+    /// zero-length segment is ever produced. Returns the ids of the
+    /// resulting pieces in timeline order, the first of which is `item_id`
+    /// itself.
+    pub fn split_into_segments(
+        &mut self,
+        item_id: Uuid,
+        max_duration_us: TimeUs,
+    ) -> Result<Vec<Uuid>> {
+        if max_duration_us <= TimeUs::ZERO {
+            return Err(CoreError::InvalidOperation(
+                "max_duration_us must be positive".into(),
+            ));
+        }
+
+        let mut pieces = Vec::new();
+        let mut current_id = item_id;
+
+        loop {
+            let (track_idx, item_idx) = self
+                .find_item_location(current_id)
+                .ok_or(CoreError::ItemNotFound(current_id))?;
+            let item = &self.tracks[track_idx].items[item_idx];
+            let start = item.timeline_start_us();
+            let end = item.timeline_end_us();
+
+            if TimeUs(end.0 - start.0) <= max_duration_us {
+                pieces.push(current_id);
+                break;
+            }
+
+            let ideal_cut = TimeUs(start.0 + max_duration_us.0);
+            let markers_in_range: Vec<TimeUs> = self
+                .markers
+                .iter()
+                .map(|m| m.time_us)
+                .filter(|&t| t > start && t < end)
+                .collect();
+            let snapped = find_snap_point(
+                ideal_cut,
+                &markers_in_range,
+                SEGMENT_SPLIT_MARKER_TOLERANCE_US,
+            );
+            let cut = if snapped > start && snapped < end {
+                snapped
+            } else {
+                ideal_cut
+            };
+
+            let (left_id, right_id) = self.split_at(current_id, cut, None)?;
+            pieces.push(left_id);
+            current_id = right_id;
+        }
+
+        Ok(pieces)
+    }
+
     /// Reorder an item within its track (move to a different index in items vec)
     pub fn reorder_item(&mut self, item_id: Uuid, new_index: usize) -> Result<()> {
         let (track_idx, item_idx) = self
@@ -408,353 +1238,2914 @@ impl Timeline {
         Ok(())
     }
 
-    /// Find the (track_index, item_index) for a given item id.
-    fn find_item_location(&self, item_id: Uuid) -> Option<(usize, usize)> {
-        for (ti, track) in self.tracks.iter().enumerate() {
-            for (ii, item) in track.items.iter().enumerate() {
-                if item.id() == item_id {
-                    return Some((ti, ii));
-                }
-            }
+    /// Swap two equal-length items in place by exchanging their
+    /// `timeline_start_us`, leaving both items' source ranges untouched --
+    /// ported from Blender's `SEQ_edit_sequence_swap`. Rejects items of
+    /// differing `duration_us()` or of incompatible kinds (a clip only
+    /// swaps with a clip of its own kind; any overlay swaps with any other
+    /// overlay). Re-checks `items_overlap` on each affected track afterward
+    /// and rolls the swap back if either item would then overlap a
+    /// neighbor.
+    pub fn swap_items(&mut self, a: Uuid, b: Uuid) -> Result<()> {
+        if a == b {
+            return Ok(());
         }
-        None
-    }
-}
 
-/// Helper: check if two items overlap on the timeline.
-/// Two items overlap if their timeline ranges [start, end) intersect.
-fn items_overlap(a: &Item, b: &Item) -> bool {
-    let a_start = a.timeline_start_us().0;
-    let a_end = a.timeline_end_us().0;
-    let b_start = b.timeline_start_us().0;
-    let b_end = b.timeline_end_us().0;
+        let (a_track, a_idx) = self
+            .find_item_location(a)
+            .ok_or(CoreError::ItemNotFound(a))?;
+        let (b_track, b_idx) = self
+            .find_item_location(b)
+            .ok_or(CoreError::ItemNotFound(b))?;
 
-    a_start < b_end && b_start < a_end
-}
+        let item_a = &self.tracks[a_track].items[a_idx];
+        let item_b = &self.tracks[b_track].items[b_idx];
 
-/// Helper: set timeline_start_us on any Item variant.
-fn set_timeline_start(item: &mut Item, new_start: TimeUs) {
-    match item {
-        Item::VideoClip {
-            timeline_start_us, ..
-        } => *timeline_start_us = new_start,
-        Item::AudioClip {
-            timeline_start_us, ..
-        } => *timeline_start_us = new_start,
-        Item::ImageOverlay {
-            timeline_start_us, ..
+        if item_a.duration_us() != item_b.duration_us() {
+            return Err(CoreError::InvalidOperation(
+                "swap_items requires both strips to be the same length".into(),
+            ));
+        }
+        if !items_swappable(item_a, item_b) {
+            return Err(CoreError::InvalidOperation(
+                "swap_items requires compatible item kinds".into(),
+            ));
+        }
+
+        let a_start = item_a.timeline_start_us();
+        let b_start = item_b.timeline_start_us();
+
+        set_timeline_start(&mut self.tracks[a_track].items[a_idx], b_start);
+        set_timeline_start(&mut self.tracks[b_track].items[b_idx], a_start);
+
+        let a_clone = self.tracks[a_track].items[a_idx].clone();
+        let a_overlaps = self.tracks[a_track]
+            .items
+            .iter()
+            .enumerate()
+            .any(|(i, existing)| i != a_idx && items_overlap(existing, &a_clone));
+
+        let b_clone = self.tracks[b_track].items[b_idx].clone();
+        let b_overlaps = self.tracks[b_track]
+            .items
+            .iter()
+            .enumerate()
+            .any(|(i, existing)| i != b_idx && items_overlap(existing, &b_clone));
+
+        if a_overlaps || b_overlaps {
+            set_timeline_start(&mut self.tracks[a_track].items[a_idx], a_start);
+            set_timeline_start(&mut self.tracks[b_track].items[b_idx], b_start);
+            return Err(CoreError::OverlapDetected);
+        }
+
+        Ok(())
+    }
+
+    /// Group the given items into a single `Item::CompoundClip`, replacing
+    /// them on the timeline with one movable, trimmable unit that wraps a
+    /// nested sub-[`Timeline`]. Items keep their relative layout and original
+    /// track grouping inside the sub-timeline, rebased so the earliest item
+    /// starts at time zero. Returns the id of the new compound clip.
+    pub fn create_compound_from_selection(&mut self, item_ids: &[Uuid]) -> Result<Uuid> {
+        if item_ids.is_empty() {
+            return Err(CoreError::InvalidOperation(
+                "selection must contain at least one item".into(),
+            ));
+        }
+
+        let mut removed = Vec::with_capacity(item_ids.len());
+        for &item_id in item_ids {
+            removed.push((item_id, self.remove_item(item_id)?));
+        }
+
+        let compound_start = removed
+            .iter()
+            .map(|(_, item)| item.timeline_start_us())
+            .min()
+            .expect("removed is non-empty");
+        let compound_end = removed
+            .iter()
+            .map(|(_, item)| item.timeline_end_us())
+            .max()
+            .expect("removed is non-empty");
+        let host_track_id = removed
+            .iter()
+            .min_by_key(|(_, item)| item.timeline_start_us())
+            .map(|(_, item)| item.track_id())
+            .expect("removed is non-empty");
+
+        let mut sub_tracks: Vec<Track> = Vec::new();
+        for (_, mut item) in removed {
+            let rebased_start = TimeUs(item.timeline_start_us().0 - compound_start.0);
+            set_timeline_start(&mut item, rebased_start);
+
+            let track_id = item.track_id();
+            let sub_track = match sub_tracks.iter_mut().find(|t| t.id == track_id) {
+                Some(t) => t,
+                None => {
+                    let kind = self
+                        .tracks
+                        .iter()
+                        .find(|t| t.id == track_id)
+                        .map(|t| t.kind.clone())
+                        .ok_or(CoreError::TrackNotFound(track_id))?;
+                    sub_tracks.push(Track {
+                        id: track_id,
+                        kind,
+                        items: Vec::new(),
+                        transitions: vec![],
+                        subtitles: None,
+                    });
+                    sub_tracks.last_mut().expect("just pushed")
+                }
+            };
+            sub_track.items.push(item);
+        }
+
+        let sequence = Timeline {
+            tracks: sub_tracks,
+            markers: Vec::new(),
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let compound = Item::CompoundClip {
+            id: Uuid::new_v4(),
+            track_id: host_track_id,
+            timeline_start_us: compound_start,
+            source_in_us: TimeUs::ZERO,
+            source_out_us: TimeUs(compound_end.0 - compound_start.0),
+            sequence: Box::new(sequence),
+        };
+        let compound_id = compound.id();
+
+        self.add_item(host_track_id, compound)?;
+        Ok(compound_id)
+    }
+
+    /// Capture a selection of items, plus the [`TrackKind`] each came from,
+    /// so it can be re-inserted elsewhere with [`paste`](Self::paste) while
+    /// preserving the items' spacing relative to one another -- like
+    /// Kdenlive's `copyClip`.
+    pub fn copy_items(&self, item_ids: &[Uuid]) -> Result<Clipboard> {
+        if item_ids.is_empty() {
+            return Err(CoreError::InvalidOperation(
+                "selection must contain at least one item".into(),
+            ));
+        }
+
+        let mut items = Vec::with_capacity(item_ids.len());
+        for &id in item_ids {
+            let (track_idx, item_idx) = self
+                .find_item_location(id)
+                .ok_or(CoreError::ItemNotFound(id))?;
+            items.push(ClipboardItem {
+                item: self.tracks[track_idx].items[item_idx].clone(),
+                source_track_kind: self.tracks[track_idx].kind.clone(),
+            });
+        }
+
+        let min_start_us = items
+            .iter()
+            .map(|c| c.item.timeline_start_us())
+            .min()
+            .expect("items is non-empty");
+
+        Ok(Clipboard {
+            items,
+            min_start_us,
+        })
+    }
+
+    /// Re-instantiate a [`Clipboard`]'s items with fresh ids, offsetting
+    /// every `timeline_start_us` by `paste_start_us - clipboard.min_start_us`
+    /// so the items' relative spacing is preserved. Each item lands on
+    /// `target_track_id` if its source track's kind matches, or on the first
+    /// track of matching [`TrackKind`] otherwise -- so a copied video+audio
+    /// pair lands on a video and an audio track. Validates every destination
+    /// position for overlap up front and inserts nothing if any of them
+    /// collide.
+    pub fn paste(
+        &mut self,
+        clipboard: &Clipboard,
+        target_track_id: Uuid,
+        paste_start_us: TimeUs,
+    ) -> Result<Vec<Uuid>> {
+        let target_kind = self
+            .tracks
+            .iter()
+            .find(|t| t.id == target_track_id)
+            .map(|t| t.kind.clone())
+            .ok_or(CoreError::TrackNotFound(target_track_id))?;
+
+        let offset = paste_start_us.0 - clipboard.min_start_us.0;
+
+        let mut placements = Vec::with_capacity(clipboard.items.len());
+        for clip in &clipboard.items {
+            let dest_track_id = if clip.source_track_kind == target_kind {
+                target_track_id
+            } else {
+                self.tracks
+                    .iter()
+                    .find(|t| t.kind == clip.source_track_kind)
+                    .map(|t| t.id)
+                    .ok_or_else(|| {
+                        CoreError::InvalidOperation(format!(
+                            "no destination track of kind {:?} to paste onto",
+                            clip.source_track_kind
+                        ))
+                    })?
+            };
+
+            let mut item = clip.item.clone();
+            let new_start = TimeUs(item.timeline_start_us().0 + offset);
+            retarget(&mut item, Uuid::new_v4(), dest_track_id);
+            set_timeline_start(&mut item, new_start);
+            placements.push((dest_track_id, item));
+        }
+
+        for (track_id, item) in &placements {
+            let track = self
+                .tracks
+                .iter()
+                .find(|t| t.id == *track_id)
+                .expect("validated above");
+            if track
+                .items
+                .iter()
+                .any(|existing| items_overlap(existing, item))
+            {
+                return Err(CoreError::OverlapDetected);
+            }
+        }
+
+        let mut new_ids = Vec::with_capacity(placements.len());
+        for (track_id, item) in placements {
+            new_ids.push(item.id());
+            let track = self
+                .tracks
+                .iter_mut()
+                .find(|t| t.id == track_id)
+                .expect("validated above");
+            track.items.push(item);
+        }
+
+        Ok(new_ids)
+    }
+
+    /// Group the given items so [`move_item`](Self::move_item),
+    /// [`trim_in`](Self::trim_in), [`trim_out`](Self::trim_out),
+    /// [`split_at`](Self::split_at), and
+    /// [`remove_item`](Self::remove_item) treat them as a single unit --
+    /// e.g. keeping a video clip and its matching audio clip in sync, the
+    /// way Kdenlive moves an A/V group through the model rather than
+    /// per-clip. Unlike [`create_compound_from_selection`](Self::create_compound_from_selection),
+    /// grouped items stay independent entries on their own tracks; only the
+    /// edit operations above are affected. Returns the new group's id.
+    pub fn group_items(&mut self, item_ids: &[Uuid]) -> Result<Uuid> {
+        if item_ids.len() < 2 {
+            return Err(CoreError::InvalidOperation(
+                "a group must contain at least two items".into(),
+            ));
+        }
+
+        for &id in item_ids {
+            if self.find_item_location(id).is_none() {
+                return Err(CoreError::ItemNotFound(id));
+            }
+            if self.group_of(id).is_some() {
+                return Err(CoreError::InvalidOperation(format!(
+                    "item {id} is already in a group"
+                )));
+            }
+        }
+
+        let group = Group {
+            id: Uuid::new_v4(),
+            item_ids: item_ids.to_vec(),
+        };
+        let group_id = group.id;
+        self.groups.push(group);
+        Ok(group_id)
+    }
+
+    /// Dissolve a group. The member items stay on the timeline unaffected.
+    pub fn ungroup(&mut self, group_id: Uuid) -> Result<()> {
+        let idx = self
+            .groups
+            .iter()
+            .position(|g| g.id == group_id)
+            .ok_or(CoreError::GroupNotFound(group_id))?;
+        self.groups.remove(idx);
+        Ok(())
+    }
+
+    /// Walk into nested `CompoundClip` sequences following `path` (a chain of
+    /// compound clip ids, outermost first), returning the timeline that an
+    /// "entered" editing session should operate on. An empty path returns
+    /// `self`. Returns `None` if any id in the path is not a compound clip
+    /// in the timeline reached so far.
+    pub fn resolve_compound_path(&self, path: &[Uuid]) -> Option<&Timeline> {
+        let Some((&id, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        for track in &self.tracks {
+            for item in &track.items {
+                if let Item::CompoundClip {
+                    id: item_id,
+                    sequence,
+                    ..
+                } = item
+                {
+                    if *item_id == id {
+                        return sequence.resolve_compound_path(rest);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Mutable counterpart to [`Timeline::resolve_compound_path`].
+    pub fn resolve_compound_path_mut(&mut self, path: &[Uuid]) -> Option<&mut Timeline> {
+        let Some((&id, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        for track in &mut self.tracks {
+            for item in &mut track.items {
+                if let Item::CompoundClip {
+                    id: item_id,
+                    sequence,
+                    ..
+                } = item
+                {
+                    if *item_id == id {
+                        return sequence.resolve_compound_path_mut(rest);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Find the clip underneath `playhead_us`, recursing into nested
+    /// `CompoundClip` sequences so a compound plays back as a single clip
+    /// from the outside. Returns `None` if no clip covers that position.
+    pub fn resolve_clip_at(&self, playhead_us: TimeUs) -> Option<PlayheadClip> {
+        for track in &self.tracks {
+            for item in &track.items {
+                let start = item.timeline_start_us();
+                let end = item.timeline_end_us();
+                if playhead_us < start || playhead_us >= end {
+                    continue;
+                }
+                match item {
+                    Item::VideoClip {
+                        asset_id,
+                        source_in_us,
+                        ..
+                    }
+                    | Item::AudioClip {
+                        asset_id,
+                        source_in_us,
+                        ..
+                    } => {
+                        let offset = TimeUs(playhead_us.0 - start.0);
+                        return Some(PlayheadClip {
+                            asset_id: *asset_id,
+                            source_in_us: *source_in_us,
+                            seek_us: TimeUs(source_in_us.0 + offset.0),
+                            clip_start_us: start,
+                            clip_end_us: end,
+                        });
+                    }
+                    Item::CompoundClip {
+                        source_in_us,
+                        sequence,
+                        ..
+                    } => {
+                        let offset = TimeUs(playhead_us.0 - start.0);
+                        let nested_playhead = TimeUs(source_in_us.0 + offset.0);
+                        if let Some(mut resolved) = sequence.resolve_clip_at(nested_playhead) {
+                            // Rebase the nested clip's bounds into this timeline's frame.
+                            let shift = TimeUs(start.0 - source_in_us.0);
+                            resolved.clip_start_us = TimeUs(resolved.clip_start_us.0 + shift.0);
+                            resolved.clip_end_us = TimeUs(resolved.clip_end_us.0 + shift.0);
+                            return Some(resolved);
+                        }
+                    }
+                    Item::ImageOverlay { .. } | Item::TextOverlay { .. } => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Every asset id referenced anywhere in this timeline, recursing into
+    /// nested `CompoundClip` sequences -- an asset used only inside a
+    /// compound clip's sub-timeline is still referenced, even though
+    /// `Item::asset_id` returns `None` for the `CompoundClip` item itself.
+    /// Used by asset garbage collection so a compound clip's nested footage
+    /// is never mistaken for orphaned.
+    pub fn all_referenced_asset_ids(&self) -> std::collections::HashSet<Uuid> {
+        let mut referenced = std::collections::HashSet::new();
+        self.collect_referenced_asset_ids(&mut referenced);
+        referenced
+    }
+
+    fn collect_referenced_asset_ids(&self, referenced: &mut std::collections::HashSet<Uuid>) {
+        for track in &self.tracks {
+            for item in &track.items {
+                if let Some(asset_id) = item.asset_id() {
+                    referenced.insert(asset_id);
+                }
+                if let Item::CompoundClip { sequence, .. } = item {
+                    sequence.collect_referenced_asset_ids(referenced);
+                }
+            }
+        }
+    }
+
+    /// Collect overlay items active at `playhead_us`, recursing into nested
+    /// `CompoundClip` sequences and rebasing their `timeline_start_us` into
+    /// this timeline's coordinate frame.
+    pub fn resolve_overlays_at(&self, playhead_us: TimeUs) -> Vec<Item> {
+        let mut overlays = Vec::new();
+
+        for track in &self.tracks {
+            if track.kind == TrackKind::OverlayImage || track.kind == TrackKind::OverlayText {
+                for item in &track.items {
+                    let start = item.timeline_start_us();
+                    let end = item.timeline_end_us();
+                    if playhead_us >= start && playhead_us < end {
+                        overlays.push(item.clone());
+                    }
+                }
+            }
+        }
+
+        for track in &self.tracks {
+            for item in &track.items {
+                let Item::CompoundClip {
+                    timeline_start_us,
+                    source_in_us,
+                    sequence,
+                    ..
+                } = item
+                else {
+                    continue;
+                };
+                let end = item.timeline_end_us();
+                if playhead_us < *timeline_start_us || playhead_us >= end {
+                    continue;
+                }
+                let offset = TimeUs(playhead_us.0 - timeline_start_us.0);
+                let nested_playhead = TimeUs(source_in_us.0 + offset.0);
+                let shift = TimeUs(timeline_start_us.0 - source_in_us.0);
+                for mut nested in sequence.resolve_overlays_at(nested_playhead) {
+                    let rebased = TimeUs(nested.timeline_start_us().0 + shift.0);
+                    set_timeline_start(&mut nested, rebased);
+                    overlays.push(nested);
+                }
+            }
+        }
+
+        overlays
+    }
+
+    /// Find the (track_index, item_index) for a given item id.
+    fn find_item_location(&self, item_id: Uuid) -> Option<(usize, usize)> {
+        for (ti, track) in self.tracks.iter().enumerate() {
+            for (ii, item) in track.items.iter().enumerate() {
+                if item.id() == item_id {
+                    return Some((ti, ii));
+                }
+            }
+        }
+        None
+    }
+
+    /// Snap `candidate_us` to the nearest other item edge or marker within
+    /// `snap_radius_us`, excluding `item_id`'s own edges so a clip doesn't
+    /// snap to itself. Returns `candidate_us` unchanged if `snap_radius_us`
+    /// is `None` or nothing is in range.
+    fn snapped_position(
+        &self,
+        item_id: Uuid,
+        candidate_us: TimeUs,
+        snap_radius_us: Option<TimeUs>,
+    ) -> TimeUs {
+        match snap_radius_us {
+            Some(radius) => {
+                let points = collect_snap_points(self, Some(item_id));
+                find_snap_point(candidate_us, &points, radius)
+            }
+            None => candidate_us,
+        }
+    }
+
+    /// The group that `item_id` belongs to, if any.
+    fn group_of(&self, item_id: Uuid) -> Option<&Group> {
+        self.groups.iter().find(|g| g.item_ids.contains(&item_id))
+    }
+
+    /// Shift every item in `ids` by `delta`, validating first that none of
+    /// them would then overlap a non-member item on its track. Nothing is
+    /// mutated unless every member's post-shift position is valid, so a
+    /// failure leaves the timeline exactly as it was.
+    fn shift_items_by_ids(&mut self, ids: &[Uuid], delta: TimeUs) -> Result<()> {
+        if delta.0 == 0 || ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut locations = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let loc = self
+                .find_item_location(id)
+                .ok_or(CoreError::ItemNotFound(id))?;
+            locations.push(loc);
+        }
+
+        let shifted: Vec<Item> = locations
+            .iter()
+            .map(|&(ti, ii)| {
+                let mut clone = self.tracks[ti].items[ii].clone();
+                let new_start = TimeUs(clone.timeline_start_us().0 + delta.0);
+                set_timeline_start(&mut clone, new_start);
+                clone
+            })
+            .collect();
+
+        for (&(ti, _), moved) in locations.iter().zip(shifted.iter()) {
+            let overlaps = self.tracks[ti]
+                .items
+                .iter()
+                .any(|existing| !ids.contains(&existing.id()) && items_overlap(existing, moved));
+            if overlaps {
+                return Err(CoreError::OverlapDetected);
+            }
+        }
+
+        for ((ti, ii), moved) in locations.into_iter().zip(shifted.into_iter()) {
+            self.tracks[ti].items[ii] = moved;
+        }
+
+        Ok(())
+    }
+
+    /// Trim every item in `ids` by applying `delta` to its own end (new end
+    /// = old end + `delta`), validating first that none of them would then
+    /// overlap a non-member item on its track. Nothing is mutated unless
+    /// every member's post-trim state is valid, so a failure leaves the
+    /// timeline exactly as it was.
+    fn trim_out_group_by_delta(&mut self, ids: &[Uuid], delta: TimeUs) -> Result<()> {
+        if delta.0 == 0 || ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut locations = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let loc = self
+                .find_item_location(id)
+                .ok_or(CoreError::ItemNotFound(id))?;
+            locations.push(loc);
+        }
+
+        let mut trimmed = Vec::with_capacity(locations.len());
+        for &(ti, ii) in &locations {
+            let mut clone = self.tracks[ti].items[ii].clone();
+            let new_out_us = TimeUs(clone.timeline_end_us().0 + delta.0);
+            apply_trim_out(&mut clone, new_out_us)?;
+            trimmed.push(clone);
+        }
+
+        for (&(ti, _), trimmed_item) in locations.iter().zip(trimmed.iter()) {
+            let overlaps = self.tracks[ti].items.iter().any(|existing| {
+                !ids.contains(&existing.id()) && items_overlap(existing, trimmed_item)
+            });
+            if overlaps {
+                return Err(CoreError::OverlapDetected);
+            }
+        }
+
+        for (&(ti, ii), trimmed_item) in locations.iter().zip(trimmed.into_iter()) {
+            let item_id = trimmed_item.id();
+            self.tracks[ti].items[ii] = trimmed_item;
+            shrink_transitions_for(&mut self.tracks[ti], item_id);
+        }
+
+        Ok(())
+    }
+
+    /// Remove every member of `group` from the timeline and dissolve it,
+    /// returning the item that triggered the removal.
+    fn remove_group(&mut self, group: &Group, item_id: Uuid) -> Result<Item> {
+        let mut target = None;
+        for &id in &group.item_ids {
+            for track in &mut self.tracks {
+                if let Some(pos) = track.items.iter().position(|i| i.id() == id) {
+                    drop_transitions_for(track, id);
+                    let removed = track.items.remove(pos);
+                    if id == item_id {
+                        target = Some(removed);
+                    }
+                    break;
+                }
+            }
+        }
+        self.groups.retain(|g| g.id != group.id);
+        target.ok_or(CoreError::ItemNotFound(item_id))
+    }
+
+    /// Split every member of `group` at `split_time_us`, validating that
+    /// the position is strictly inside every member's bounds before
+    /// splitting any of them. Places the right-hand pieces into a new
+    /// group; the original group keeps the left-hand (original-id) pieces.
+    /// Returns the (left, right) ids of `item_id`'s own split.
+    fn split_group_at(
+        &mut self,
+        group: &Group,
+        item_id: Uuid,
+        split_time_us: TimeUs,
+    ) -> Result<(Uuid, Uuid)> {
+        for &id in &group.item_ids {
+            let (track_idx, item_idx) = self
+                .find_item_location(id)
+                .ok_or(CoreError::ItemNotFound(id))?;
+            let item = &self.tracks[track_idx].items[item_idx];
+            if split_time_us <= item.timeline_start_us() || split_time_us >= item.timeline_end_us()
+            {
+                return Err(CoreError::InvalidOperation(
+                    "split position must be strictly between start and end for every grouped item"
+                        .into(),
+                ));
+            }
+        }
+
+        let mut right_ids = Vec::with_capacity(group.item_ids.len());
+        let mut target_result = None;
+        for &id in &group.item_ids {
+            let (left_id, right_id) =
+                self.split_at_with_right_id(id, split_time_us, Uuid::new_v4())?;
+            right_ids.push(right_id);
+            if id == item_id {
+                target_result = Some((left_id, right_id));
+            }
+        }
+
+        self.groups.push(Group {
+            id: Uuid::new_v4(),
+            item_ids: right_ids,
+        });
+
+        target_result.ok_or(CoreError::ItemNotFound(item_id))
+    }
+}
+
+/// Helper: check if two items overlap on the timeline.
+/// Two items overlap if their timeline ranges [start, end) intersect.
+fn items_overlap(a: &Item, b: &Item) -> bool {
+    let a_start = a.timeline_start_us().0;
+    let a_end = a.timeline_end_us().0;
+    let b_start = b.timeline_start_us().0;
+    let b_end = b.timeline_end_us().0;
+
+    a_start < b_end && b_start < a_end
+}
+
+/// Helper: whether two items are compatible kinds for
+/// [`Timeline::swap_items`] -- a clip only swaps with a clip of its own
+/// kind, while any overlay swaps with any other overlay.
+fn items_swappable(a: &Item, b: &Item) -> bool {
+    matches!(
+        (a, b),
+        (Item::VideoClip { .. }, Item::VideoClip { .. })
+            | (Item::AudioClip { .. }, Item::AudioClip { .. })
+            | (Item::CompoundClip { .. }, Item::CompoundClip { .. })
+            | (
+                Item::ImageOverlay { .. } | Item::TextOverlay { .. },
+                Item::ImageOverlay { .. } | Item::TextOverlay { .. }
+            )
+    )
+}
+
+/// Helper: apply a new out-point to any `Item` variant, shared by
+/// [`Timeline::trim_out`] and its grouped counterpart.
+/// For VideoClip/AudioClip/CompoundClip, `new_out_us` is the new
+/// `source_out_us`; for overlays it is the new timeline end.
+fn apply_trim_out(item: &mut Item, new_out_us: TimeUs) -> Result<()> {
+    match item {
+        Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            ..
+        }
+        | Item::AudioClip {
+            source_in_us,
+            source_out_us,
+            ..
+        }
+        | Item::CompoundClip {
+            source_in_us,
+            source_out_us,
+            ..
+        } => {
+            if new_out_us <= *source_in_us {
+                return Err(CoreError::InvalidOperation(
+                    "source_out must be greater than source_in".into(),
+                ));
+            }
+            *source_out_us = new_out_us;
+        }
+        Item::ImageOverlay {
+            timeline_start_us,
+            duration_us,
+            ..
+        }
+        | Item::TextOverlay {
+            timeline_start_us,
+            duration_us,
+            ..
+        } => {
+            let new_dur = TimeUs(new_out_us.0 - timeline_start_us.0);
+            if new_dur.0 <= 0 {
+                return Err(CoreError::InvalidOperation(
+                    "new out must be after start".into(),
+                ));
+            }
+            *duration_us = new_dur;
+        }
+    }
+    Ok(())
+}
+
+/// Helper: check that a track has no item starting before [`TimeUs::ZERO`]
+/// and no pair of items overlapping -- the invariant ripple edits must
+/// re-establish after shifting.
+fn track_is_valid(track: &Track) -> bool {
+    if track
+        .items
+        .iter()
+        .any(|i| i.timeline_start_us() < TimeUs::ZERO)
+    {
+        return false;
+    }
+    for (i, a) in track.items.iter().enumerate() {
+        for b in &track.items[i + 1..] {
+            if items_overlap(a, b) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Helper: set timeline_start_us on any Item variant.
+fn set_timeline_start(item: &mut Item, new_start: TimeUs) {
+    match item {
+        Item::VideoClip {
+            timeline_start_us, ..
+        } => *timeline_start_us = new_start,
+        Item::AudioClip {
+            timeline_start_us, ..
+        } => *timeline_start_us = new_start,
+        Item::ImageOverlay {
+            timeline_start_us, ..
         } => *timeline_start_us = new_start,
         Item::TextOverlay {
             timeline_start_us, ..
         } => *timeline_start_us = new_start,
+        Item::CompoundClip {
+            timeline_start_us, ..
+        } => *timeline_start_us = new_start,
+    }
+}
+
+/// Helper: give any Item variant a fresh id and retarget it to a different
+/// track, used by [`Timeline::paste`] when re-instantiating a clipboard item.
+fn retarget(item: &mut Item, new_id: Uuid, new_track_id: Uuid) {
+    match item {
+        Item::VideoClip { id, track_id, .. }
+        | Item::AudioClip { id, track_id, .. }
+        | Item::ImageOverlay { id, track_id, .. }
+        | Item::TextOverlay { id, track_id, .. }
+        | Item::CompoundClip { id, track_id, .. } => {
+            *id = new_id;
+            *track_id = new_track_id;
+        }
+    }
+}
+
+/// Roll-edit helper: apply `delta` to a clip's out-point (negative trims,
+/// positive extends). Used on the earlier of the two clips in
+/// [`Timeline::roll_edit`]; see [`roll_trim_in`] for the later one.
+fn roll_extend_out(item: &mut Item, delta: TimeUs) -> Result<()> {
+    match item {
+        Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            ..
+        }
+        | Item::AudioClip {
+            source_in_us,
+            source_out_us,
+            ..
+        }
+        | Item::CompoundClip {
+            source_in_us,
+            source_out_us,
+            ..
+        } => {
+            let new_out = TimeUs(source_out_us.0 + delta.0);
+            if new_out <= *source_in_us {
+                return Err(CoreError::InvalidOperation(
+                    "roll edit would invert source_in/source_out".into(),
+                ));
+            }
+            *source_out_us = new_out;
+        }
+        Item::ImageOverlay { duration_us, .. } | Item::TextOverlay { duration_us, .. } => {
+            let new_dur = TimeUs(duration_us.0 + delta.0);
+            if new_dur.0 <= 0 {
+                return Err(CoreError::InvalidOperation(
+                    "roll edit would invert item duration".into(),
+                ));
+            }
+            *duration_us = new_dur;
+        }
+    }
+    Ok(())
+}
+
+/// Roll-edit helper: apply `delta` to a clip's in-point, shifting
+/// `timeline_start_us` by the same amount so the clip's end stays fixed.
+/// Mirrors [`Timeline::trim_in`]; used on the later of the two clips in
+/// [`Timeline::roll_edit`].
+fn roll_trim_in(item: &mut Item, delta: TimeUs) -> Result<()> {
+    match item {
+        Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            timeline_start_us,
+            ..
+        }
+        | Item::AudioClip {
+            source_in_us,
+            source_out_us,
+            timeline_start_us,
+            ..
+        }
+        | Item::CompoundClip {
+            source_in_us,
+            source_out_us,
+            timeline_start_us,
+            ..
+        } => {
+            let new_in = TimeUs(source_in_us.0 + delta.0);
+            if new_in >= *source_out_us {
+                return Err(CoreError::InvalidOperation(
+                    "roll edit would invert source_in/source_out".into(),
+                ));
+            }
+            *source_in_us = new_in;
+            *timeline_start_us = TimeUs(timeline_start_us.0 + delta.0);
+        }
+        Item::ImageOverlay {
+            timeline_start_us,
+            duration_us,
+            ..
+        }
+        | Item::TextOverlay {
+            timeline_start_us,
+            duration_us,
+            ..
+        } => {
+            let new_dur = TimeUs(duration_us.0 - delta.0);
+            if new_dur.0 <= 0 {
+                return Err(CoreError::InvalidOperation(
+                    "roll edit would invert item duration".into(),
+                ));
+            }
+            *duration_us = new_dur;
+            *timeline_start_us = TimeUs(timeline_start_us.0 + delta.0);
+        }
+    }
+    Ok(())
+}
+
+/// Whether an item kind can carry a crossfade (only clips with real media
+/// to fade, not overlays or compound sequences).
+fn has_fade_fields(item: &Item) -> bool {
+    matches!(item, Item::VideoClip { .. } | Item::AudioClip { .. })
+}
+
+/// Helper: set fade_in_us on a fadeable item. No-op for item kinds without
+/// fade fields.
+fn set_fade_in(item: &mut Item, fade: TimeUs) {
+    match item {
+        Item::VideoClip { fade_in_us, .. } => *fade_in_us = fade,
+        Item::AudioClip { fade_in_us, .. } => *fade_in_us = fade,
+        _ => {}
+    }
+}
+
+/// Helper: set fade_out_us on a fadeable item. See [`set_fade_in`].
+fn set_fade_out(item: &mut Item, fade: TimeUs) {
+    match item {
+        Item::VideoClip { fade_out_us, .. } => *fade_out_us = fade,
+        Item::AudioClip { fade_out_us, .. } => *fade_out_us = fade,
+        _ => {}
+    }
+}
+
+/// Try to turn an overlap between `existing` and `incoming` into a
+/// crossfade, matching Ardour's `OverlapStart` case: the earlier item must
+/// end strictly inside the later item's range (a simple tail/head
+/// crossover, not one item fully containing the other). On success, sets
+/// both items' fade fields and returns the `Transition` to record on the
+/// track; returns `None` (leaving both items untouched) for any other
+/// overlap shape or for item kinds that can't carry a fade.
+fn apply_crossfade(existing: &mut Item, incoming: &mut Item) -> Option<Transition> {
+    if !has_fade_fields(existing) || !has_fade_fields(incoming) {
+        return None;
+    }
+
+    let (earlier, later) = if existing.timeline_start_us() <= incoming.timeline_start_us() {
+        (existing, incoming)
+    } else {
+        (incoming, existing)
+    };
+
+    let earlier_end = earlier.timeline_end_us();
+    let later_start = later.timeline_start_us();
+    let later_end = later.timeline_end_us();
+
+    if earlier_end <= later_start || earlier_end >= later_end {
+        return None;
+    }
+
+    let fade_len = TimeUs(earlier_end.0 - later_start.0);
+    set_fade_out(earlier, fade_len);
+    set_fade_in(later, fade_len);
+
+    Some(Transition {
+        out_item: earlier.id(),
+        in_item: later.id(),
+        region_us: (later_start, earlier_end),
+        kind: TransitionKind::default(),
+    })
+}
+
+/// The ids of items already linked to `item_id` by a transition on `track`.
+fn transition_partners_of(track: &Track, item_id: Uuid) -> Vec<Uuid> {
+    track
+        .transitions
+        .iter()
+        .filter_map(|t| {
+            if t.out_item == item_id {
+                Some(t.in_item)
+            } else if t.in_item == item_id {
+                Some(t.out_item)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Shrink or drop every [`Transition`] involving `item_id` after a trim,
+/// re-deriving each one's region and fade lengths from the items' current
+/// bounds. A transition whose items no longer overlap in the tail/head
+/// shape `apply_crossfade` requires is dropped and both fades zeroed.
+fn shrink_transitions_for(track: &mut Track, item_id: Uuid) {
+    let mut to_drop = Vec::new();
+    let mut to_shrink = Vec::new();
+
+    for (i, t) in track.transitions.iter().enumerate() {
+        if t.out_item != item_id && t.in_item != item_id {
+            continue;
+        }
+        let out_end = track.items.iter().find(|it| it.id() == t.out_item);
+        let in_item = track.items.iter().find(|it| it.id() == t.in_item);
+        match (out_end, in_item) {
+            (Some(out_item), Some(in_item)) => {
+                let out_end = out_item.timeline_end_us();
+                let in_start = in_item.timeline_start_us();
+                let in_end = in_item.timeline_end_us();
+                if out_end > in_start && out_end < in_end {
+                    to_shrink.push((i, in_start, out_end));
+                } else {
+                    to_drop.push(i);
+                }
+            }
+            _ => to_drop.push(i),
+        }
+    }
+
+    for (i, start_us, end_us) in to_shrink {
+        track.transitions[i].region_us = (start_us, end_us);
+        let fade_len = TimeUs(end_us.0 - start_us.0);
+        let out_id = track.transitions[i].out_item;
+        let in_id = track.transitions[i].in_item;
+        if let Some(out_item) = track.items.iter_mut().find(|it| it.id() == out_id) {
+            set_fade_out(out_item, fade_len);
+        }
+        if let Some(in_item) = track.items.iter_mut().find(|it| it.id() == in_id) {
+            set_fade_in(in_item, fade_len);
+        }
+    }
+
+    for i in to_drop.into_iter().rev() {
+        let t = track.transitions.remove(i);
+        if let Some(out_item) = track.items.iter_mut().find(|it| it.id() == t.out_item) {
+            set_fade_out(out_item, TimeUs::ZERO);
+        }
+        if let Some(in_item) = track.items.iter_mut().find(|it| it.id() == t.in_item) {
+            set_fade_in(in_item, TimeUs::ZERO);
+        }
+    }
+}
+
+/// Drop every [`Transition`] involving `item_id` after a split, zeroing the
+/// fade on whichever partner item remains. Simpler than retargeting the
+/// transition to whichever half of the split now owns the relevant edge.
+fn drop_transitions_for(track: &mut Track, item_id: Uuid) {
+    let mut to_drop = Vec::new();
+    for (i, t) in track.transitions.iter().enumerate() {
+        if t.out_item == item_id || t.in_item == item_id {
+            to_drop.push(i);
+        }
+    }
+
+    for i in to_drop.into_iter().rev() {
+        let t = track.transitions.remove(i);
+        let partner_id = if t.out_item == item_id {
+            t.in_item
+        } else {
+            t.out_item
+        };
+        if let Some(partner) = track.items.iter_mut().find(|it| it.id() == partner_id) {
+            if t.out_item == partner_id {
+                set_fade_out(partner, TimeUs::ZERO);
+            } else {
+                set_fade_in(partner, TimeUs::ZERO);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_video_clip(
+        track_id: Uuid,
+        start_us: i64,
+        source_in: i64,
+        source_out: i64,
+    ) -> (Uuid, Item) {
+        let id = Uuid::new_v4();
+        let item = Item::VideoClip {
+            id,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(start_us),
+            source_in_us: TimeUs(source_in),
+            source_out_us: TimeUs(source_out),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        (id, item)
+    }
+
+    fn make_test_timeline() -> (Timeline, Uuid, Uuid) {
+        let track_id = Uuid::new_v4();
+        let (clip_id, clip) = make_video_clip(track_id, 0, 0, 5_000_000);
+        let tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        (tl, track_id, clip_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // add_item
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn add_item_to_empty_track_succeeds() {
+        let track_id = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let (_, clip) = make_video_clip(track_id, 0, 0, 5_000_000);
+        assert!(tl.add_item(track_id, clip).is_ok());
+        assert_eq!(tl.tracks[0].items.len(), 1);
+    }
+
+    #[test]
+    fn add_item_with_overlap_fails() {
+        let (mut tl, track_id, _) = make_test_timeline();
+
+        // Existing clip: [0, 5_000_000). Try adding overlapping clip at [2_000_000, 7_000_000).
+        let (_, clip) = make_video_clip(track_id, 2_000_000, 0, 5_000_000);
+        let result = tl.add_item(track_id, clip);
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+    }
+
+    #[test]
+    fn add_item_adjacent_succeeds() {
+        let (mut tl, track_id, _) = make_test_timeline();
+
+        // Existing clip: [0, 5_000_000). Add adjacent clip at [5_000_000, 10_000_000).
+        let (_, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        assert!(tl.add_item(track_id, clip).is_ok());
+        assert_eq!(tl.tracks[0].items.len(), 2);
+    }
+
+    #[test]
+    fn add_item_to_nonexistent_track_fails() {
+        let mut tl = Timeline {
+            tracks: vec![],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        let fake_track = Uuid::new_v4();
+        let (_, clip) = make_video_clip(fake_track, 0, 0, 5_000_000);
+        let result = tl.add_item(fake_track, clip);
+        assert!(matches!(result.unwrap_err(), CoreError::TrackNotFound(_)));
+    }
+
+    #[test]
+    fn add_video_clip_from_path_defaults_source_out_to_probed_duration() {
+        use tempfile::TempDir;
+
+        fn make_box(fourcc: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+            let mut out = Vec::new();
+            out.extend_from_slice(&((8 + payload.len()) as u32).to_be_bytes());
+            out.extend_from_slice(fourcc);
+            out.extend_from_slice(payload);
+            out
+        }
+
+        // A minimal single-track moov: 90_000 Hz timescale, 3s duration.
+        let mut stts_payload = 0u32.to_be_bytes().to_vec();
+        stts_payload.extend_from_slice(&1u32.to_be_bytes());
+        stts_payload.extend_from_slice(&90u32.to_be_bytes()); // sample_count
+        stts_payload.extend_from_slice(&3_000u32.to_be_bytes()); // sample_delta
+        let stbl = make_box(b"stbl", &make_box(b"stts", &stts_payload));
+        let minf = make_box(b"minf", &stbl);
+        let mut mdhd_payload = vec![0u8; 16];
+        mdhd_payload[12..16].copy_from_slice(&90_000u32.to_be_bytes());
+        let mdhd = make_box(b"mdhd", &mdhd_payload);
+        let mut hdlr_payload = vec![0u8; 8];
+        hdlr_payload.extend_from_slice(b"vide");
+        let hdlr = make_box(b"hdlr", &hdlr_payload);
+        let mut mdia_payload = Vec::new();
+        mdia_payload.extend(mdhd);
+        mdia_payload.extend(hdlr);
+        mdia_payload.extend(minf);
+        let trak = make_box(b"trak", &make_box(b"mdia", &mdia_payload));
+        let moov = make_box(b"moov", &trak);
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("clip.mp4");
+        std::fs::write(&path, &moov).unwrap();
+
+        let track_id = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let asset_id = Uuid::new_v4();
+        let item_id = tl
+            .add_video_clip_from_path(track_id, asset_id, &path, TimeUs::ZERO)
+            .unwrap();
+
+        let item = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == item_id)
+            .unwrap();
+        assert_eq!(item.source_in_us(), Some(TimeUs::ZERO));
+        if let Item::VideoClip { source_out_us, .. } = item {
+            assert_eq!(*source_out_us, TimeUs(3_000_000));
+        } else {
+            panic!("expected a VideoClip");
+        }
+    }
+
+    #[test]
+    fn add_item_overlap_creates_crossfade_in_crossfade_mode() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
+
+        // Existing clip: [0, 5M). Overlapping clip starts at 3M, so the
+        // overlap is [3M, 5M).
+        let (second_id, clip) = make_video_clip(track_id, 3_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        assert_eq!(tl.tracks[0].items.len(), 2);
+        assert_eq!(tl.tracks[0].transitions.len(), 1);
+
+        let transition = &tl.tracks[0].transitions[0];
+        assert_eq!(transition.out_item, clip_id);
+        assert_eq!(transition.in_item, second_id);
+        assert_eq!(transition.region_us, (TimeUs(3_000_000), TimeUs(5_000_000)));
+
+        let earlier = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let later = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(earlier.fade_out_us(), Some(TimeUs(2_000_000)));
+        assert_eq!(later.fade_in_us(), Some(TimeUs(2_000_000)));
+    }
+
+    #[test]
+    fn add_item_overlap_still_rejected_in_reject_mode() {
+        let (mut tl, track_id, _) = make_test_timeline();
+
+        let (_, clip) = make_video_clip(track_id, 3_000_000, 0, 5_000_000);
+        let result = tl.add_item(track_id, clip);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+    }
+
+    #[test]
+    fn add_item_full_containment_rejected_even_in_crossfade_mode() {
+        let (mut tl, track_id, _) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
+
+        // Existing clip: [0, 5M). New clip fully contained within it: [1M, 2M).
+        let (_, clip) = make_video_clip(track_id, 1_000_000, 0, 1_000_000);
+        let result = tl.add_item(track_id, clip);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+        assert!(tl.tracks[0].transitions.is_empty());
+    }
+
+    // -----------------------------------------------------------------------
+    // add_transition
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn add_transition_pulls_right_clip_left_and_creates_crossfade() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        // Existing clip: [0, 5M). Second clip touches it exactly: [5M, 10M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        tl.add_transition(clip_id, second_id, TimeUs(1_000_000))
+            .unwrap();
+
+        assert_eq!(tl.tracks[0].transitions.len(), 1);
+        let transition = &tl.tracks[0].transitions[0];
+        assert_eq!(transition.out_item, clip_id);
+        assert_eq!(transition.in_item, second_id);
+        assert_eq!(transition.region_us, (TimeUs(4_000_000), TimeUs(5_000_000)));
+
+        let earlier = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let later = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(earlier.fade_out_us(), Some(TimeUs(1_000_000)));
+        assert_eq!(later.fade_in_us(), Some(TimeUs(1_000_000)));
+        assert_eq!(later.timeline_start_us(), TimeUs(4_000_000));
+    }
+
+    #[test]
+    fn add_transition_rejects_non_adjacent_clips() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        // Gap between [0, 5M) and [6M, 11M).
+        let (second_id, clip) = make_video_clip(track_id, 6_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        let result = tl.add_transition(clip_id, second_id, TimeUs(1_000_000));
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+        assert!(tl.tracks[0].transitions.is_empty());
+    }
+
+    #[test]
+    fn add_transition_rejects_duration_exceeding_clip_length() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        let result = tl.add_transition(clip_id, second_id, TimeUs(6_000_000));
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+        assert!(tl.tracks[0].transitions.is_empty());
+    }
+
+    #[test]
+    fn add_transition_rejects_clips_on_different_tracks() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        let other_track = Uuid::new_v4();
+        tl.tracks.push(Track {
+            id: other_track,
+            kind: TrackKind::Video,
+            items: vec![],
+            transitions: vec![],
+            subtitles: None,
+        });
+        let (other_id, other_clip) = make_video_clip(other_track, 0, 0, 5_000_000);
+        tl.add_item(other_track, other_clip).unwrap();
+
+        let result = tl.add_transition(clip_id, other_id, TimeUs(1_000_000));
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    #[test]
+    fn remove_item_drops_bound_transition_from_partner() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+        tl.add_transition(clip_id, second_id, TimeUs(1_000_000))
+            .unwrap();
+
+        tl.remove_item(clip_id).unwrap();
+
+        assert!(tl.tracks[0].transitions.is_empty());
+        let remaining = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(remaining.fade_in_us(), Some(TimeUs::ZERO));
+    }
+
+    // -----------------------------------------------------------------------
+    // remove_item
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn remove_item_works() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let removed = tl.remove_item(clip_id).unwrap();
+        assert_eq!(removed.id(), clip_id);
+        assert!(tl.tracks[0].items.is_empty());
+    }
+
+    #[test]
+    fn remove_item_with_bad_id_fails() {
+        let (mut tl, _, _) = make_test_timeline();
+        let bad_id = Uuid::new_v4();
+        let result = tl.remove_item(bad_id);
+        assert!(matches!(result.unwrap_err(), CoreError::ItemNotFound(_)));
+    }
+
+    // -----------------------------------------------------------------------
+    // move_item
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn move_item_to_valid_position() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Move clip from [0, 5M) to [10M, 15M)
+        assert!(tl.move_item(clip_id, TimeUs(10_000_000), None).is_ok());
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_start_us(), TimeUs(10_000_000));
+    }
+
+    #[test]
+    fn move_item_causing_overlap_fails() {
+        let (mut tl, track_id, _clip_id) = make_test_timeline();
+
+        // Add second clip at [5M, 10M)
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Try to move second clip to [3M, 8M) -- overlaps first clip [0, 5M)
+        let result = tl.move_item(second_id, TimeUs(3_000_000), None);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+    }
+
+    #[test]
+    fn move_item_nonexistent_fails() {
+        let (mut tl, _, _) = make_test_timeline();
+        let bad_id = Uuid::new_v4();
+        let result = tl.move_item(bad_id, TimeUs(0), None);
+        assert!(matches!(result.unwrap_err(), CoreError::ItemNotFound(_)));
+    }
+
+    #[test]
+    fn move_item_overlap_creates_crossfade_in_crossfade_mode() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
+
+        // Second clip starts right after the first: [5M, 10M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Move it back so it overlaps the first clip's tail by 2M.
+        tl.move_item(second_id, TimeUs(3_000_000), None).unwrap();
+
+        assert_eq!(tl.tracks[0].transitions.len(), 1);
+        let transition = &tl.tracks[0].transitions[0];
+        assert_eq!(transition.out_item, clip_id);
+        assert_eq!(transition.in_item, second_id);
+        assert_eq!(transition.region_us, (TimeUs(3_000_000), TimeUs(5_000_000)));
+    }
+
+    #[test]
+    fn move_item_snaps_to_nearby_marker_within_radius() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        tl.markers.push(Marker {
+            id: Uuid::new_v4(),
+            time_us: TimeUs(10_000_000),
+            label: "marker".to_string(),
+        });
+
+        tl.move_item(clip_id, TimeUs(9_900_000), Some(TimeUs(200_000)))
+            .unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_start_us(), TimeUs(10_000_000));
+    }
+
+    #[test]
+    fn move_item_does_not_snap_beyond_radius() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        tl.markers.push(Marker {
+            id: Uuid::new_v4(),
+            time_us: TimeUs(10_000_000),
+            label: "marker".to_string(),
+        });
+
+        tl.move_item(clip_id, TimeUs(9_000_000), Some(TimeUs(200_000)))
+            .unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_start_us(), TimeUs(9_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // trim_in
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn trim_in_adjusts_start_correctly() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Original: timeline_start=0, source_in=0, source_out=5M, end=5M
+        // Trim in to source_in=1M. End stays at 5M, new duration=4M, new timeline_start=1M
+        tl.trim_in(clip_id, TimeUs(1_000_000), None).unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_end_us(), TimeUs(5_000_000));
+        assert_eq!(item.duration_us(), TimeUs(4_000_000));
+        assert_eq!(item.timeline_start_us(), TimeUs(1_000_000));
+        if let Item::VideoClip { source_in_us, .. } = item {
+            assert_eq!(*source_in_us, TimeUs(1_000_000));
+        }
+    }
+
+    #[test]
+    fn trim_in_invalid_past_out_point_fails() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // source_out is 5M, try to set source_in to 6M
+        let result = tl.trim_in(clip_id, TimeUs(6_000_000), None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn trim_in_maps_new_start_through_speed() {
+        let track_id = Uuid::new_v4();
+        let clip_id = Uuid::new_v4();
+        let clip = Item::VideoClip {
+            id: clip_id,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(10_000_000),
+            speed: 2.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        // timeline_end starts at (10M - 0) / 2.0 = 5M.
+        // Trim source_in to 2M: new source-time duration is 8M, which at
+        // speed 2.0 is a 4M timeline duration, so timeline_start becomes
+        // end - 4M = 1M.
+        tl.trim_in(clip_id, TimeUs(2_000_000), None).unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_end_us(), TimeUs(5_000_000));
+        assert_eq!(item.duration_us(), TimeUs(4_000_000));
+        assert_eq!(item.timeline_start_us(), TimeUs(1_000_000));
+        if let Item::VideoClip { source_in_us, .. } = item {
+            assert_eq!(*source_in_us, TimeUs(2_000_000));
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // trim_out
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn trim_out_adjusts_end_correctly() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Original: timeline_start=0, source_in=0, source_out=5M
+        // Trim out to 3M: new end = 0 + 3M = 3M
+        tl.trim_out(clip_id, TimeUs(3_000_000), None).unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_start_us(), TimeUs(0));
+        assert_eq!(item.timeline_end_us(), TimeUs(3_000_000));
+        assert_eq!(item.duration_us(), TimeUs(3_000_000));
+    }
+
+    #[test]
+    fn trim_out_invalid_before_in_point_fails() {
+        let (mut tl, track_id, _) = make_test_timeline();
+        // Add a clip with source_in=2M, source_out=5M
+        let (clip_id, clip) = make_video_clip(track_id, 10_000_000, 2_000_000, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Try to trim out to 1M (before source_in of 2M)
+        let result = tl.trim_out(clip_id, TimeUs(1_000_000), None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn trim_out_shrinks_transition_still_overlapping() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
+
+        let (second_id, clip) = make_video_clip(track_id, 3_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+        assert_eq!(
+            tl.tracks[0].transitions[0].region_us,
+            (TimeUs(3_000_000), TimeUs(5_000_000))
+        );
+
+        // Trim the earlier clip's out point back to 4M: overlap shrinks to [3M, 4M).
+        tl.trim_out(clip_id, TimeUs(4_000_000), None).unwrap();
+
+        assert_eq!(tl.tracks[0].transitions.len(), 1);
+        assert_eq!(
+            tl.tracks[0].transitions[0].region_us,
+            (TimeUs(3_000_000), TimeUs(4_000_000))
+        );
+
+        let earlier = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let later = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(earlier.fade_out_us(), Some(TimeUs(1_000_000)));
+        assert_eq!(later.fade_in_us(), Some(TimeUs(1_000_000)));
+    }
+
+    #[test]
+    fn trim_out_drops_transition_no_longer_overlapping() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
+
+        let (second_id, clip) = make_video_clip(track_id, 3_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Trim the earlier clip's out point back to 3M: it no longer
+        // overlaps the later clip at all.
+        tl.trim_out(clip_id, TimeUs(3_000_000), None).unwrap();
+
+        assert!(tl.tracks[0].transitions.is_empty());
+        let earlier = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let later = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(earlier.fade_out_us(), Some(TimeUs::ZERO));
+        assert_eq!(later.fade_in_us(), Some(TimeUs::ZERO));
+    }
+
+    // -----------------------------------------------------------------------
+    // set_speed
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn set_speed_shortens_duration_keeping_start_fixed() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Original: timeline_start=0, source range [0, 5M), speed=1.0 -> 5M duration.
+        tl.set_speed(clip_id, 2.0).unwrap();
+
+        let item = &tl.tracks[0].items[0];
+        assert_eq!(item.timeline_start_us(), TimeUs(0));
+        assert_eq!(item.duration_us(), TimeUs(2_500_000));
+        assert_eq!(item.timeline_end_us(), TimeUs(2_500_000));
+        if let Item::VideoClip { speed, .. } = item {
+            assert_eq!(*speed, 2.0);
+        } else {
+            panic!("expected VideoClip");
+        }
+    }
+
+    #[test]
+    fn set_speed_rejects_non_positive_or_non_finite() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        assert!(tl.set_speed(clip_id, 0.0).is_err());
+        assert!(tl.set_speed(clip_id, -1.0).is_err());
+        assert!(tl.set_speed(clip_id, f64::NAN).is_err());
+        assert!(tl.set_speed(clip_id, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn set_speed_rejects_overlay_items() {
+        let (mut tl, track_id, _) = make_test_timeline();
+        let overlay_id = Uuid::new_v4();
+        tl.tracks[0].items.push(Item::TextOverlay {
+            id: overlay_id,
+            track_id,
+            timeline_start_us: TimeUs(6_000_000),
+            duration_us: TimeUs(1_000_000),
+            text: "hi".into(),
+            x: 0,
+            y: 0,
+            font_size: 24,
+            color: "white".into(),
+        });
+
+        let result = tl.set_speed(overlay_id, 2.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn set_speed_rejects_overlap_created_by_slower_playback() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Second clip starts right after at 5M.
+        let (_, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Slowing the first clip to half speed doubles its duration to 10M,
+        // which now overlaps the second clip starting at 5M.
+        let result = tl.set_speed(clip_id, 0.5);
+        assert!(result.is_err());
+    }
+
+    // -----------------------------------------------------------------------
+    // ripple_trim_out
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn ripple_trim_out_shifts_later_items_on_same_track() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Second clip: [5M, 8M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Trim the first clip's out point back to 2M: end moves from 5M to
+        // 2M, delta = -3M, so the second clip should shift from 5M to 2M.
+        tl.ripple_trim_out(clip_id, TimeUs(2_000_000), RippleScope::SameTrack)
+            .unwrap();
+
+        let second = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(second.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(second.timeline_end_us(), TimeUs(5_000_000));
+    }
+
+    #[test]
+    fn ripple_trim_out_all_tracks_shifts_parallel_track() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        let other_track_id = Uuid::new_v4();
+        let (other_id, other_clip) = make_video_clip(other_track_id, 5_000_000, 0, 3_000_000);
+        tl.tracks.push(Track {
+            id: other_track_id,
+            kind: TrackKind::Video,
+            items: vec![other_clip],
+            transitions: vec![],
+            subtitles: None,
+        });
+
+        tl.ripple_trim_out(clip_id, TimeUs(2_000_000), RippleScope::AllTracks)
+            .unwrap();
+
+        let other = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == other_id)
+            .unwrap();
+        assert_eq!(other.timeline_start_us(), TimeUs(2_000_000));
+    }
+
+    #[test]
+    fn ripple_trim_out_same_track_leaves_other_tracks_alone() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        let other_track_id = Uuid::new_v4();
+        let (other_id, other_clip) = make_video_clip(other_track_id, 5_000_000, 0, 3_000_000);
+        tl.tracks.push(Track {
+            id: other_track_id,
+            kind: TrackKind::Video,
+            items: vec![other_clip],
+            transitions: vec![],
+            subtitles: None,
+        });
+
+        tl.ripple_trim_out(clip_id, TimeUs(2_000_000), RippleScope::SameTrack)
+            .unwrap();
+
+        let other = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == other_id)
+            .unwrap();
+        assert_eq!(other.timeline_start_us(), TimeUs(5_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // ripple_delete
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn ripple_delete_pulls_later_items_left() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Second clip: [5M, 8M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        let removed = tl.ripple_delete(clip_id, RippleScope::SameTrack).unwrap();
+        assert_eq!(removed.id(), clip_id);
+
+        let second = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(second.timeline_start_us(), TimeUs(0));
+        assert_eq!(second.timeline_end_us(), TimeUs(3_000_000));
+    }
+
+    #[test]
+    fn ripple_delete_nonexistent_fails() {
+        let (mut tl, _, _) = make_test_timeline();
+        let bad_id = Uuid::new_v4();
+        let result = tl.ripple_delete(bad_id, RippleScope::SameTrack);
+        assert!(matches!(result.unwrap_err(), CoreError::ItemNotFound(_)));
+    }
+
+    #[test]
+    fn ripple_trim_out_rejects_shift_that_would_overlap_another_track() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        let other_track_id = Uuid::new_v4();
+        let (other1_id, other1) = make_video_clip(other_track_id, 2_000_000, 0, 2_000_000);
+        let (other2_id, other2) = make_video_clip(other_track_id, 6_000_000, 0, 3_000_000);
+        tl.tracks.push(Track {
+            id: other_track_id,
+            kind: TrackKind::Video,
+            items: vec![other1, other2],
+            transitions: vec![],
+            subtitles: None,
+        });
+
+        // Trimming the main clip's out to 2M shifts everything at/after 5M
+        // by -3M, which would land other2 at [3M, 6M) -- overlapping other1
+        // at [2M, 4M).
+        let result = tl.ripple_trim_out(clip_id, TimeUs(2_000_000), RippleScope::AllTracks);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        // Nothing should have moved: the edit and the shift both rolled back.
+        let clip = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(clip.timeline_end_us(), TimeUs(5_000_000));
+        let other1 = tl.tracks[1]
+            .items
+            .iter()
+            .find(|i| i.id() == other1_id)
+            .unwrap();
+        let other2 = tl.tracks[1]
+            .items
+            .iter()
+            .find(|i| i.id() == other2_id)
+            .unwrap();
+        assert_eq!(other1.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(other2.timeline_start_us(), TimeUs(6_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // ripple_trim_in
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn ripple_trim_in_shifts_later_items_on_same_track() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Second clip: [5M, 8M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Trim the first clip's in-point to 1M: its own start moves from 0
+        // to 1M (end held fixed), delta = +1M, so the second clip should
+        // shift from 5M to 6M.
+        tl.ripple_trim_in(clip_id, TimeUs(1_000_000), RippleScope::SameTrack)
+            .unwrap();
+
+        let first = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(first.timeline_start_us(), TimeUs(1_000_000));
+        assert_eq!(first.timeline_end_us(), TimeUs(5_000_000));
+
+        let second = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(second.timeline_start_us(), TimeUs(6_000_000));
+    }
+
+    #[test]
+    fn ripple_trim_in_rejects_shift_below_zero() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Trimming the second clip's in-point far enough back would push
+        // its own timeline_start below zero.
+        let result = tl.ripple_trim_in(second_id, TimeUs(-10_000_000), RippleScope::SameTrack);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        let first = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let second = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(first.timeline_end_us(), TimeUs(5_000_000));
+        assert_eq!(second.timeline_start_us(), TimeUs(5_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // ripple_remove_item
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn ripple_remove_item_pulls_later_items_left() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        let removed = tl
+            .ripple_remove_item(clip_id, RippleScope::SameTrack)
+            .unwrap();
+        assert_eq!(removed.id(), clip_id);
+
+        let second = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(second.timeline_start_us(), TimeUs(0));
+    }
+
+    #[test]
+    fn ripple_remove_item_rejects_shift_that_would_overlap_another_track() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+
+        let other_track_id = Uuid::new_v4();
+        let (other1_id, other1) = make_video_clip(other_track_id, 2_000_000, 0, 2_000_000);
+        let (other2_id, other2) = make_video_clip(other_track_id, 6_000_000, 0, 3_000_000);
+        tl.tracks.push(Track {
+            id: other_track_id,
+            kind: TrackKind::Video,
+            items: vec![other1, other2],
+            transitions: vec![],
+            subtitles: None,
+        });
+
+        // Removing the main clip shifts everything at/after 5M left by 5M,
+        // which would land other2 at [1M, 4M) -- overlapping other1.
+        let result = tl.ripple_remove_item(clip_id, RippleScope::AllTracks);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        // The removal itself should have rolled back too.
+        assert!(tl.tracks[0].items.iter().any(|i| i.id() == clip_id));
+        let other1 = tl.tracks[1]
+            .items
+            .iter()
+            .find(|i| i.id() == other1_id)
+            .unwrap();
+        let other2 = tl.tracks[1]
+            .items
+            .iter()
+            .find(|i| i.id() == other2_id)
+            .unwrap();
+        assert_eq!(other1.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(other2.timeline_start_us(), TimeUs(6_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // roll_edit
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn roll_edit_moves_boundary_keeping_span_fixed() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // First clip: [0, 5M), source [0, 5M). Second: [5M, 8M), source [0, 3M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Roll the boundary forward by 1M: first clip extends to 6M, second
+        // clip now starts at 6M.
+        tl.roll_edit(clip_id, second_id, TimeUs(1_000_000)).unwrap();
+
+        let left = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        let right = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == second_id)
+            .unwrap();
+        assert_eq!(left.timeline_end_us(), TimeUs(6_000_000));
+        assert_eq!(right.timeline_start_us(), TimeUs(6_000_000));
+        assert_eq!(right.timeline_end_us(), TimeUs(8_000_000));
+    }
+
+    #[test]
+    fn roll_edit_rejects_crossing_source_limits() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Second clip has only 3M of source material: [5M, 8M), source [0, 3M).
+        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        // Rolling forward by 4M would require trimming 4M off a 3M source clip.
+        let result = tl.roll_edit(clip_id, second_id, TimeUs(4_000_000));
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    #[test]
+    fn roll_edit_requires_adjacent_clips() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Gap between clips: [0, 5M) and [6M, 9M).
+        let (second_id, clip) = make_video_clip(track_id, 6_000_000, 0, 3_000_000);
+        tl.add_item(track_id, clip).unwrap();
+
+        let result = tl.roll_edit(clip_id, second_id, TimeUs(500_000));
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    // -----------------------------------------------------------------------
+    // splice_insert
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn splice_insert_ripples_later_items_right() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Insert a 2M clip at 0, pushing it to [2M, 7M).
+        let (new_id, new_clip) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let inserted_id = tl.splice_insert(track_id, new_clip, TimeUs(0)).unwrap();
+        assert_eq!(inserted_id, new_id);
+
+        let inserted = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == new_id)
+            .unwrap();
+        assert_eq!(inserted.timeline_start_us(), TimeUs(0));
+        assert_eq!(inserted.timeline_end_us(), TimeUs(2_000_000));
+
+        let original = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(original.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(original.timeline_end_us(), TimeUs(7_000_000));
+    }
+
+    #[test]
+    fn splice_insert_leaves_earlier_items_untouched() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Insert a 2M clip at 5M -- nothing before it moves.
+        let (_, new_clip) = make_video_clip(track_id, 0, 0, 2_000_000);
+        tl.splice_insert(track_id, new_clip, TimeUs(5_000_000))
+            .unwrap();
+
+        let original = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(original.timeline_start_us(), TimeUs(0));
+    }
+
+    #[test]
+    fn splice_insert_rejects_insert_point_straddling_an_existing_item() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        // Existing clip: [0, 5M). Inserting at 2M would land strictly inside
+        // it -- only items starting at or after 2M get rippled, so the
+        // existing clip would otherwise overlap the inserted one untouched.
+        let (_, new_clip) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let result = tl.splice_insert(track_id, new_clip, TimeUs(2_000_000));
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        let original = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(original.timeline_start_us(), TimeUs(0));
+        assert_eq!(original.timeline_end_us(), TimeUs(5_000_000));
+    }
+
+    #[test]
+    fn splice_insert_rejects_unknown_track() {
+        let (mut tl, track_id, _) = make_test_timeline();
+        let (_, new_clip) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let result = tl.splice_insert(Uuid::new_v4(), new_clip, TimeUs(0));
+        assert!(matches!(result.unwrap_err(), CoreError::TrackNotFound(_)));
+    }
+
+    // -----------------------------------------------------------------------
+    // resplice
+    // -----------------------------------------------------------------------
+
+    fn make_playlist_track(track_id: Uuid, ids: &[Uuid]) -> Track {
+        let items = ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| {
+                let start = TimeUs((i as i64) * 2_000_000);
+                Item::VideoClip {
+                    id,
+                    asset_id: Uuid::new_v4(),
+                    track_id,
+                    timeline_start_us: start,
+                    source_in_us: TimeUs::ZERO,
+                    source_out_us: TimeUs(2_000_000),
+                    speed: 1.0,
+                    fade_in_us: TimeUs::ZERO,
+                    fade_out_us: TimeUs::ZERO,
+                }
+            })
+            .collect();
+        Track {
+            id: track_id,
+            kind: TrackKind::Video,
+            items,
+            transitions: vec![],
+            subtitles: None,
+        }
+    }
+
+    #[test]
+    fn resplice_keeps_head_and_joins_at_matching_id() {
+        let track_id = Uuid::new_v4();
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![make_playlist_track(track_id, &[a, b, c])],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        // New list: b (shared, playing's successor), then two brand new clips.
+        let d = Uuid::new_v4();
+        let e = Uuid::new_v4();
+        let new_items = [b, d, e]
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| Item::VideoClip {
+                id,
+                asset_id: Uuid::new_v4(),
+                track_id,
+                timeline_start_us: TimeUs((i as i64) * 2_000_000),
+                source_in_us: TimeUs::ZERO,
+                source_out_us: TimeUs(2_000_000),
+                speed: 1.0,
+                fade_in_us: TimeUs::ZERO,
+                fade_out_us: TimeUs::ZERO,
+            })
+            .collect();
+
+        // a is playing (index 0); b is its immediate successor.
+        let result = tl.resplice(track_id, 0, new_items).unwrap();
+        assert_eq!(result.splice_point, Some(0));
+
+        let ids: Vec<Uuid> = tl.tracks[0].items.iter().map(|i| i.id()).collect();
+        // a kept unchanged, b's old copy dropped along with c, new tail is d, e.
+        assert_eq!(ids, vec![a, d, e]);
+
+        let a_item = &tl.tracks[0].items[0];
+        assert_eq!(a_item.timeline_start_us(), TimeUs(0));
+        let d_item = &tl.tracks[0].items[1];
+        assert_eq!(d_item.timeline_start_us(), a_item.timeline_end_us());
+    }
+
+    #[test]
+    fn resplice_falls_back_to_appending_when_no_shared_id() {
+        let track_id = Uuid::new_v4();
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![make_playlist_track(track_id, &[a, b])],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let d = Uuid::new_v4();
+        let new_items = vec![Item::VideoClip {
+            id: d,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::ZERO,
+            source_out_us: TimeUs(2_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        }];
+
+        let result = tl.resplice(track_id, 0, new_items).unwrap();
+        assert_eq!(result.splice_point, None);
+
+        let ids: Vec<Uuid> = tl.tracks[0].items.iter().map(|i| i.id()).collect();
+        assert_eq!(ids, vec![a, d]);
+        assert_eq!(tl.tracks[0].items[1].timeline_start_us(), TimeUs(2_000_000));
+    }
+
+    #[test]
+    fn resplice_rejects_out_of_bounds_playing_index() {
+        let track_id = Uuid::new_v4();
+        let a = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![make_playlist_track(track_id, &[a])],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let result = tl.resplice(track_id, 5, vec![]);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    // -----------------------------------------------------------------------
+    // split_at
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn split_at_creates_two_clips_summing_to_original() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Original: [0, 5M), source [0, 5M)
+        let (left_id, right_id) = tl.split_at(clip_id, TimeUs(2_000_000), None).unwrap();
+
+        assert_eq!(left_id, clip_id);
+        assert_ne!(right_id, clip_id);
+
+        let left = &tl.tracks[0].items[0];
+        let right = &tl.tracks[0].items[1];
+
+        assert_eq!(left.duration_us().0 + right.duration_us().0, 5_000_000);
+        assert_eq!(left.timeline_start_us(), TimeUs(0));
+        assert_eq!(left.timeline_end_us(), TimeUs(2_000_000));
+        assert_eq!(right.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(right.timeline_end_us(), TimeUs(5_000_000));
+    }
+
+    #[test]
+    fn split_at_preserves_source_ranges() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        tl.split_at(clip_id, TimeUs(2_000_000), None).unwrap();
+
+        let left = &tl.tracks[0].items[0];
+        let right = &tl.tracks[0].items[1];
+
+        if let Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            ..
+        } = left
+        {
+            assert_eq!(*source_in_us, TimeUs(0));
+            assert_eq!(*source_out_us, TimeUs(2_000_000));
+        } else {
+            panic!("expected VideoClip");
+        }
+
+        if let Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            ..
+        } = right
+        {
+            assert_eq!(*source_in_us, TimeUs(2_000_000));
+            assert_eq!(*source_out_us, TimeUs(5_000_000));
+        } else {
+            panic!("expected VideoClip");
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    fn make_video_clip(
-        track_id: Uuid,
-        start_us: i64,
-        source_in: i64,
-        source_out: i64,
-    ) -> (Uuid, Item) {
-        let id = Uuid::new_v4();
-        let item = Item::VideoClip {
-            id,
+    #[test]
+    fn split_at_preserves_source_ranges_with_speed() {
+        let track_id = Uuid::new_v4();
+        let clip_id = Uuid::new_v4();
+        let clip = Item::VideoClip {
+            id: clip_id,
             asset_id: Uuid::new_v4(),
             track_id,
-            timeline_start_us: TimeUs(start_us),
-            source_in_us: TimeUs(source_in),
-            source_out_us: TimeUs(source_out),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(10_000_000),
+            speed: 2.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
-        (id, item)
-    }
-
-    fn make_test_timeline() -> (Timeline, Uuid, Uuid) {
-        let track_id = Uuid::new_v4();
-        let (clip_id, clip) = make_video_clip(track_id, 0, 0, 5_000_000);
-        let tl = Timeline {
+        let mut tl = Timeline {
             tracks: vec![Track {
                 id: track_id,
                 kind: TrackKind::Video,
                 items: vec![clip],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
-        (tl, track_id, clip_id)
-    }
 
-    // -----------------------------------------------------------------------
-    // add_item
-    // -----------------------------------------------------------------------
+        // At speed 2.0 the clip's timeline duration is (10M - 0) / 2.0 = 5M,
+        // same overall span as the speed-1.0 fixture above.
+        assert_eq!(tl.tracks[0].items[0].duration_us(), TimeUs(5_000_000));
 
-    #[test]
-    fn add_item_to_empty_track_succeeds() {
-        let track_id = Uuid::new_v4();
-        let mut tl = Timeline {
-            tracks: vec![Track {
-                id: track_id,
-                kind: TrackKind::Video,
-                items: vec![],
-            }],
-            markers: vec![],
-        };
+        tl.split_at(clip_id, TimeUs(2_000_000), None).unwrap();
 
-        let (_, clip) = make_video_clip(track_id, 0, 0, 5_000_000);
-        assert!(tl.add_item(track_id, clip).is_ok());
-        assert_eq!(tl.tracks[0].items.len(), 1);
+        let left = &tl.tracks[0].items[0];
+        let right = &tl.tracks[0].items[1];
+
+        // The timeline split point (2M) maps to a source offset of
+        // 2M * speed = 4M, so the split still lands on contiguous source
+        // media even though the two halves no longer span the timeline in
+        // 1:1 proportion to their source ranges.
+        if let Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            speed,
+            ..
+        } = left
+        {
+            assert_eq!(*source_in_us, TimeUs(0));
+            assert_eq!(*source_out_us, TimeUs(4_000_000));
+            assert_eq!(*speed, 2.0);
+        } else {
+            panic!("expected VideoClip");
+        }
+
+        if let Item::VideoClip {
+            source_in_us,
+            source_out_us,
+            speed,
+            ..
+        } = right
+        {
+            assert_eq!(*source_in_us, TimeUs(4_000_000));
+            assert_eq!(*source_out_us, TimeUs(10_000_000));
+            assert_eq!(*speed, 2.0);
+        } else {
+            panic!("expected VideoClip");
+        }
+
+        assert_eq!(left.timeline_start_us(), TimeUs(0));
+        assert_eq!(left.timeline_end_us(), TimeUs(2_000_000));
+        assert_eq!(right.timeline_start_us(), TimeUs(2_000_000));
+        assert_eq!(right.timeline_end_us(), TimeUs(5_000_000));
     }
 
     #[test]
-    fn add_item_with_overlap_fails() {
-        let (mut tl, track_id, _) = make_test_timeline();
+    fn split_at_start_fails() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let result = tl.split_at(clip_id, TimeUs(0), None);
+        assert!(result.is_err());
+    }
 
-        // Existing clip: [0, 5_000_000). Try adding overlapping clip at [2_000_000, 7_000_000).
-        let (_, clip) = make_video_clip(track_id, 2_000_000, 0, 5_000_000);
-        let result = tl.add_item(track_id, clip);
+    #[test]
+    fn split_at_end_fails() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let result = tl.split_at(clip_id, TimeUs(5_000_000), None);
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
     }
 
     #[test]
-    fn add_item_adjacent_succeeds() {
-        let (mut tl, track_id, _) = make_test_timeline();
+    fn split_drops_transition_on_split_item() {
+        let (mut tl, track_id, clip_id) = make_test_timeline();
+        tl.config.overlap_mode = OverlapMode::Crossfade;
 
-        // Existing clip: [0, 5_000_000). Add adjacent clip at [5_000_000, 10_000_000).
-        let (_, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
-        assert!(tl.add_item(track_id, clip).is_ok());
-        assert_eq!(tl.tracks[0].items.len(), 2);
+        let (second_id, clip) = make_video_clip(track_id, 3_000_000, 0, 5_000_000);
+        tl.add_item(track_id, clip).unwrap();
+        assert_eq!(tl.tracks[0].transitions.len(), 1);
+
+        // Split the later (fading-in) clip partway through its fade.
+        tl.split_at(second_id, TimeUs(4_000_000), None).unwrap();
+
+        assert!(tl.tracks[0].transitions.is_empty());
+        let earlier = tl.tracks[0]
+            .items
+            .iter()
+            .find(|i| i.id() == clip_id)
+            .unwrap();
+        assert_eq!(earlier.fade_out_us(), Some(TimeUs::ZERO));
     }
 
     #[test]
-    fn add_item_to_nonexistent_track_fails() {
+    fn split_audio_clip() {
+        let track_id = Uuid::new_v4();
+        let clip_id = Uuid::new_v4();
+        let item = Item::AudioClip {
+            id: clip_id,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(6_000_000),
+            volume: 0.8,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
         let mut tl = Timeline {
-            tracks: vec![],
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Audio,
+                items: vec![item],
+                transitions: vec![],
+                subtitles: None,
+            }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
-        let fake_track = Uuid::new_v4();
-        let (_, clip) = make_video_clip(fake_track, 0, 0, 5_000_000);
-        let result = tl.add_item(fake_track, clip);
-        assert!(matches!(result.unwrap_err(), CoreError::TrackNotFound(_)));
+
+        let (left_id, right_id) = tl.split_at(clip_id, TimeUs(3_000_000), None).unwrap();
+        assert_eq!(left_id, clip_id);
+
+        let left = &tl.tracks[0].items[0];
+        let right = &tl.tracks[0].items[1];
+        assert_eq!(left.duration_us(), TimeUs(3_000_000));
+        assert_eq!(right.duration_us(), TimeUs(3_000_000));
+        assert_eq!(right.id(), right_id);
+
+        if let Item::AudioClip { volume, .. } = right {
+            assert!((volume - 0.8).abs() < f64::EPSILON);
+        }
     }
 
     // -----------------------------------------------------------------------
-    // remove_item
+    // split_into_segments
     // -----------------------------------------------------------------------
 
     #[test]
-    fn remove_item_works() {
+    fn split_into_segments_evenly_spaced_without_markers() {
         let (mut tl, _, clip_id) = make_test_timeline();
-        let removed = tl.remove_item(clip_id).unwrap();
-        assert_eq!(removed.id(), clip_id);
-        assert!(tl.tracks[0].items.is_empty());
+        // Original: [0, 5M). Max segment 2M -> pieces of 2M, 2M, 1M.
+        let pieces = tl.split_into_segments(clip_id, TimeUs(2_000_000)).unwrap();
+
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0], clip_id);
+
+        let items = &tl.tracks[0].items;
+        assert_eq!(items.len(), 3);
+        for (piece_id, item) in pieces.iter().zip(items.iter()) {
+            assert_eq!(*piece_id, item.id());
+        }
+        assert_eq!(items[0].duration_us(), TimeUs(2_000_000));
+        assert_eq!(items[1].duration_us(), TimeUs(2_000_000));
+        assert_eq!(items[2].duration_us(), TimeUs(1_000_000));
+        assert_eq!(items[2].timeline_end_us(), TimeUs(5_000_000));
     }
 
     #[test]
-    fn remove_item_with_bad_id_fails() {
-        let (mut tl, _, _) = make_test_timeline();
-        let bad_id = Uuid::new_v4();
-        let result = tl.remove_item(bad_id);
-        assert!(matches!(result.unwrap_err(), CoreError::ItemNotFound(_)));
-    }
+    fn split_into_segments_snaps_to_nearby_marker() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        // Original: [0, 5M), max segment 2M -> ideal first cut at 2M, but a
+        // marker at 2.1M is within tolerance and should be preferred.
+        tl.markers.push(Marker {
+            id: Uuid::new_v4(),
+            time_us: TimeUs(2_100_000),
+            label: "beat".to_string(),
+        });
 
-    // -----------------------------------------------------------------------
-    // move_item
-    // -----------------------------------------------------------------------
+        let pieces = tl.split_into_segments(clip_id, TimeUs(2_000_000)).unwrap();
+
+        let items = &tl.tracks[0].items;
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(items[0].timeline_end_us(), TimeUs(2_100_000));
+    }
 
     #[test]
-    fn move_item_to_valid_position() {
+    fn split_into_segments_never_emits_zero_length_trailing_piece() {
         let (mut tl, _, clip_id) = make_test_timeline();
-        // Move clip from [0, 5M) to [10M, 15M)
-        assert!(tl.move_item(clip_id, TimeUs(10_000_000)).is_ok());
-        let item = &tl.tracks[0].items[0];
-        assert_eq!(item.timeline_start_us(), TimeUs(10_000_000));
+        // Original: [0, 5M), max segment exactly divides the duration.
+        let pieces = tl.split_into_segments(clip_id, TimeUs(2_500_000)).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+        let items = &tl.tracks[0].items;
+        assert_eq!(items[0].duration_us(), TimeUs(2_500_000));
+        assert_eq!(items[1].duration_us(), TimeUs(2_500_000));
     }
 
     #[test]
-    fn move_item_causing_overlap_fails() {
-        let (mut tl, track_id, _clip_id) = make_test_timeline();
-
-        // Add second clip at [5M, 10M)
-        let (second_id, clip) = make_video_clip(track_id, 5_000_000, 0, 5_000_000);
-        tl.add_item(track_id, clip).unwrap();
+    fn split_into_segments_noop_when_already_within_max() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let pieces = tl.split_into_segments(clip_id, TimeUs(10_000_000)).unwrap();
 
-        // Try to move second clip to [3M, 8M) -- overlaps first clip [0, 5M)
-        let result = tl.move_item(second_id, TimeUs(3_000_000));
-        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+        assert_eq!(pieces, vec![clip_id]);
+        assert_eq!(tl.tracks[0].items.len(), 1);
     }
 
     #[test]
-    fn move_item_nonexistent_fails() {
-        let (mut tl, _, _) = make_test_timeline();
-        let bad_id = Uuid::new_v4();
-        let result = tl.move_item(bad_id, TimeUs(0));
-        assert!(matches!(result.unwrap_err(), CoreError::ItemNotFound(_)));
+    fn split_into_segments_rejects_non_positive_max_duration() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let result = tl.split_into_segments(clip_id, TimeUs::ZERO);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
     }
 
     // -----------------------------------------------------------------------
-    // trim_in
+    // copy_items / paste
     // -----------------------------------------------------------------------
 
+    fn make_av_pair_timeline() -> (Timeline, Uuid, Uuid, Uuid, Uuid) {
+        let video_track = Uuid::new_v4();
+        let audio_track = Uuid::new_v4();
+        let (video_id, video_clip) = make_video_clip(video_track, 0, 0, 5_000_000);
+        let audio_clip = Item::AudioClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id: audio_track,
+            timeline_start_us: TimeUs(1_000_000),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(4_000_000),
+            volume: 1.0,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let audio_id = audio_clip.id();
+
+        let tl = Timeline {
+            tracks: vec![
+                Track {
+                    id: video_track,
+                    kind: TrackKind::Video,
+                    items: vec![video_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+                Track {
+                    id: audio_track,
+                    kind: TrackKind::Audio,
+                    items: vec![audio_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        (tl, video_track, audio_track, video_id, audio_id)
+    }
+
     #[test]
-    fn trim_in_adjusts_start_correctly() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        // Original: timeline_start=0, source_in=0, source_out=5M, end=5M
-        // Trim in to source_in=1M. End stays at 5M, new duration=4M, new timeline_start=1M
-        tl.trim_in(clip_id, TimeUs(1_000_000)).unwrap();
+    fn copy_items_rejects_empty_selection() {
+        let (tl, ..) = make_av_pair_timeline();
+        let result = tl.copy_items(&[]);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
 
-        let item = &tl.tracks[0].items[0];
-        assert_eq!(item.timeline_end_us(), TimeUs(5_000_000));
-        assert_eq!(item.duration_us(), TimeUs(4_000_000));
-        assert_eq!(item.timeline_start_us(), TimeUs(1_000_000));
-        if let Item::VideoClip { source_in_us, .. } = item {
-            assert_eq!(*source_in_us, TimeUs(1_000_000));
-        }
+    #[test]
+    fn copy_items_captures_min_start_and_track_kinds() {
+        let (tl, _, _, video_id, audio_id) = make_av_pair_timeline();
+        let clipboard = tl.copy_items(&[video_id, audio_id]).unwrap();
+
+        assert_eq!(clipboard.items.len(), 2);
+        assert_eq!(clipboard.min_start_us, TimeUs(0));
+        assert!(clipboard
+            .items
+            .iter()
+            .any(|c| c.item.id() == video_id && c.source_track_kind == TrackKind::Video));
+        assert!(clipboard
+            .items
+            .iter()
+            .any(|c| c.item.id() == audio_id && c.source_track_kind == TrackKind::Audio));
     }
 
     #[test]
-    fn trim_in_invalid_past_out_point_fails() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        // source_out is 5M, try to set source_in to 6M
-        let result = tl.trim_in(clip_id, TimeUs(6_000_000));
-        assert!(result.is_err());
+    fn paste_preserves_relative_spacing_and_maps_tracks_by_kind() {
+        let (mut tl, video_track, audio_track, video_id, audio_id) = make_av_pair_timeline();
+        let clipboard = tl.copy_items(&[video_id, audio_id]).unwrap();
+
+        // video started at 0, audio at 1M -- paste starting at 10M should
+        // preserve that 1M gap.
+        let new_ids = tl
+            .paste(&clipboard, video_track, TimeUs(10_000_000))
+            .unwrap();
+        assert_eq!(new_ids.len(), 2);
+
+        let video_track_ref = tl.tracks.iter().find(|t| t.id == video_track).unwrap();
+        let audio_track_ref = tl.tracks.iter().find(|t| t.id == audio_track).unwrap();
+        assert_eq!(video_track_ref.items.len(), 2);
+        assert_eq!(audio_track_ref.items.len(), 2);
+
+        let pasted_video = video_track_ref
+            .items
+            .iter()
+            .find(|i| i.id() != video_id)
+            .unwrap();
+        let pasted_audio = audio_track_ref
+            .items
+            .iter()
+            .find(|i| i.id() != audio_id)
+            .unwrap();
+        assert_eq!(pasted_video.timeline_start_us(), TimeUs(10_000_000));
+        assert_eq!(pasted_audio.timeline_start_us(), TimeUs(11_000_000));
+
+        // Fresh ids, not the originals.
+        assert_ne!(pasted_video.id(), video_id);
+        assert_ne!(pasted_audio.id(), audio_id);
+    }
+
+    #[test]
+    fn paste_rejects_unknown_target_track() {
+        let (mut tl, _, _, video_id, audio_id) = make_av_pair_timeline();
+        let clipboard = tl.copy_items(&[video_id, audio_id]).unwrap();
+        let result = tl.paste(&clipboard, Uuid::new_v4(), TimeUs(0));
+        assert!(matches!(result.unwrap_err(), CoreError::TrackNotFound(_)));
+    }
+
+    #[test]
+    fn paste_inserts_nothing_if_any_item_would_overlap() {
+        let (mut tl, video_track, _, video_id, audio_id) = make_av_pair_timeline();
+        let clipboard = tl.copy_items(&[video_id, audio_id]).unwrap();
+
+        // Paste at 0 collides with the original video clip [0, 5M).
+        let result = tl.paste(&clipboard, video_track, TimeUs(0));
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        // Nothing should have been inserted on either track.
+        assert_eq!(tl.tracks[0].items.len(), 1);
+        assert_eq!(tl.tracks[1].items.len(), 1);
     }
 
     // -----------------------------------------------------------------------
-    // trim_out
+    // groups
     // -----------------------------------------------------------------------
 
-    #[test]
-    fn trim_out_adjusts_end_correctly() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        // Original: timeline_start=0, source_in=0, source_out=5M
-        // Trim out to 3M: new end = 0 + 3M = 3M
-        tl.trim_out(clip_id, TimeUs(3_000_000)).unwrap();
+    fn make_grouped_av_timeline() -> (Timeline, Uuid, Uuid, Uuid) {
+        let video_track = Uuid::new_v4();
+        let audio_track = Uuid::new_v4();
+        let (video_id, video_clip) = make_video_clip(video_track, 0, 0, 5_000_000);
+        let audio_clip = Item::AudioClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id: audio_track,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(5_000_000),
+            volume: 1.0,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let audio_id = audio_clip.id();
 
-        let item = &tl.tracks[0].items[0];
-        assert_eq!(item.timeline_start_us(), TimeUs(0));
-        assert_eq!(item.timeline_end_us(), TimeUs(3_000_000));
-        assert_eq!(item.duration_us(), TimeUs(3_000_000));
+        let mut tl = Timeline {
+            tracks: vec![
+                Track {
+                    id: video_track,
+                    kind: TrackKind::Video,
+                    items: vec![video_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+                Track {
+                    id: audio_track,
+                    kind: TrackKind::Audio,
+                    items: vec![audio_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        let group_id = tl.group_items(&[video_id, audio_id]).unwrap();
+        (tl, group_id, video_id, audio_id)
     }
 
     #[test]
-    fn trim_out_invalid_before_in_point_fails() {
-        let (mut tl, track_id, _) = make_test_timeline();
-        // Add a clip with source_in=2M, source_out=5M
-        let (clip_id, clip) = make_video_clip(track_id, 10_000_000, 2_000_000, 5_000_000);
-        tl.add_item(track_id, clip).unwrap();
+    fn group_items_requires_at_least_two() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let result = tl.group_items(&[clip_id]);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
 
-        // Try to trim out to 1M (before source_in of 2M)
-        let result = tl.trim_out(clip_id, TimeUs(1_000_000));
-        assert!(result.is_err());
+    #[test]
+    fn group_items_rejects_already_grouped_item() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
+        let result = tl.group_items(&[video_id, audio_id]);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
     }
 
-    // -----------------------------------------------------------------------
-    // split_at
-    // -----------------------------------------------------------------------
-
     #[test]
-    fn split_at_creates_two_clips_summing_to_original() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        // Original: [0, 5M), source [0, 5M)
-        let (left_id, right_id) = tl.split_at(clip_id, TimeUs(2_000_000)).unwrap();
+    fn ungroup_dissolves_group_without_touching_items() {
+        let (mut tl, group_id, video_id, _) = make_grouped_av_timeline();
+        tl.ungroup(group_id).unwrap();
+        assert!(tl.groups.is_empty());
+        assert!(tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .any(|i| i.id() == video_id));
+    }
 
-        assert_eq!(left_id, clip_id);
-        assert_ne!(right_id, clip_id);
+    #[test]
+    fn ungroup_nonexistent_fails() {
+        let (mut tl, _, _, _) = make_grouped_av_timeline();
+        let result = tl.ungroup(Uuid::new_v4());
+        assert!(matches!(result.unwrap_err(), CoreError::GroupNotFound(_)));
+    }
 
-        let left = &tl.tracks[0].items[0];
-        let right = &tl.tracks[0].items[1];
+    #[test]
+    fn move_item_on_grouped_item_moves_sibling_too() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
+        tl.move_item(video_id, TimeUs(10_000_000), None).unwrap();
 
-        assert_eq!(left.duration_us().0 + right.duration_us().0, 5_000_000);
-        assert_eq!(left.timeline_start_us(), TimeUs(0));
-        assert_eq!(left.timeline_end_us(), TimeUs(2_000_000));
-        assert_eq!(right.timeline_start_us(), TimeUs(2_000_000));
-        assert_eq!(right.timeline_end_us(), TimeUs(5_000_000));
+        let video = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == video_id)
+            .unwrap();
+        let audio = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == audio_id)
+            .unwrap();
+        assert_eq!(video.timeline_start_us(), TimeUs(10_000_000));
+        assert_eq!(audio.timeline_start_us(), TimeUs(10_000_000));
     }
 
     #[test]
-    fn split_at_preserves_source_ranges() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        tl.split_at(clip_id, TimeUs(2_000_000)).unwrap();
+    fn move_item_on_grouped_item_rolls_back_on_sibling_overlap() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
 
-        let left = &tl.tracks[0].items[0];
-        let right = &tl.tracks[0].items[1];
+        // Add a blocking clip on the audio track at [10M, 15M).
+        let audio_track_id = tl.tracks[1].id;
+        let (_, blocker) = make_video_clip(audio_track_id, 10_000_000, 0, 5_000_000);
+        tl.tracks[1].items.push(blocker);
 
-        if let Item::VideoClip {
-            source_in_us,
-            source_out_us,
-            ..
-        } = left
-        {
-            assert_eq!(*source_in_us, TimeUs(0));
-            assert_eq!(*source_out_us, TimeUs(2_000_000));
-        } else {
-            panic!("expected VideoClip");
-        }
+        let result = tl.move_item(video_id, TimeUs(10_000_000), None);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
 
-        if let Item::VideoClip {
-            source_in_us,
-            source_out_us,
-            ..
-        } = right
-        {
-            assert_eq!(*source_in_us, TimeUs(2_000_000));
-            assert_eq!(*source_out_us, TimeUs(5_000_000));
-        } else {
-            panic!("expected VideoClip");
-        }
+        // Nothing should have moved.
+        let video = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == video_id)
+            .unwrap();
+        let audio = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == audio_id)
+            .unwrap();
+        assert_eq!(video.timeline_start_us(), TimeUs(0));
+        assert_eq!(audio.timeline_start_us(), TimeUs(0));
     }
 
     #[test]
-    fn split_at_start_fails() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        let result = tl.split_at(clip_id, TimeUs(0));
-        assert!(result.is_err());
+    fn trim_in_on_grouped_item_shifts_sibling_by_same_delta() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
+        // Trim video's in-point to 1M: timeline_start moves from 0 to 1M.
+        tl.trim_in(video_id, TimeUs(1_000_000), None).unwrap();
+
+        let audio = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == audio_id)
+            .unwrap();
+        assert_eq!(audio.timeline_start_us(), TimeUs(1_000_000));
     }
 
     #[test]
-    fn split_at_end_fails() {
-        let (mut tl, _, clip_id) = make_test_timeline();
-        let result = tl.split_at(clip_id, TimeUs(5_000_000));
-        assert!(result.is_err());
+    fn trim_out_on_grouped_item_trims_sibling_by_same_delta() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
+        // Trim video's out-point back to 3M: delta = 3M - 5M = -2M.
+        tl.trim_out(video_id, TimeUs(3_000_000), None).unwrap();
+
+        let audio = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == audio_id)
+            .unwrap();
+        assert_eq!(audio.timeline_end_us(), TimeUs(3_000_000));
     }
 
     #[test]
-    fn split_audio_clip() {
-        let track_id = Uuid::new_v4();
-        let clip_id = Uuid::new_v4();
-        let item = Item::AudioClip {
-            id: clip_id,
+    fn trim_out_on_grouped_item_rolls_back_on_sibling_overlap() {
+        let (mut tl, _, video_id, audio_id) = make_grouped_av_timeline();
+        let audio_track = tl.find_item_location(audio_id).unwrap().0;
+        let other_id = Uuid::new_v4();
+        let other_clip = Item::AudioClip {
+            id: other_id,
             asset_id: Uuid::new_v4(),
-            track_id,
-            timeline_start_us: TimeUs(0),
+            track_id: tl.tracks[audio_track].id,
+            timeline_start_us: TimeUs(6_000_000),
             source_in_us: TimeUs(0),
-            source_out_us: TimeUs(6_000_000),
-            volume: 0.8,
-        };
-        let mut tl = Timeline {
-            tracks: vec![Track {
-                id: track_id,
-                kind: TrackKind::Audio,
-                items: vec![item],
-            }],
-            markers: vec![],
+            source_out_us: TimeUs(1_000_000),
+            volume: 1.0,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
+        tl.tracks[audio_track].items.push(other_clip);
 
-        let (left_id, right_id) = tl.split_at(clip_id, TimeUs(3_000_000)).unwrap();
-        assert_eq!(left_id, clip_id);
+        // Extending the video's out-point to 7M would extend audio to 7M
+        // too, which overlaps the lone clip sitting at [6M, 7M) on the
+        // audio track.
+        let result = tl.trim_out(video_id, TimeUs(7_000_000), None);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
 
-        let left = &tl.tracks[0].items[0];
-        let right = &tl.tracks[0].items[1];
-        assert_eq!(left.duration_us(), TimeUs(3_000_000));
-        assert_eq!(right.duration_us(), TimeUs(3_000_000));
-        assert_eq!(right.id(), right_id);
+        let video = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == video_id)
+            .unwrap();
+        let audio = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| i.id() == audio_id)
+            .unwrap();
+        assert_eq!(video.timeline_end_us(), TimeUs(5_000_000));
+        assert_eq!(audio.timeline_end_us(), TimeUs(5_000_000));
+        assert!(tl.tracks[audio_track]
+            .items
+            .iter()
+            .any(|i| i.id() == other_id));
+    }
 
-        if let Item::AudioClip { volume, .. } = right {
-            assert!((volume - 0.8).abs() < f64::EPSILON);
-        }
+    #[test]
+    fn remove_item_on_grouped_item_removes_sibling_and_group() {
+        let (mut tl, group_id, video_id, audio_id) = make_grouped_av_timeline();
+        let removed = tl.remove_item(video_id).unwrap();
+        assert_eq!(removed.id(), video_id);
+
+        assert!(tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .all(|i| i.id() != video_id));
+        assert!(tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .all(|i| i.id() != audio_id));
+        assert!(tl.groups.iter().all(|g| g.id != group_id));
+    }
+
+    #[test]
+    fn split_at_on_grouped_item_splits_sibling_and_regroups_right_halves() {
+        let (mut tl, group_id, video_id, audio_id) = make_grouped_av_timeline();
+        let (left_id, right_id) = tl.split_at(video_id, TimeUs(2_000_000), None).unwrap();
+        assert_eq!(left_id, video_id);
+
+        // Original group still holds the (unchanged-id) left-hand pieces.
+        let original_group = tl.groups.iter().find(|g| g.id == group_id).unwrap();
+        assert!(original_group.item_ids.contains(&video_id));
+        assert!(original_group.item_ids.contains(&audio_id));
+
+        // A new group holds the two right-hand pieces.
+        let new_group = tl.groups.iter().find(|g| g.id != group_id).unwrap();
+        assert!(new_group.item_ids.contains(&right_id));
+        assert_eq!(new_group.item_ids.len(), 2);
+
+        let audio_right = tl
+            .tracks
+            .iter()
+            .flat_map(|t| &t.items)
+            .find(|i| new_group.item_ids.contains(&i.id()) && i.id() != right_id)
+            .unwrap();
+        assert_eq!(audio_right.timeline_start_us(), TimeUs(2_000_000));
     }
 
     // -----------------------------------------------------------------------
@@ -773,8 +4164,12 @@ mod tests {
                 id: track_id,
                 kind: TrackKind::Video,
                 items: vec![clip_a, clip_b, clip_c],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
 
         // Move item C (index 2) to index 0
@@ -791,6 +4186,146 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // -----------------------------------------------------------------------
+    // swap_items
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn swap_items_exchanges_timeline_start() {
+        let track_id = Uuid::new_v4();
+        let (id_a, clip_a) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (id_b, clip_b) = make_video_clip(track_id, 10_000_000, 0, 2_000_000);
+
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip_a, clip_b],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        tl.swap_items(id_a, id_b).unwrap();
+
+        let a = tl.tracks[0].items.iter().find(|i| i.id() == id_a).unwrap();
+        let b = tl.tracks[0].items.iter().find(|i| i.id() == id_b).unwrap();
+        assert_eq!(a.timeline_start_us(), TimeUs(10_000_000));
+        assert_eq!(b.timeline_start_us(), TimeUs(0));
+    }
+
+    #[test]
+    fn swap_items_rejects_mismatched_duration() {
+        let track_id = Uuid::new_v4();
+        let (id_a, clip_a) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (id_b, clip_b) = make_video_clip(track_id, 10_000_000, 0, 3_000_000);
+
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip_a, clip_b],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let result = tl.swap_items(id_a, id_b);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    #[test]
+    fn swap_items_rejects_audio_with_video() {
+        let video_track = Uuid::new_v4();
+        let audio_track = Uuid::new_v4();
+        let (video_id, video_clip) = make_video_clip(video_track, 0, 0, 2_000_000);
+        let audio_clip = Item::AudioClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id: audio_track,
+            timeline_start_us: TimeUs(10_000_000),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(2_000_000),
+            volume: 1.0,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let audio_id = audio_clip.id();
+
+        let mut tl = Timeline {
+            tracks: vec![
+                Track {
+                    id: video_track,
+                    kind: TrackKind::Video,
+                    items: vec![video_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+                Track {
+                    id: audio_track,
+                    kind: TrackKind::Audio,
+                    items: vec![audio_clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let result = tl.swap_items(video_id, audio_id);
+        assert!(matches!(
+            result.unwrap_err(),
+            CoreError::InvalidOperation(_)
+        ));
+    }
+
+    #[test]
+    fn swap_items_rolls_back_on_resulting_overlap() {
+        let track_id = Uuid::new_v4();
+        // c already sits at [0, 2M), the same slot as a -- constructed
+        // directly (bypassing add_item) to set up a fixture where b's
+        // destination is occupied. Swapping a and b would move b into
+        // a's old [0, 2M) slot, still colliding with c.
+        let (id_a, clip_a) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (id_b, clip_b) = make_video_clip(track_id, 10_000_000, 0, 2_000_000);
+        let (id_c, clip_c) = make_video_clip(track_id, 0, 0, 2_000_000);
+
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip_a, clip_b, clip_c],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let result = tl.swap_items(id_a, id_b);
+        assert!(matches!(result.unwrap_err(), CoreError::OverlapDetected));
+
+        let a = tl.tracks[0].items.iter().find(|i| i.id() == id_a).unwrap();
+        let b = tl.tracks[0].items.iter().find(|i| i.id() == id_b).unwrap();
+        let c = tl.tracks[0].items.iter().find(|i| i.id() == id_c).unwrap();
+        assert_eq!(a.timeline_start_us(), TimeUs(0));
+        assert_eq!(b.timeline_start_us(), TimeUs(10_000_000));
+        assert_eq!(c.timeline_start_us(), TimeUs(0));
+    }
+
     // -----------------------------------------------------------------------
     // overlap detection edge cases
     // -----------------------------------------------------------------------
@@ -826,8 +4361,12 @@ mod tests {
                 id: track_id,
                 kind: TrackKind::Video,
                 items: vec![],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
 
         // Add a clip at [0, 5M)
@@ -839,7 +4378,7 @@ mod tests {
         tl.add_item(track_id, clip2).unwrap();
 
         // Split first clip at 2M
-        let (_, right_id) = tl.split_at(clip1_id, TimeUs(2_000_000)).unwrap();
+        let (_, right_id) = tl.split_at(clip1_id, TimeUs(2_000_000), None).unwrap();
         assert_eq!(tl.tracks[0].items.len(), 3);
 
         // Remove the right half of the split
@@ -847,7 +4386,7 @@ mod tests {
         assert_eq!(tl.tracks[0].items.len(), 2);
 
         // Move clip2 to [2M, 7M)
-        tl.move_item(clip2_id, TimeUs(2_000_000)).unwrap();
+        tl.move_item(clip2_id, TimeUs(2_000_000), None).unwrap();
 
         let items = &tl.tracks[0].items;
         // Clip1-left at [0, 2M), clip2 at [2M, 7M) -- no overlap
@@ -878,12 +4417,16 @@ mod tests {
                 id: track_id,
                 kind: TrackKind::OverlayText,
                 items: vec![item],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
 
         // Trim in: move start to 3M, end stays at 10M
-        tl.trim_in(item_id, TimeUs(3_000_000)).unwrap();
+        tl.trim_in(item_id, TimeUs(3_000_000), None).unwrap();
         let item = &tl.tracks[0].items[0];
         assert_eq!(item.timeline_start_us(), TimeUs(3_000_000));
         assert_eq!(item.timeline_end_us(), TimeUs(10_000_000));
@@ -911,15 +4454,256 @@ mod tests {
                 id: track_id,
                 kind: TrackKind::OverlayImage,
                 items: vec![item],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
 
         // Trim out: end at 6M
-        tl.trim_out(item_id, TimeUs(6_000_000)).unwrap();
+        tl.trim_out(item_id, TimeUs(6_000_000), None).unwrap();
         let item = &tl.tracks[0].items[0];
         assert_eq!(item.timeline_start_us(), TimeUs(0));
         assert_eq!(item.timeline_end_us(), TimeUs(6_000_000));
         assert_eq!(item.duration_us(), TimeUs(6_000_000));
     }
+
+    // -----------------------------------------------------------------------
+    // compound clips
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn create_compound_from_selection_groups_items_into_one_clip() {
+        let track_id = Uuid::new_v4();
+        let (clip1_id, clip1) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (clip2_id, clip2) = make_video_clip(track_id, 2_000_000, 0, 3_000_000);
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip1, clip2],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let compound_id = tl
+            .create_compound_from_selection(&[clip1_id, clip2_id])
+            .unwrap();
+
+        assert_eq!(tl.tracks[0].items.len(), 1);
+        let compound = &tl.tracks[0].items[0];
+        assert_eq!(compound.id(), compound_id);
+        assert_eq!(compound.timeline_start_us(), TimeUs(0));
+        assert_eq!(compound.timeline_end_us(), TimeUs(5_000_000));
+
+        match compound {
+            Item::CompoundClip { sequence, .. } => {
+                assert_eq!(sequence.tracks.len(), 1);
+                assert_eq!(sequence.tracks[0].items.len(), 2);
+                assert_eq!(sequence.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+                assert_eq!(
+                    sequence.tracks[0].items[1].timeline_start_us(),
+                    TimeUs(2_000_000)
+                );
+            }
+            _ => panic!("expected CompoundClip"),
+        }
+    }
+
+    #[test]
+    fn all_referenced_asset_ids_recurses_into_compound_clip_sequence() {
+        let track_id = Uuid::new_v4();
+        let (clip1_id, clip1) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (clip2_id, clip2) = make_video_clip(track_id, 2_000_000, 0, 3_000_000);
+        let clip1_asset_id = clip1.asset_id().unwrap();
+        let clip2_asset_id = clip2.asset_id().unwrap();
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip1, clip2],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        tl.create_compound_from_selection(&[clip1_id, clip2_id])
+            .unwrap();
+
+        // The top-level timeline now holds only the CompoundClip, whose own
+        // `asset_id()` is `None` -- but both original clips' assets are still
+        // referenced through its nested sequence.
+        let referenced = tl.all_referenced_asset_ids();
+        assert!(referenced.contains(&clip1_asset_id));
+        assert!(referenced.contains(&clip2_asset_id));
+        assert_eq!(referenced.len(), 2);
+    }
+
+    #[test]
+    fn create_compound_from_selection_rejects_empty_selection() {
+        let (mut tl, _, _) = make_test_timeline();
+        let result = tl.create_compound_from_selection(&[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn compound_clip_moves_trims_and_splits_like_a_regular_clip() {
+        let track_id = Uuid::new_v4();
+        let (clip1_id, clip1) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (clip2_id, clip2) = make_video_clip(track_id, 2_000_000, 0, 3_000_000);
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip1, clip2],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        let compound_id = tl
+            .create_compound_from_selection(&[clip1_id, clip2_id])
+            .unwrap();
+
+        tl.move_item(compound_id, TimeUs(1_000_000), None).unwrap();
+        let compound = &tl.tracks[0].items[0];
+        assert_eq!(compound.timeline_start_us(), TimeUs(1_000_000));
+        assert_eq!(compound.timeline_end_us(), TimeUs(6_000_000));
+
+        tl.trim_out(compound_id, TimeUs(4_000_000), None).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_end_us(), TimeUs(5_000_000));
+
+        let (left_id, right_id) = tl.split_at(compound_id, TimeUs(3_000_000), None).unwrap();
+        assert_eq!(tl.tracks[0].items.len(), 2);
+        let left = &tl.tracks[0].items[0];
+        let right = &tl.tracks[0].items[1];
+        assert_eq!(left.id(), left_id);
+        assert_eq!(right.id(), right_id);
+        assert_eq!(left.timeline_end_us(), right.timeline_start_us());
+    }
+
+    #[test]
+    fn resolve_clip_at_recurses_into_compound_sequence() {
+        let track_id = Uuid::new_v4();
+        let (clip1_id, clip1) = make_video_clip(track_id, 0, 0, 2_000_000);
+        let (_, clip2) = make_video_clip(track_id, 2_000_000, 0, 3_000_000);
+        let clip2_asset_id = match &clip2 {
+            Item::VideoClip { asset_id, .. } => *asset_id,
+            _ => unreachable!(),
+        };
+        let clip2_id = clip2.id();
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![clip1, clip2],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        tl.create_compound_from_selection(&[clip1_id, clip2_id])
+            .unwrap();
+
+        // Playhead at 3M falls inside the compound, 1M into clip2's slot.
+        let resolved = tl.resolve_clip_at(TimeUs(3_000_000)).unwrap();
+        assert_eq!(resolved.asset_id, clip2_asset_id);
+        assert_eq!(resolved.seek_us, TimeUs(1_000_000));
+        assert_eq!(resolved.clip_start_us, TimeUs(2_000_000));
+        assert_eq!(resolved.clip_end_us, TimeUs(5_000_000));
+    }
+
+    #[test]
+    fn resolve_overlays_at_rebases_nested_overlay_into_outer_frame() {
+        let video_track = Uuid::new_v4();
+        let overlay_track = Uuid::new_v4();
+        let (clip_id, clip) = make_video_clip(video_track, 0, 0, 5_000_000);
+        let overlay_id = Uuid::new_v4();
+        let overlay = Item::TextOverlay {
+            id: overlay_id,
+            track_id: overlay_track,
+            timeline_start_us: TimeUs(1_000_000),
+            duration_us: TimeUs(2_000_000),
+            text: "Hi".into(),
+            font_size: 24,
+            color: "#fff".into(),
+            x: 0,
+            y: 0,
+        };
+        let mut tl = Timeline {
+            tracks: vec![
+                Track {
+                    id: video_track,
+                    kind: TrackKind::Video,
+                    items: vec![clip],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+                Track {
+                    id: overlay_track,
+                    kind: TrackKind::OverlayText,
+                    items: vec![overlay],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        let compound_id = tl
+            .create_compound_from_selection(&[clip_id, overlay_id])
+            .unwrap();
+        tl.move_item(compound_id, TimeUs(10_000_000), None).unwrap();
+
+        // Overlay was at 1M inside the sub-sequence; compound now starts at
+        // 10M, so the overlay should appear rebased to 11M in the outer frame.
+        let overlays = tl.resolve_overlays_at(TimeUs(11_500_000));
+        assert_eq!(overlays.len(), 1);
+        assert_eq!(overlays[0].timeline_start_us(), TimeUs(11_000_000));
+    }
+
+    #[test]
+    fn resolve_compound_path_finds_nested_sequence() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let compound_id = tl.create_compound_from_selection(&[clip_id]).unwrap();
+
+        let nested = tl.resolve_compound_path(&[compound_id]).unwrap();
+        assert_eq!(nested.tracks[0].items.len(), 1);
+        assert_eq!(nested.tracks[0].items[0].id(), clip_id);
+
+        assert!(tl.resolve_compound_path(&[Uuid::new_v4()]).is_none());
+        assert_eq!(
+            tl.resolve_compound_path(&[]).unwrap().tracks[0].items[0].id(),
+            compound_id
+        );
+    }
+
+    #[test]
+    fn resolve_compound_path_mut_allows_editing_nested_sequence() {
+        let (mut tl, _, clip_id) = make_test_timeline();
+        let compound_id = tl.create_compound_from_selection(&[clip_id]).unwrap();
+
+        let nested = tl.resolve_compound_path_mut(&[compound_id]).unwrap();
+        nested.move_item(clip_id, TimeUs(1_000_000), None).unwrap();
+
+        let nested = tl.resolve_compound_path(&[compound_id]).unwrap();
+        assert_eq!(
+            nested.tracks[0].items[0].timeline_start_us(),
+            TimeUs(1_000_000)
+        );
+    }
 }
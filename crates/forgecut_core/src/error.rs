@@ -14,6 +14,9 @@ pub enum CoreError {
     #[error("Track not found: {0}")]
     TrackNotFound(uuid::Uuid),
 
+    #[error("Group not found: {0}")]
+    GroupNotFound(uuid::Uuid),
+
     #[error("Overlap detected")]
     OverlapDetected,
 
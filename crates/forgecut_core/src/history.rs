@@ -1,6 +1,9 @@
 use crate::error::{CoreError, Result};
 use crate::types::*;
-use std::cell::RefCell;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant, SystemTime};
 use uuid::Uuid;
 
 /// A command that can be executed, undone, and described.
@@ -8,17 +11,227 @@ pub trait Command: std::fmt::Debug {
     fn execute(&self, timeline: &mut Timeline) -> Result<()>;
     fn undo(&self, timeline: &mut Timeline) -> Result<()>;
     fn description(&self) -> &str;
+    /// A serializable record of this command, capturing both its forward
+    /// parameters and the state it captured during `execute`. Only valid to
+    /// call after `execute` has succeeded.
+    fn log_entry(&self) -> EditCommand;
+
+    /// Whether this (already-pushed) command should coalesce with `next`
+    /// into a single undo step, borrowing the merge model from the `undo`
+    /// crate -- so a drag gesture that emits dozens of `MoveItemCommand`s
+    /// collapses into one. Implementations that return `Merge::Yes` must
+    /// also update their own captured target value in place (via interior
+    /// mutability) to `next`'s, so undoing the combined command reverts the
+    /// whole gesture at once. Default: never merge.
+    fn merge(&self, _next: &dyn Command) -> Merge {
+        Merge::No
+    }
+
+    /// Downcasting hook so [`merge`](Command::merge) implementations can
+    /// inspect a concrete sibling type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// The result of [`Command::merge`]: whether a new command should be folded
+/// into the previous undo-stack entry instead of becoming its own step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    /// Push the new command as its own undo step.
+    No,
+    /// The new command coalesces into the previous one; don't push a
+    /// second step.
+    Yes,
+    /// The new command exactly cancels the previous one (e.g. move A->B
+    /// then B->A); drop the previous step instead of pushing either.
+    Annul,
+}
+
+/// A serializable, replayable record of one edit. Each variant mirrors a
+/// `Timeline` editing method, carrying both the forward parameters and
+/// enough captured state to invert it. A `Vec<EditCommand>` is an
+/// append-only audit log: it can be serialized with a bug report or
+/// replayed with [`replay`] onto an empty timeline to deterministically
+/// reproduce the exact same state, independent of the live undo/redo stack.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum EditCommand {
+    AddItem {
+        track_id: Uuid,
+        item: Item,
+    },
+    RemoveItem {
+        item_id: Uuid,
+        track_id: Uuid,
+        item: Item,
+    },
+    Move {
+        item_id: Uuid,
+        new_start_us: TimeUs,
+        old_start_us: TimeUs,
+    },
+    TrimIn {
+        item_id: Uuid,
+        new_in_us: TimeUs,
+        old_in_us: TimeUs,
+    },
+    TrimOut {
+        item_id: Uuid,
+        new_out_us: TimeUs,
+        old_out_us: TimeUs,
+    },
+    SetSpeed {
+        item_id: Uuid,
+        new_speed: f64,
+        old_speed: f64,
+    },
+    /// `right_id` is the id minted for the new right-hand item when the
+    /// command first executed; replaying passes it back in so the split
+    /// produces the same id instead of minting a fresh random one.
+    Split {
+        item_id: Uuid,
+        at: TimeUs,
+        right_id: Uuid,
+    },
+    MoveToTrack {
+        item_id: Uuid,
+        new_track_id: Uuid,
+        new_start_us: TimeUs,
+        old_track_id: Uuid,
+        old_start_us: TimeUs,
+    },
+    Reorder {
+        item_id: Uuid,
+        new_index: usize,
+        old_index: usize,
+    },
+    /// A group of edits that were applied as one atomic undo step. See
+    /// [`CompositeCommand`].
+    Composite { entries: Vec<EditCommand> },
+}
+
+/// Serialize a recorded command log as pretty-printed JSON, e.g. to attach
+/// to a bug report so a failing real-world edit session can be replayed
+/// deterministically in a unit test via [`load_script`] and [`replay`].
+pub fn dump_script(log: &[EditCommand]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(log)?)
+}
+
+/// Parse a command log previously serialized by [`dump_script`].
+pub fn load_script(json: &str) -> Result<Vec<EditCommand>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Re-apply a recorded command log to a timeline (typically an empty one),
+/// reproducing the exact sequence of edits. Each entry's forward operation
+/// is replayed in order; captured inverse state is ignored, the same as it
+/// is during a normal redo.
+pub fn replay(log: &[EditCommand], timeline: &mut Timeline) -> Result<()> {
+    for cmd in log {
+        match cmd {
+            EditCommand::AddItem { track_id, item } => {
+                timeline.add_item(*track_id, item.clone())?;
+            }
+            EditCommand::RemoveItem { item_id, .. } => {
+                timeline.remove_item(*item_id)?;
+            }
+            EditCommand::Move {
+                item_id,
+                new_start_us,
+                ..
+            } => timeline.move_item(*item_id, *new_start_us, None)?,
+            EditCommand::TrimIn {
+                item_id, new_in_us, ..
+            } => timeline.trim_in(*item_id, *new_in_us, None)?,
+            EditCommand::TrimOut {
+                item_id,
+                new_out_us,
+                ..
+            } => timeline.trim_out(*item_id, *new_out_us, None)?,
+            EditCommand::SetSpeed {
+                item_id, new_speed, ..
+            } => timeline.set_speed(*item_id, *new_speed)?,
+            EditCommand::Split {
+                item_id,
+                at,
+                right_id,
+            } => {
+                timeline.split_at_with_right_id(*item_id, *at, *right_id)?;
+            }
+            EditCommand::MoveToTrack {
+                item_id,
+                new_track_id,
+                new_start_us,
+                ..
+            } => timeline.move_item_to_track(*item_id, *new_track_id, *new_start_us)?,
+            EditCommand::Reorder {
+                item_id, new_index, ..
+            } => timeline.reorder_item(*item_id, *new_index)?,
+            EditCommand::Composite { entries } => replay(entries, timeline)?,
+        }
+    }
+    Ok(())
 }
 
-/// Undo/redo history stack.
+/// Identifies a node in the [`History`] tree. Stable for the lifetime of a
+/// `History` instance; returned by [`History::branches`] and accepted by
+/// [`History::switch_branch`] and [`History::go_to`].
+pub type NodeId = usize;
+
+/// A branch point: one of possibly several commands that were executed from
+/// the same ancestor node. See [`History::branches`].
+pub type BranchId = NodeId;
+
+/// One executed command in the history tree, plus its place in the tree.
+/// `cmd` is `None` only for the sentinel node at index 0, which represents
+/// the document before any command was ever executed.
+struct HistoryNode {
+    cmd: Option<Box<dyn Command>>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// Which child `redo` follows; updated to the newest child whenever one
+    /// is added, and overridable via [`History::switch_branch`].
+    active_child: Option<NodeId>,
+    /// Wall-clock time `cmd` was captured, for [`History::time_travel_to`]
+    /// and [`History::entries`]. `None` only for the sentinel root node.
+    timestamp: Option<SystemTime>,
+}
+
+/// Undo/redo history, kept as a tree rather than a linear stack so that
+/// undoing and then making a new edit creates a *sibling* branch instead of
+/// discarding the undone commands (see [`History::branches`],
+/// [`History::switch_branch`], [`History::go_to`]).
 ///
 /// Safety: History is only accessed behind a Mutex, ensuring single-threaded access.
 /// The Command implementations use RefCell for interior mutability which is not Send,
 /// but since we guarantee exclusive access via Mutex, this is safe.
 pub struct History {
-    undo_stack: Vec<Box<dyn Command>>,
-    redo_stack: Vec<Box<dyn Command>>,
+    nodes: Vec<HistoryNode>,
+    /// The oldest node still reachable by `undo`; advanced past the original
+    /// sentinel (and any nodes older than `max_size`) as history grows, to
+    /// bound the undoable depth without physically dropping tree nodes that
+    /// other branches may still reference.
+    root: NodeId,
+    current: NodeId,
     max_size: usize,
+    /// Append-only record of every successfully executed command, independent
+    /// of `undo`/`redo`. See [`EditCommand`] and [`replay`].
+    log: Vec<EditCommand>,
+    /// Only coalesce a command into the previous undo step if it was pushed
+    /// within this long of the previous one, so distinct user actions
+    /// separated by a pause aren't accidentally fused. `None` (the default)
+    /// disables coalescing entirely.
+    merge_window: Option<Duration>,
+    last_push_at: Option<Instant>,
+    /// The node that corresponds to the last successfully persisted
+    /// document, set by [`set_saved`](Self::set_saved). A node identity
+    /// rather than a depth, so undoing past the save point and redoing back
+    /// to it is still recognized as saved. `None` once that node falls
+    /// outside the retained history window (see `max_size`) or before the
+    /// first save.
+    saved: Option<NodeId>,
+    /// Commands buffered by an open [`begin_transaction`](Self::begin_transaction),
+    /// already applied to the timeline but not yet pushed as history nodes.
+    /// `None` when no transaction is open.
+    pending: Option<Vec<Box<dyn Command>>>,
 }
 
 // Safety: History is always accessed behind a Mutex in AppState
@@ -27,53 +240,418 @@ unsafe impl Send for History {}
 impl History {
     pub fn new(max_size: usize) -> Self {
         Self {
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
+            nodes: vec![HistoryNode {
+                cmd: None,
+                parent: None,
+                children: Vec::new(),
+                active_child: None,
+                timestamp: None,
+            }],
+            root: 0,
+            current: 0,
             max_size,
+            log: Vec::new(),
+            merge_window: None,
+            last_push_at: None,
+            saved: None,
+            pending: None,
+        }
+    }
+
+    /// Enable (or disable, with `None`) coalescing of commands pushed within
+    /// `window` of each other into a single undo step. See
+    /// [`Command::merge`].
+    pub fn set_merge_window(&mut self, window: Option<Duration>) {
+        self.merge_window = window;
+    }
+
+    /// The node the tree is currently positioned at.
+    pub fn current_node(&self) -> NodeId {
+        self.current
+    }
+
+    fn path_from_root(&self, mut node: NodeId) -> Vec<NodeId> {
+        let mut path = vec![node];
+        while let Some(parent) = self.nodes[node].parent {
+            path.push(parent);
+            node = parent;
         }
+        path.reverse();
+        path
     }
 
-    /// Execute a command and push it onto the undo stack. Clears redo stack.
+    /// Execute a command and add it as a child of the current node. If a
+    /// [`merge_window`](Self::set_merge_window) is set and the previous
+    /// push happened within it, the new command is first offered to the
+    /// current node's command via [`Command::merge`]: on `Merge::Yes` it
+    /// coalesces into the existing step instead of becoming its own node,
+    /// and on `Merge::Annul` the existing step is dropped (the cursor moves
+    /// back to its parent) because the two cancel out. Otherwise the new
+    /// command becomes a new child of the current node -- a sibling branch
+    /// of any existing children, none of which are discarded.
     pub fn execute(&mut self, cmd: Box<dyn Command>, timeline: &mut Timeline) -> Result<()> {
         cmd.execute(timeline)?;
-        self.redo_stack.clear();
-        self.undo_stack.push(cmd);
-        if self.undo_stack.len() > self.max_size {
-            self.undo_stack.remove(0);
-        }
+        self.push_node(cmd);
         Ok(())
     }
 
-    /// Undo the last command.
+    /// Record an already-applied command as a new history node, without
+    /// executing it. Shared by [`execute`](Self::execute) (which just ran
+    /// the command) and [`commit_transaction`](Self::commit_transaction) (whose children already
+    /// ran as they were [`push`](Self::push)ed into the open transaction).
+    fn push_node(&mut self, cmd: Box<dyn Command>) {
+        self.log.push(cmd.log_entry());
+
+        let now = Instant::now();
+        let within_window = matches!(
+            (self.merge_window, self.last_push_at),
+            (Some(window), Some(last)) if now.duration_since(last) <= window
+        );
+        self.last_push_at = Some(now);
+
+        if within_window {
+            if let Some(top) = self.nodes[self.current].cmd.as_ref() {
+                match top.merge(cmd.as_ref()) {
+                    Merge::Annul => {
+                        let parent = self.nodes[self.current]
+                            .parent
+                            .expect("non-root node always has a parent");
+                        self.nodes[parent].children.retain(|&c| c != self.current);
+                        self.nodes[parent].active_child = None;
+                        self.current = parent;
+                        return;
+                    }
+                    // `top` already absorbed `cmd`'s target value as a side
+                    // effect of `merge`, so `cmd` itself is dropped here
+                    // rather than becoming a second node. It keeps the
+                    // newest timestamp, as if the whole gesture happened now.
+                    Merge::Yes => {
+                        self.nodes[self.current].timestamp = Some(SystemTime::now());
+                        return;
+                    }
+                    Merge::No => {}
+                }
+            }
+        }
+
+        let new_id = self.nodes.len();
+        self.nodes.push(HistoryNode {
+            cmd: Some(cmd),
+            parent: Some(self.current),
+            children: Vec::new(),
+            active_child: None,
+            timestamp: Some(SystemTime::now()),
+        });
+        self.nodes[self.current].children.push(new_id);
+        self.nodes[self.current].active_child = Some(new_id);
+        self.current = new_id;
+
+        // Bound the undoable depth: advance the floor past the oldest
+        // retained node rather than physically removing tree nodes, so
+        // other branches rooted below it stay intact. `path_from_root`
+        // always walks up to the tree's true sentinel (node 0), not
+        // `self.root`, so we track `self.root`'s own index within `path`
+        // and advance one slot past *that* each eviction step -- advancing
+        // to the constant `path[1]` would leave `self.root` stuck once it
+        // had moved past node 0's direct child.
+        let path = self.path_from_root(self.current);
+        let mut root_idx = path
+            .iter()
+            .position(|&n| n == self.root)
+            .expect("self.root always lies on the path from the true root to current");
+        while path.len() - 1 - root_idx > self.max_size {
+            let dropped = self.root;
+            root_idx += 1;
+            self.root = path[root_idx];
+            // The saved marker can no longer be reached once its node falls
+            // off the undoable window, so it no longer means anything.
+            if self.saved == Some(dropped) {
+                self.saved = None;
+            }
+        }
+    }
+
+    /// The full append-only edit log, in execution order. Unaffected by
+    /// `undo`/`redo`; pass it to [`replay`] to reproduce this timeline's
+    /// state from an empty one.
+    pub fn log(&self) -> &[EditCommand] {
+        &self.log
+    }
+
+    /// Serialize this session's edit log as pretty-printed JSON. See
+    /// [`dump_script`].
+    pub fn dump_script(&self) -> Result<String> {
+        dump_script(&self.log)
+    }
+
+    /// A cloned snapshot of [`log`](Self::log), suitable for storing
+    /// alongside a project file so the full edit history survives a reload.
+    /// Pass it to [`History::replay`] to reproduce this session's timeline
+    /// state from an empty one.
+    pub fn to_log(&self) -> Vec<EditCommand> {
+        self.log.clone()
+    }
+
+    /// Re-apply a log previously captured with [`to_log`](Self::to_log) (or
+    /// [`log`](Self::log)) to `timeline`. A thin wrapper around the
+    /// free-standing [`replay`] function for callers that already have a
+    /// `History` in hand.
+    pub fn replay(log: &[EditCommand], timeline: &mut Timeline) -> Result<()> {
+        replay(log, timeline)
+    }
+
+    /// Undo the last command, moving the cursor to its parent node.
     pub fn undo(&mut self, timeline: &mut Timeline) -> Result<()> {
-        let cmd = self.undo_stack.pop().ok_or(CoreError::NothingToUndo)?;
-        cmd.undo(timeline)?;
-        self.redo_stack.push(cmd);
+        if self.current == self.root {
+            return Err(CoreError::NothingToUndo);
+        }
+        let parent = self.nodes[self.current]
+            .parent
+            .ok_or(CoreError::NothingToUndo)?;
+        self.nodes[self.current]
+            .cmd
+            .as_ref()
+            .ok_or(CoreError::NothingToUndo)?
+            .undo(timeline)?;
+        self.current = parent;
         Ok(())
     }
 
-    /// Redo the last undone command.
+    /// Redo the active child of the current node (the most recently executed
+    /// one, unless overridden via [`switch_branch`](Self::switch_branch)).
     pub fn redo(&mut self, timeline: &mut Timeline) -> Result<()> {
-        let cmd = self.redo_stack.pop().ok_or(CoreError::NothingToRedo)?;
-        cmd.execute(timeline)?;
-        self.undo_stack.push(cmd);
+        let next = self.nodes[self.current]
+            .active_child
+            .ok_or(CoreError::NothingToRedo)?;
+        self.nodes[next]
+            .cmd
+            .as_ref()
+            .ok_or(CoreError::NothingToRedo)?
+            .execute(timeline)?;
+        self.current = next;
         Ok(())
     }
 
     pub fn can_undo(&self) -> bool {
-        !self.undo_stack.is_empty()
+        self.current != self.root
     }
 
     pub fn can_redo(&self) -> bool {
-        !self.redo_stack.is_empty()
+        self.nodes[self.current].active_child.is_some()
     }
 
     pub fn undo_description(&self) -> Option<&str> {
-        self.undo_stack.last().map(|cmd| cmd.description())
+        if self.current == self.root {
+            return None;
+        }
+        self.nodes[self.current]
+            .cmd
+            .as_ref()
+            .map(|cmd| cmd.description())
     }
 
     pub fn redo_description(&self) -> Option<&str> {
-        self.redo_stack.last().map(|cmd| cmd.description())
+        let next = self.nodes[self.current].active_child?;
+        self.nodes[next].cmd.as_ref().map(|cmd| cmd.description())
+    }
+
+    /// Like [`undo_description`](Self::undo_description), with the elapsed
+    /// wall-clock time since the command was captured appended, e.g.
+    /// `"Move clip (12s ago)"`, for rendering in a history panel.
+    pub fn undo_description_with_elapsed(&self) -> Option<String> {
+        if self.current == self.root {
+            return None;
+        }
+        let node = &self.nodes[self.current];
+        Some(describe_with_elapsed(node.cmd.as_deref()?, node.timestamp))
+    }
+
+    /// Like [`redo_description`](Self::redo_description), with the elapsed
+    /// wall-clock time since the command was captured appended.
+    pub fn redo_description_with_elapsed(&self) -> Option<String> {
+        let next = self.nodes[self.current].active_child?;
+        let node = &self.nodes[next];
+        Some(describe_with_elapsed(node.cmd.as_deref()?, node.timestamp))
+    }
+
+    /// The currently active undo stack, root to current (oldest first),
+    /// pairing each command's description with the wall-clock time it was
+    /// captured. Skips the sentinel root. Useful for rendering a history
+    /// panel.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, SystemTime)> + '_ {
+        self.path_from_root(self.current)
+            .into_iter()
+            .filter_map(move |id| {
+                let node = &self.nodes[id];
+                Some((node.cmd.as_deref()?.description(), node.timestamp?))
+            })
+    }
+
+    /// Move the cursor along the current undo/redo path (no branch-switching)
+    /// until the active command's capture time is at or before `target`:
+    /// undoes while the top command is newer than `target`, then redoes
+    /// while the next command is at or before it. Mirrors the `undo` crate's
+    /// instant-based timeline navigation.
+    pub fn time_travel_to(&mut self, target: SystemTime, timeline: &mut Timeline) -> Result<()> {
+        while let Some(ts) = self.nodes[self.current].timestamp {
+            if ts <= target {
+                break;
+            }
+            self.undo(timeline)?;
+        }
+        while let Some(next) = self.nodes[self.current].active_child {
+            let ts = self.nodes[next]
+                .timestamp
+                .expect("non-root node always has a timestamp");
+            if ts > target {
+                break;
+            }
+            self.redo(timeline)?;
+        }
+        Ok(())
+    }
+
+    /// Mark the current position as the last persisted document, e.g. right
+    /// after a successful save.
+    pub fn set_saved(&mut self) {
+        self.saved = Some(self.current);
+    }
+
+    /// Whether the current position matches the last saved position. This
+    /// is a node identity, not a boolean flag, so undoing past the save
+    /// point and then redoing back to it is still reported as saved.
+    pub fn is_saved(&self) -> bool {
+        self.saved == Some(self.current)
+    }
+
+    /// The inverse of [`is_saved`](Self::is_saved): whether there are
+    /// changes since the last save that a "Save" action would persist.
+    pub fn is_dirty(&self) -> bool {
+        !self.is_saved()
+    }
+
+    /// Whether the UI should currently offer a "Save" action. Equivalent to
+    /// [`is_dirty`](Self::is_dirty).
+    pub fn can_save(&self) -> bool {
+        self.is_dirty()
+    }
+
+    /// The other edits that branched off the current node, besides the one
+    /// `redo` would currently follow -- alternate futures the user diverged
+    /// from by undoing and then making a different edit, instead of losing
+    /// them.
+    pub fn branches(&self) -> Vec<BranchId> {
+        self.nodes[self.current].children.clone()
+    }
+
+    /// Make `redo` follow branch `id` instead of whichever child is
+    /// currently active. `id` must be a child of the current node.
+    pub fn switch_branch(&mut self, id: BranchId) -> Result<()> {
+        if !self.nodes[self.current].children.contains(&id) {
+            return Err(CoreError::InvalidOperation(
+                "not a branch of the current history node".into(),
+            ));
+        }
+        self.nodes[self.current].active_child = Some(id);
+        Ok(())
+    }
+
+    /// Move the cursor to `node`, undoing back to the common ancestor of the
+    /// current position and `node` and then executing forward down to it.
+    pub fn go_to(&mut self, node: NodeId, timeline: &mut Timeline) -> Result<()> {
+        if node >= self.nodes.len() {
+            return Err(CoreError::InvalidOperation(format!(
+                "no history node {node}"
+            )));
+        }
+        let current_path = self.path_from_root(self.current);
+        let target_path = self.path_from_root(node);
+        if !current_path.contains(&self.root) || !target_path.contains(&self.root) {
+            return Err(CoreError::InvalidOperation(
+                "node is outside the retained history window".into(),
+            ));
+        }
+
+        let common_len = current_path
+            .iter()
+            .zip(target_path.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let common_ancestor = current_path[common_len - 1];
+
+        while self.current != common_ancestor {
+            self.undo(timeline)?;
+        }
+        for &step in &target_path[common_len..] {
+            self.nodes[step]
+                .cmd
+                .as_ref()
+                .expect("non-root path node always has a command")
+                .execute(timeline)?;
+            self.nodes[self.current].active_child = Some(step);
+            self.current = step;
+        }
+        Ok(())
+    }
+
+    /// Open a transaction: subsequent [`push`](Self::push) calls apply and
+    /// buffer commands instead of pushing them as individual history nodes,
+    /// borrowing the deferred-queue model from Ruffle's `ActionQueue`. Call
+    /// [`commit_transaction`](Self::commit_transaction) to fold the buffered
+    /// commands into one [`CompositeCommand`] undo step, or
+    /// [`abort`](Self::abort) to undo them and discard the transaction. Only
+    /// one transaction may be open at a time.
+    pub fn begin_transaction(&mut self) -> Result<()> {
+        if self.pending.is_some() {
+            return Err(CoreError::InvalidOperation(
+                "a transaction is already open".into(),
+            ));
+        }
+        self.pending = Some(Vec::new());
+        Ok(())
+    }
+
+    /// Execute `cmd` and buffer it into the open transaction. Errors if no
+    /// transaction is open.
+    pub fn push(&mut self, cmd: Box<dyn Command>, timeline: &mut Timeline) -> Result<()> {
+        let pending = self
+            .pending
+            .as_mut()
+            .ok_or_else(|| CoreError::InvalidOperation("no transaction is open".into()))?;
+        cmd.execute(timeline)?;
+        pending.push(cmd);
+        Ok(())
+    }
+
+    /// Close the open transaction, wrapping its buffered commands in a
+    /// single [`CompositeCommand`] pushed as one undo step. A no-op (and not
+    /// pushed) if the transaction buffered no commands. Errors if no
+    /// transaction is open.
+    pub fn commit_transaction(&mut self, description: impl Into<String>) -> Result<()> {
+        let children = self
+            .pending
+            .take()
+            .ok_or_else(|| CoreError::InvalidOperation("no transaction is open".into()))?;
+        if children.is_empty() {
+            return Ok(());
+        }
+        let composite: Box<dyn Command> = Box::new(CompositeCommand::new(children, description));
+        self.push_node(composite);
+        Ok(())
+    }
+
+    /// Close the open transaction, undoing any commands already applied to
+    /// `timeline` and discarding them without recording a history node.
+    /// Errors if no transaction is open.
+    pub fn abort(&mut self, timeline: &mut Timeline) -> Result<()> {
+        let children = self
+            .pending
+            .take()
+            .ok_or_else(|| CoreError::InvalidOperation("no transaction is open".into()))?;
+        for cmd in children.iter().rev() {
+            cmd.undo(timeline)?;
+        }
+        Ok(())
     }
 }
 
@@ -105,6 +683,17 @@ impl Command for AddItemCommand {
     fn description(&self) -> &str {
         "Add clip"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::AddItem {
+            track_id: self.track_id,
+            item: self.item.clone(),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -152,6 +741,25 @@ impl Command for RemoveItemCommand {
     fn description(&self) -> &str {
         "Remove clip"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::RemoveItem {
+            item_id: self.item_id,
+            track_id: self
+                .track_id
+                .borrow()
+                .expect("log_entry called before execute"),
+            item: self
+                .removed_item
+                .borrow()
+                .clone()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -161,7 +769,7 @@ impl Command for RemoveItemCommand {
 #[derive(Debug)]
 pub struct MoveItemCommand {
     item_id: Uuid,
-    new_start_us: TimeUs,
+    new_start_us: Cell<TimeUs>,
     old_start_us: RefCell<Option<TimeUs>>,
 }
 
@@ -169,7 +777,7 @@ impl MoveItemCommand {
     pub fn new(item_id: Uuid, new_start_us: TimeUs) -> Self {
         Self {
             item_id,
-            new_start_us,
+            new_start_us: Cell::new(new_start_us),
             old_start_us: RefCell::new(None),
         }
     }
@@ -180,7 +788,7 @@ impl Command for MoveItemCommand {
         // Find the item to save its current start before moving
         let old_start = find_item(timeline, self.item_id)?.timeline_start_us();
         *self.old_start_us.borrow_mut() = Some(old_start);
-        timeline.move_item(self.item_id, self.new_start_us)
+        timeline.move_item(self.item_id, self.new_start_us.get(), None)
     }
 
     fn undo(&self, timeline: &mut Timeline) -> Result<()> {
@@ -188,12 +796,43 @@ impl Command for MoveItemCommand {
             .old_start_us
             .borrow()
             .ok_or_else(|| CoreError::InvalidOperation("no old start saved".into()))?;
-        timeline.move_item(self.item_id, old_start)
+        timeline.move_item(self.item_id, old_start, None)
     }
 
     fn description(&self) -> &str {
         "Move clip"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::Move {
+            item_id: self.item_id,
+            new_start_us: self.new_start_us.get(),
+            old_start_us: self
+                .old_start_us
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn merge(&self, next: &dyn Command) -> Merge {
+        let Some(other) = next.as_any().downcast_ref::<MoveItemCommand>() else {
+            return Merge::No;
+        };
+        if other.item_id != self.item_id {
+            return Merge::No;
+        }
+        if let Some(old_start) = *self.old_start_us.borrow() {
+            if old_start == other.new_start_us.get() {
+                return Merge::Annul;
+            }
+        }
+        self.new_start_us.set(other.new_start_us.get());
+        Merge::Yes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -203,7 +842,7 @@ impl Command for MoveItemCommand {
 #[derive(Debug)]
 pub struct TrimInCommand {
     item_id: Uuid,
-    new_in_us: TimeUs,
+    new_in_us: Cell<TimeUs>,
     old_in_us: RefCell<Option<TimeUs>>,
 }
 
@@ -211,7 +850,7 @@ impl TrimInCommand {
     pub fn new(item_id: Uuid, new_in_us: TimeUs) -> Self {
         Self {
             item_id,
-            new_in_us,
+            new_in_us: Cell::new(new_in_us),
             old_in_us: RefCell::new(None),
         }
     }
@@ -230,9 +869,10 @@ impl Command for TrimInCommand {
             | Item::TextOverlay {
                 timeline_start_us, ..
             } => *timeline_start_us,
+            Item::CompoundClip { source_in_us, .. } => *source_in_us,
         };
         *self.old_in_us.borrow_mut() = Some(old_in);
-        timeline.trim_in(self.item_id, self.new_in_us)
+        timeline.trim_in(self.item_id, self.new_in_us.get(), None)
     }
 
     fn undo(&self, timeline: &mut Timeline) -> Result<()> {
@@ -240,12 +880,43 @@ impl Command for TrimInCommand {
             .old_in_us
             .borrow()
             .ok_or_else(|| CoreError::InvalidOperation("no old in-point saved".into()))?;
-        timeline.trim_in(self.item_id, old_in)
+        timeline.trim_in(self.item_id, old_in, None)
     }
 
     fn description(&self) -> &str {
         "Trim in-point"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::TrimIn {
+            item_id: self.item_id,
+            new_in_us: self.new_in_us.get(),
+            old_in_us: self
+                .old_in_us
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn merge(&self, next: &dyn Command) -> Merge {
+        let Some(other) = next.as_any().downcast_ref::<TrimInCommand>() else {
+            return Merge::No;
+        };
+        if other.item_id != self.item_id {
+            return Merge::No;
+        }
+        if let Some(old_in) = *self.old_in_us.borrow() {
+            if old_in == other.new_in_us.get() {
+                return Merge::Annul;
+            }
+        }
+        self.new_in_us.set(other.new_in_us.get());
+        Merge::Yes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -255,7 +926,7 @@ impl Command for TrimInCommand {
 #[derive(Debug)]
 pub struct TrimOutCommand {
     item_id: Uuid,
-    new_out_us: TimeUs,
+    new_out_us: Cell<TimeUs>,
     old_out_us: RefCell<Option<TimeUs>>,
 }
 
@@ -263,7 +934,7 @@ impl TrimOutCommand {
     pub fn new(item_id: Uuid, new_out_us: TimeUs) -> Self {
         Self {
             item_id,
-            new_out_us,
+            new_out_us: Cell::new(new_out_us),
             old_out_us: RefCell::new(None),
         }
     }
@@ -289,9 +960,10 @@ impl Command for TrimOutCommand {
                 duration_us,
                 ..
             } => TimeUs(timeline_start_us.0 + duration_us.0),
+            Item::CompoundClip { source_out_us, .. } => *source_out_us,
         };
         *self.old_out_us.borrow_mut() = Some(old_out);
-        timeline.trim_out(self.item_id, self.new_out_us)
+        timeline.trim_out(self.item_id, self.new_out_us.get(), None)
     }
 
     fn undo(&self, timeline: &mut Timeline) -> Result<()> {
@@ -299,22 +971,150 @@ impl Command for TrimOutCommand {
             .old_out_us
             .borrow()
             .ok_or_else(|| CoreError::InvalidOperation("no old out-point saved".into()))?;
-        timeline.trim_out(self.item_id, old_out)
+        timeline.trim_out(self.item_id, old_out, None)
     }
 
     fn description(&self) -> &str {
         "Trim out-point"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::TrimOut {
+            item_id: self.item_id,
+            new_out_us: self.new_out_us.get(),
+            old_out_us: self
+                .old_out_us
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn merge(&self, next: &dyn Command) -> Merge {
+        let Some(other) = next.as_any().downcast_ref::<TrimOutCommand>() else {
+            return Merge::No;
+        };
+        if other.item_id != self.item_id {
+            return Merge::No;
+        }
+        if let Some(old_out) = *self.old_out_us.borrow() {
+            if old_out == other.new_out_us.get() {
+                return Merge::Annul;
+            }
+        }
+        self.new_out_us.set(other.new_out_us.get());
+        Merge::Yes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SetSpeedCommand
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct SetSpeedCommand {
+    item_id: Uuid,
+    new_speed: Cell<f64>,
+    old_speed: RefCell<Option<f64>>,
+}
+
+impl SetSpeedCommand {
+    pub fn new(item_id: Uuid, new_speed: f64) -> Self {
+        Self {
+            item_id,
+            new_speed: Cell::new(new_speed),
+            old_speed: RefCell::new(None),
+        }
+    }
+}
+
+impl Command for SetSpeedCommand {
+    fn execute(&self, timeline: &mut Timeline) -> Result<()> {
+        let item = find_item(timeline, self.item_id)?;
+        let old_speed = match item {
+            Item::VideoClip { speed, .. } | Item::AudioClip { speed, .. } => *speed,
+            Item::ImageOverlay { .. } | Item::TextOverlay { .. } | Item::CompoundClip { .. } => {
+                return Err(CoreError::InvalidOperation(
+                    "only VideoClip and AudioClip items have a playback speed".into(),
+                ));
+            }
+        };
+        *self.old_speed.borrow_mut() = Some(old_speed);
+        timeline.set_speed(self.item_id, self.new_speed.get())
+    }
+
+    fn undo(&self, timeline: &mut Timeline) -> Result<()> {
+        let old_speed = self
+            .old_speed
+            .borrow()
+            .ok_or_else(|| CoreError::InvalidOperation("no old speed saved".into()))?;
+        timeline.set_speed(self.item_id, old_speed)
+    }
+
+    fn description(&self) -> &str {
+        "Change clip speed"
+    }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::SetSpeed {
+            item_id: self.item_id,
+            new_speed: self.new_speed.get(),
+            old_speed: self
+                .old_speed
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn merge(&self, next: &dyn Command) -> Merge {
+        let Some(other) = next.as_any().downcast_ref::<SetSpeedCommand>() else {
+            return Merge::No;
+        };
+        if other.item_id != self.item_id {
+            return Merge::No;
+        }
+        if let Some(old_speed) = *self.old_speed.borrow() {
+            if old_speed == other.new_speed.get() {
+                return Merge::Annul;
+            }
+        }
+        self.new_speed.set(other.new_speed.get());
+        Merge::Yes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
 // SplitCommand
 // ---------------------------------------------------------------------------
 
+/// How [`SplitCommand::new_snapped`] adjusts a requested split time to land
+/// on a decodable keyframe boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapMode {
+    /// Whichever keyframe is closest, before or after.
+    Nearest,
+    /// The keyframe at or before the requested time.
+    PrecedingKeyframe,
+    /// The keyframe at or after the requested time.
+    FollowingKeyframe,
+}
+
 #[derive(Debug)]
 pub struct SplitCommand {
     item_id: Uuid,
-    split_time_us: TimeUs,
+    split_time_us: Cell<TimeUs>,
+    /// The asset's sync-sample positions (e.g. from
+    /// [`crate::media::probe::read_keyframes_us`]) and how to snap to them,
+    /// resolved against `split_time_us` during `execute`. `None` for a plain
+    /// unsnapped split.
+    snap: Option<(Vec<TimeUs>, SnapMode)>,
     right_id: RefCell<Option<Uuid>>,
     original_item: RefCell<Option<Item>>,
 }
@@ -323,20 +1123,92 @@ impl SplitCommand {
     pub fn new(item_id: Uuid, split_time_us: TimeUs) -> Self {
         Self {
             item_id,
-            split_time_us,
+            split_time_us: Cell::new(split_time_us),
+            snap: None,
+            right_id: RefCell::new(None),
+            original_item: RefCell::new(None),
+        }
+    }
+
+    /// Like [`new`](Self::new), but adjusts `requested` to the nearest entry
+    /// in `keyframes_us` per `mode` before splitting, so the resulting clips
+    /// don't need a re-encode to decode cleanly. `keyframes_us` must already
+    /// be expressed in the same (timeline) coordinate space as `requested`
+    /// -- translate source-relative positions from
+    /// [`crate::media::probe::read_keyframes_us`] by the clip's timeline
+    /// offset from its `source_in_us` first. If `keyframes_us` is empty (no
+    /// `stss` box: every sample is a sync sample), this is equivalent to
+    /// `new`. A split that would land before the first keyframe clamps to
+    /// the clip's `source_in_us` instead.
+    pub fn new_snapped(
+        item_id: Uuid,
+        requested: TimeUs,
+        keyframes_us: Vec<TimeUs>,
+        mode: SnapMode,
+    ) -> Self {
+        Self {
+            item_id,
+            split_time_us: Cell::new(requested),
+            snap: Some((keyframes_us, mode)),
             right_id: RefCell::new(None),
             original_item: RefCell::new(None),
         }
     }
 }
 
+/// Resolve `requested` against a sorted keyframe list per `mode`.
+/// `source_in_us` is the clamp floor used when `requested` falls before the
+/// first keyframe (so a preceding-keyframe snap never cuts before the
+/// clip's own source start).
+fn snap_to_keyframe(
+    requested: TimeUs,
+    keyframes_us: &[TimeUs],
+    mode: SnapMode,
+    source_in_us: TimeUs,
+) -> TimeUs {
+    if keyframes_us.is_empty() {
+        return requested;
+    }
+    match keyframes_us.binary_search(&requested) {
+        Ok(index) => keyframes_us[index],
+        Err(insert_at) => {
+            let preceding = insert_at.checked_sub(1).map(|i| keyframes_us[i]);
+            let following = keyframes_us.get(insert_at).copied();
+            match mode {
+                SnapMode::PrecedingKeyframe => preceding.unwrap_or(source_in_us),
+                SnapMode::FollowingKeyframe => following.unwrap_or(requested),
+                SnapMode::Nearest => match (preceding, following) {
+                    (Some(p), Some(f)) => {
+                        if (requested.0 - p.0) <= (f.0 - requested.0) {
+                            p
+                        } else {
+                            f
+                        }
+                    }
+                    (Some(p), None) => p,
+                    (None, Some(f)) => f,
+                    (None, None) => source_in_us,
+                },
+            }
+        }
+    }
+}
+
 impl Command for SplitCommand {
     fn execute(&self, timeline: &mut Timeline) -> Result<()> {
         // Save original item state before splitting
         let item = find_item(timeline, self.item_id)?;
         *self.original_item.borrow_mut() = Some(item.clone());
 
-        let (_left_id, right_id) = timeline.split_at(self.item_id, self.split_time_us)?;
+        if let Some((keyframes_us, mode)) = &self.snap {
+            let source_in_us = item.source_in_us().unwrap_or(TimeUs::ZERO);
+            let snapped =
+                snap_to_keyframe(self.split_time_us.get(), keyframes_us, *mode, source_in_us);
+            self.split_time_us.set(snapped);
+        }
+
+        let (_left_id, right_id) =
+            timeline.split_at(self.item_id, self.split_time_us.get(), None)?;
         *self.right_id.borrow_mut() = Some(right_id);
         Ok(())
     }
@@ -369,6 +1241,21 @@ impl Command for SplitCommand {
     fn description(&self) -> &str {
         "Split clip"
     }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::Split {
+            item_id: self.item_id,
+            at: self.split_time_us.get(),
+            right_id: self
+                .right_id
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -378,8 +1265,8 @@ impl Command for SplitCommand {
 #[derive(Debug)]
 pub struct MoveItemToTrackCommand {
     item_id: Uuid,
-    new_track_id: Uuid,
-    new_start_us: TimeUs,
+    new_track_id: Cell<Uuid>,
+    new_start_us: Cell<TimeUs>,
     old_track_id: RefCell<Option<Uuid>>,
     old_start_us: RefCell<Option<TimeUs>>,
 }
@@ -388,8 +1275,8 @@ impl MoveItemToTrackCommand {
     pub fn new(item_id: Uuid, new_track_id: Uuid, new_start_us: TimeUs) -> Self {
         Self {
             item_id,
-            new_track_id,
-            new_start_us,
+            new_track_id: Cell::new(new_track_id),
+            new_start_us: Cell::new(new_start_us),
             old_track_id: RefCell::new(None),
             old_start_us: RefCell::new(None),
         }
@@ -401,7 +1288,7 @@ impl Command for MoveItemToTrackCommand {
         let item = find_item(timeline, self.item_id)?;
         *self.old_track_id.borrow_mut() = Some(item.track_id());
         *self.old_start_us.borrow_mut() = Some(item.timeline_start_us());
-        timeline.move_item_to_track(self.item_id, self.new_track_id, self.new_start_us)
+        timeline.move_item_to_track(self.item_id, self.new_track_id.get(), self.new_start_us.get())
     }
 
     fn undo(&self, timeline: &mut Timeline) -> Result<()> {
@@ -419,56 +1306,237 @@ impl Command for MoveItemToTrackCommand {
     fn description(&self) -> &str {
         "Move clip to track"
     }
-}
 
-// ---------------------------------------------------------------------------
-// Helpers
-// ---------------------------------------------------------------------------
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::MoveToTrack {
+            item_id: self.item_id,
+            new_track_id: self.new_track_id.get(),
+            new_start_us: self.new_start_us.get(),
+            old_track_id: self
+                .old_track_id
+                .borrow()
+                .expect("log_entry called before execute"),
+            old_start_us: self
+                .old_start_us
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
 
-fn find_item(timeline: &Timeline, item_id: Uuid) -> Result<&Item> {
-    for track in &timeline.tracks {
-        for item in &track.items {
-            if item.id() == item_id {
-                return Ok(item);
+    fn merge(&self, next: &dyn Command) -> Merge {
+        let Some(other) = next.as_any().downcast_ref::<MoveItemToTrackCommand>() else {
+            return Merge::No;
+        };
+        if other.item_id != self.item_id {
+            return Merge::No;
+        }
+        if let (Some(old_track), Some(old_start)) =
+            (*self.old_track_id.borrow(), *self.old_start_us.borrow())
+        {
+            if old_track == other.new_track_id.get() && old_start == other.new_start_us.get() {
+                return Merge::Annul;
             }
         }
+        self.new_track_id.set(other.new_track_id.get());
+        self.new_start_us.set(other.new_start_us.get());
+        Merge::Yes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
-    Err(CoreError::ItemNotFound(item_id))
 }
 
 // ---------------------------------------------------------------------------
-// Tests
+// ReorderItemCommand
 // ---------------------------------------------------------------------------
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Debug)]
+pub struct ReorderItemCommand {
+    item_id: Uuid,
+    new_index: usize,
+    old_index: RefCell<Option<usize>>,
+}
 
-    fn make_test_timeline() -> (Timeline, Uuid, Uuid, Item) {
-        let track_id = Uuid::new_v4();
-        let clip_id = Uuid::new_v4();
-        let asset_id = Uuid::new_v4();
-        let item = Item::VideoClip {
-            id: clip_id,
-            asset_id,
-            track_id,
-            timeline_start_us: TimeUs(0),
-            source_in_us: TimeUs(0),
-            source_out_us: TimeUs(5_000_000),
-        };
-        let tl = Timeline {
-            tracks: vec![Track {
-                id: track_id,
-                kind: TrackKind::Video,
-                items: vec![],
-            }],
-            markers: vec![],
-        };
-        (tl, track_id, clip_id, item)
+impl ReorderItemCommand {
+    pub fn new(item_id: Uuid, new_index: usize) -> Self {
+        Self {
+            item_id,
+            new_index,
+            old_index: RefCell::new(None),
+        }
     }
+}
 
-    // -----------------------------------------------------------------------
-    // AddItemCommand + undo/redo
+impl Command for ReorderItemCommand {
+    fn execute(&self, timeline: &mut Timeline) -> Result<()> {
+        let old_index = find_item_index(timeline, self.item_id)?;
+        *self.old_index.borrow_mut() = Some(old_index);
+        timeline.reorder_item(self.item_id, self.new_index)
+    }
+
+    fn undo(&self, timeline: &mut Timeline) -> Result<()> {
+        let old_index = self
+            .old_index
+            .borrow()
+            .ok_or_else(|| CoreError::InvalidOperation("no old index saved".into()))?;
+        timeline.reorder_item(self.item_id, old_index)
+    }
+
+    fn description(&self) -> &str {
+        "Reorder clip"
+    }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::Reorder {
+            item_id: self.item_id,
+            new_index: self.new_index,
+            old_index: self
+                .old_index
+                .borrow()
+                .expect("log_entry called before execute"),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CompositeCommand
+// ---------------------------------------------------------------------------
+
+/// A group of commands that execute and undo as a single atomic unit, e.g.
+/// a ripple delete or a multi-clip paste. Built via [`History::begin_transaction`]
+/// / [`History::commit_transaction`], or directly with [`CompositeCommand::new`].
+#[derive(Debug)]
+pub struct CompositeCommand {
+    children: Vec<Box<dyn Command>>,
+    description: String,
+}
+
+impl CompositeCommand {
+    pub fn new(children: Vec<Box<dyn Command>>, description: impl Into<String>) -> Self {
+        Self {
+            children,
+            description: description.into(),
+        }
+    }
+}
+
+impl Command for CompositeCommand {
+    fn execute(&self, timeline: &mut Timeline) -> Result<()> {
+        for (applied, child) in self.children.iter().enumerate() {
+            if let Err(err) = child.execute(timeline) {
+                // Roll back whatever already applied so the timeline is
+                // never left half-way through this group.
+                for prior in self.children[..applied].iter().rev() {
+                    let _ = prior.undo(timeline);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn undo(&self, timeline: &mut Timeline) -> Result<()> {
+        for child in self.children.iter().rev() {
+            child.undo(timeline)?;
+        }
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn log_entry(&self) -> EditCommand {
+        EditCommand::Composite {
+            entries: self.children.iter().map(|c| c.log_entry()).collect(),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn find_item(timeline: &Timeline, item_id: Uuid) -> Result<&Item> {
+    for track in &timeline.tracks {
+        for item in &track.items {
+            if item.id() == item_id {
+                return Ok(item);
+            }
+        }
+    }
+    Err(CoreError::ItemNotFound(item_id))
+}
+
+fn find_item_index(timeline: &Timeline, item_id: Uuid) -> Result<usize> {
+    for track in &timeline.tracks {
+        if let Some(pos) = track.items.iter().position(|i| i.id() == item_id) {
+            return Ok(pos);
+        }
+    }
+    Err(CoreError::ItemNotFound(item_id))
+}
+
+/// Format a command description with how long ago it was captured, e.g.
+/// `"Move clip (12s ago)"`. Falls back to the bare description if there's
+/// no timestamp or the clock went backwards.
+fn describe_with_elapsed(cmd: &dyn Command, timestamp: Option<SystemTime>) -> String {
+    let desc = cmd.description();
+    match timestamp.and_then(|ts| SystemTime::now().duration_since(ts).ok()) {
+        Some(elapsed) => format!("{desc} ({}s ago)", elapsed.as_secs()),
+        None => desc.to_string(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_test_timeline() -> (Timeline, Uuid, Uuid, Item) {
+        let track_id = Uuid::new_v4();
+        let clip_id = Uuid::new_v4();
+        let asset_id = Uuid::new_v4();
+        let item = Item::VideoClip {
+            id: clip_id,
+            asset_id,
+            track_id,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        (tl, track_id, clip_id, item)
+    }
+
+    // -----------------------------------------------------------------------
+    // AddItemCommand + undo/redo
     // -----------------------------------------------------------------------
 
     #[test]
@@ -513,6 +1581,9 @@ mod tests {
             timeline_start_us: TimeUs(10_000_000),
             source_in_us: TimeUs(0),
             source_out_us: TimeUs(3_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
         let cmd2 = Box::new(AddItemCommand::new(track_id, item2));
         history.execute(cmd2, &mut tl).unwrap();
@@ -632,6 +1703,102 @@ mod tests {
         assert_eq!(restored.timeline_end_us(), TimeUs(5_000_000));
     }
 
+    // -----------------------------------------------------------------------
+    // SplitCommand::new_snapped: adjusting the cut to a keyframe boundary.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn split_snapped_nearest_picks_closest_keyframe() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let keyframes = vec![TimeUs(0), TimeUs(1_000_000), TimeUs(3_000_000)];
+        let mut history = History::new(100);
+        let cmd = Box::new(SplitCommand::new_snapped(
+            clip_id,
+            TimeUs(2_000_000),
+            keyframes,
+            SnapMode::Nearest,
+        ));
+        history.execute(cmd, &mut tl).unwrap();
+
+        assert_eq!(tl.tracks[0].items[0].timeline_end_us(), TimeUs(1_000_000));
+    }
+
+    #[test]
+    fn split_snapped_preceding_keyframe() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let keyframes = vec![TimeUs(0), TimeUs(1_000_000), TimeUs(3_000_000)];
+        let mut history = History::new(100);
+        let cmd = Box::new(SplitCommand::new_snapped(
+            clip_id,
+            TimeUs(2_000_000),
+            keyframes,
+            SnapMode::PrecedingKeyframe,
+        ));
+        history.execute(cmd, &mut tl).unwrap();
+
+        assert_eq!(tl.tracks[0].items[0].timeline_end_us(), TimeUs(1_000_000));
+    }
+
+    #[test]
+    fn split_snapped_following_keyframe() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let keyframes = vec![TimeUs(0), TimeUs(1_000_000), TimeUs(3_000_000)];
+        let mut history = History::new(100);
+        let cmd = Box::new(SplitCommand::new_snapped(
+            clip_id,
+            TimeUs(2_000_000),
+            keyframes,
+            SnapMode::FollowingKeyframe,
+        ));
+        history.execute(cmd, &mut tl).unwrap();
+
+        assert_eq!(tl.tracks[0].items[0].timeline_end_us(), TimeUs(3_000_000));
+    }
+
+    #[test]
+    fn split_snapped_empty_keyframes_is_a_noop() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        let cmd = Box::new(SplitCommand::new_snapped(
+            clip_id,
+            TimeUs(2_000_000),
+            Vec::new(),
+            SnapMode::Nearest,
+        ));
+        history.execute(cmd, &mut tl).unwrap();
+
+        assert_eq!(tl.tracks[0].items[0].timeline_end_us(), TimeUs(2_000_000));
+    }
+
+    #[test]
+    fn split_snapped_before_first_keyframe_clamps_to_source_in() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let keyframes = vec![TimeUs(3_000_000)];
+        let mut history = History::new(100);
+        let cmd = Box::new(SplitCommand::new_snapped(
+            clip_id,
+            TimeUs(1_000_000),
+            keyframes,
+            SnapMode::PrecedingKeyframe,
+        ));
+        let result = history.execute(cmd, &mut tl);
+
+        // Clamped to source_in_us (0), which means a zero-length left half
+        // -- split_at itself rejects that, so the command surfaces the error
+        // rather than producing a degenerate clip.
+        assert!(result.is_err());
+    }
+
     // -----------------------------------------------------------------------
     // max_size limits undo stack
     // -----------------------------------------------------------------------
@@ -650,6 +1817,9 @@ mod tests {
                 timeline_start_us: TimeUs(i * 10_000_000),
                 source_in_us: TimeUs(0),
                 source_out_us: TimeUs(5_000_000),
+                speed: 1.0,
+                fade_in_us: TimeUs::ZERO,
+                fade_out_us: TimeUs::ZERO,
             };
             let cmd = Box::new(AddItemCommand::new(track_id, item));
             history.execute(cmd, &mut tl).unwrap();
@@ -773,13 +1943,18 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs(0),
             source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
         let mut tl = Timeline {
             tracks: vec![
-                Track { id: track_a, kind: TrackKind::Video, items: vec![item] },
-                Track { id: track_b, kind: TrackKind::Video, items: vec![] },
+                Track { id: track_a, kind: TrackKind::Video, items: vec![item], transitions: vec![], subtitles: None },
+                Track { id: track_b, kind: TrackKind::Video, items: vec![], transitions: vec![], subtitles: None },
             ],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
         let mut history = History::new(100);
 
@@ -825,4 +2000,908 @@ mod tests {
             TimeUs(10_000_000)
         );
     }
+
+    // -----------------------------------------------------------------------
+    // ReorderItemCommand: reorder -> undo -> original index
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn reorder_undo_redo() {
+        let track_id = Uuid::new_v4();
+        let make_clip = |start_us| Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(start_us),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(1_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let first_id = Uuid::new_v4();
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![
+                    Item::VideoClip {
+                        id: first_id,
+                        ..make_clip(0)
+                    },
+                    make_clip(1_000_000),
+                    make_clip(2_000_000),
+                ],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        let mut history = History::new(100);
+
+        let cmd = Box::new(ReorderItemCommand::new(first_id, 2));
+        history.execute(cmd, &mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[2].id(), first_id);
+
+        history.undo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].id(), first_id);
+
+        history.redo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[2].id(), first_id);
+    }
+
+    // -----------------------------------------------------------------------
+    // Edit log capture
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn log_captures_entries_in_order() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        assert_eq!(history.log().len(), 2);
+        assert!(matches!(history.log()[0], EditCommand::AddItem { .. }));
+        assert_eq!(
+            history.log()[1],
+            EditCommand::Move {
+                item_id: clip_id,
+                new_start_us: TimeUs(1_000_000),
+                old_start_us: TimeUs(0),
+            }
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Log is untouched by undo/redo, and not appended on failure
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn log_unaffected_by_undo_and_failed_commands() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        assert_eq!(history.log().len(), 1);
+
+        history.undo(&mut tl).unwrap();
+        assert_eq!(history.log().len(), 1);
+
+        // Trimming a nonexistent item fails and must not append to the log.
+        let bad_cmd = Box::new(TrimInCommand::new(Uuid::new_v4(), TimeUs(0)));
+        assert!(history.execute(bad_cmd, &mut tl).is_err());
+        assert_eq!(history.log().len(), 1);
+
+        let _ = clip_id;
+    }
+
+    // -----------------------------------------------------------------------
+    // replay() reproduces the same state on a fresh timeline, including a
+    // split whose right-hand id must match for a later op to find it.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn replay_reproduces_split_and_later_edit() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        history
+            .execute(
+                Box::new(SplitCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        let right_id = tl.tracks[0].items[1].id();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(right_id, TimeUs(3_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        let mut replayed = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        replay(history.log(), &mut replayed).unwrap();
+
+        assert_eq!(replayed.tracks[0].items.len(), tl.tracks[0].items.len());
+        for (replayed_item, live_item) in replayed.tracks[0].items.iter().zip(&tl.tracks[0].items)
+        {
+            assert_eq!(replayed_item.id(), live_item.id());
+            assert_eq!(
+                replayed_item.timeline_start_us(),
+                live_item.timeline_start_us()
+            );
+            assert_eq!(replayed_item.timeline_end_us(), live_item.timeline_end_us());
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // to_log() / History::replay() are the project-file-facing wrappers
+    // around the free log()/replay() pair.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn to_log_replays_onto_a_fresh_timeline() {
+        let (mut tl, track_id, _clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+
+        let saved_log = history.to_log();
+
+        let mut replayed = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        History::replay(&saved_log, &mut replayed).unwrap();
+
+        assert_eq!(replayed.tracks[0].items.len(), tl.tracks[0].items.len());
+    }
+
+    // -----------------------------------------------------------------------
+    // dump_script() / load_script() round-trip a log through JSON, so a
+    // saved bug-report script can be replayed in a unit test.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn dump_and_load_script_roundtrips_through_replay() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        history
+            .execute(
+                Box::new(SplitCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        let script = history.dump_script().unwrap();
+        let loaded = load_script(&script).unwrap();
+        assert_eq!(loaded, history.log());
+
+        let mut replayed = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+        replay(&loaded, &mut replayed).unwrap();
+        assert_eq!(replayed.tracks[0].items.len(), tl.tracks[0].items.len());
+    }
+
+    // -----------------------------------------------------------------------
+    // Command coalescing: drag-gesture commands merge into one undo step
+    // within the configured time window.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn move_commands_coalesce_within_merge_window() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(
+            tl.tracks[0].items[0].timeline_start_us(),
+            TimeUs(2_000_000)
+        );
+
+        // The two moves coalesced into a single undo step: one undo restores
+        // the clip to its position before *either* move, not the first move.
+        history.undo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn move_then_move_back_annuls_within_window() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(0))),
+                &mut tl,
+            )
+            .unwrap();
+
+        // Net effect is a no-op, so the two moves cancel and there is
+        // nothing left to undo for this clip.
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn merge_does_not_apply_to_different_items() {
+        let track_id = Uuid::new_v4();
+        let clip_a = Uuid::new_v4();
+        let clip_b = Uuid::new_v4();
+        let item_a = Item::VideoClip {
+            id: clip_a,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let item_b = Item::VideoClip {
+            id: clip_b,
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(10_000_000),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let mut tl = Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items: vec![item_a, item_b],
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_a, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_b, TimeUs(11_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        // Different items never coalesce, so both moves remain undoable
+        // independently.
+        assert!(history.undo(&mut tl).is_ok());
+        assert!(history.undo(&mut tl).is_ok());
+        assert!(history.undo(&mut tl).is_err());
+    }
+
+    #[test]
+    fn merge_disabled_without_explicit_window() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        // merge_window defaults to None, so back-to-back moves stay separate
+        // undo steps even though nothing else happened between them.
+        let mut history = History::new(100);
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        history.undo(&mut tl).unwrap();
+        assert_eq!(
+            tl.tracks[0].items[0].timeline_start_us(),
+            TimeUs(1_000_000)
+        );
+        assert!(history.can_undo());
+    }
+
+    #[test]
+    fn trim_in_commands_coalesce_within_merge_window() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(Box::new(TrimInCommand::new(clip_id, TimeUs(500_000))), &mut tl)
+            .unwrap();
+        history
+            .execute(
+                Box::new(TrimInCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(
+            tl.tracks[0].items[0].source_in_us(),
+            Some(TimeUs(1_000_000))
+        );
+
+        history.undo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].source_in_us(), Some(TimeUs(0)));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn trim_out_commands_coalesce_within_merge_window() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(
+                Box::new(TrimOutCommand::new(clip_id, TimeUs(4_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(TrimOutCommand::new(clip_id, TimeUs(3_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(
+            tl.tracks[0].items[0].source_out_us(),
+            Some(TimeUs(3_000_000))
+        );
+
+        history.undo(&mut tl).unwrap();
+        assert_eq!(
+            tl.tracks[0].items[0].source_out_us(),
+            Some(TimeUs(5_000_000))
+        );
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn move_to_track_commands_coalesce_within_merge_window() {
+        let track_a = Uuid::new_v4();
+        let track_b = Uuid::new_v4();
+        let clip_id = Uuid::new_v4();
+        let item = Item::VideoClip {
+            id: clip_id,
+            asset_id: Uuid::new_v4(),
+            track_id: track_a,
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let mut tl = Timeline {
+            tracks: vec![
+                Track {
+                    id: track_a,
+                    kind: TrackKind::Video,
+                    items: vec![item],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+                Track {
+                    id: track_b,
+                    kind: TrackKind::Video,
+                    items: vec![],
+                    transitions: vec![],
+                    subtitles: None,
+                },
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let mut history = History::new(100);
+        history.set_merge_window(Some(Duration::from_millis(200)));
+
+        history
+            .execute(
+                Box::new(MoveItemToTrackCommand::new(clip_id, track_b, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemToTrackCommand::new(clip_id, track_a, TimeUs(0))),
+                &mut tl,
+            )
+            .unwrap();
+
+        // Moved to track B then straight back to the original track and
+        // position: the two moves cancel out entirely.
+        assert_eq!(tl.tracks[0].items.len(), 1);
+        assert_eq!(tl.tracks[1].items.len(), 0);
+        assert!(!history.can_undo());
+    }
+
+    // -----------------------------------------------------------------------
+    // Branching history: undoing and then making a new edit keeps the old
+    // branch around instead of discarding it.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn new_edit_after_undo_creates_sibling_branch_instead_of_discarding() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history.undo(&mut tl).unwrap();
+
+        // Diverge: a different move from the same point.
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(2_000_000));
+
+        // The original branch (move to 1M) is still in the tree, reachable
+        // from the node we diverged at.
+        history.undo(&mut tl).unwrap();
+        let branches = history.branches();
+        assert_eq!(branches.len(), 2);
+
+        history.switch_branch(branches[0]).unwrap();
+        history.redo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(1_000_000));
+    }
+
+    #[test]
+    fn switch_branch_rejects_node_not_a_child_of_current() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        let bogus_node = history.current_node() + 100;
+        assert!(history.switch_branch(bogus_node).is_err());
+    }
+
+    #[test]
+    fn go_to_walks_across_branches_via_common_ancestor() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let mut history = History::new(100);
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        let branch_a = history.current_node();
+
+        history.undo(&mut tl).unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(2_000_000));
+
+        // Jump straight back to branch_a's node without manually walking
+        // undo/redo.
+        history.go_to(branch_a, &mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(1_000_000));
+        assert_eq!(history.current_node(), branch_a);
+    }
+
+    #[test]
+    fn go_to_rejects_unknown_node() {
+        let (mut tl, track_id, _, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+        assert!(history.go_to(999, &mut tl).is_err());
+    }
+
+    // -----------------------------------------------------------------------
+    // Saved-state marker: is_saved() is a position, not a boolean, so
+    // undoing past the save point and redoing back to it is still "saved".
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn fresh_history_is_saved() {
+        let history = History::new(100);
+        assert!(history.is_saved());
+        assert!(!history.is_dirty());
+        assert!(!history.can_save());
+    }
+
+    #[test]
+    fn executing_a_command_marks_dirty_until_saved_again() {
+        let (mut tl, track_id, _, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        assert!(history.is_dirty());
+
+        history.set_saved();
+        assert!(history.is_saved());
+        assert!(!history.can_save());
+    }
+
+    #[test]
+    fn undo_past_save_point_then_redo_back_is_saved_again() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        let mut history = History::new(100);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        history.set_saved();
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert!(history.is_dirty());
+
+        history.undo(&mut tl).unwrap();
+        assert!(history.is_saved());
+
+        history.redo(&mut tl).unwrap();
+        assert!(history.is_dirty());
+    }
+
+    #[test]
+    fn save_point_invalidated_once_pruned_past_max_size() {
+        let (mut tl, track_id, _, item) = make_test_timeline();
+        let mut history = History::new(2);
+
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, item)), &mut tl)
+            .unwrap();
+        // Saved at the very first command, which will fall out of the
+        // 2-deep retained window once enough later commands are executed.
+        history.set_saved();
+
+        for i in 0..3 {
+            let more_item = Item::VideoClip {
+                id: Uuid::new_v4(),
+                asset_id: Uuid::new_v4(),
+                track_id,
+                timeline_start_us: TimeUs((i + 1) * 10_000_000),
+                source_in_us: TimeUs(0),
+                source_out_us: TimeUs(5_000_000),
+                speed: 1.0,
+                fade_in_us: TimeUs::ZERO,
+                fade_out_us: TimeUs::ZERO,
+            };
+            history
+                .execute(Box::new(AddItemCommand::new(track_id, more_item)), &mut tl)
+                .unwrap();
+        }
+
+        // The saved node was pruned out of the retained window, so there is
+        // no longer any position the document can be considered "saved" at.
+        assert!(history.is_dirty());
+    }
+
+    // -----------------------------------------------------------------------
+    // Transactions: begin_transaction/push/commit/abort buffer a group of
+    // commands into one CompositeCommand undo step.
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn committed_transaction_undoes_and_redoes_as_one_step() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        history.begin_transaction().unwrap();
+        history
+            .push(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .push(
+                Box::new(TrimInCommand::new(clip_id, TimeUs(500_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history.commit_transaction("Ripple delete").unwrap();
+
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(1_000_000));
+        assert_eq!(tl.tracks[0].items[0].source_in_us(), Some(TimeUs(500_000)));
+        assert_eq!(history.undo_description(), Some("Ripple delete"));
+
+        // Both buffered edits undo together.
+        history.undo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+        assert_eq!(tl.tracks[0].items[0].source_in_us(), Some(TimeUs(0)));
+
+        // And redo together.
+        history.redo(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(1_000_000));
+        assert_eq!(tl.tracks[0].items[0].source_in_us(), Some(TimeUs(500_000)));
+    }
+
+    #[test]
+    fn empty_transaction_commit_is_a_noop() {
+        let (mut tl, track_id, _, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        history.begin_transaction().unwrap();
+        history.commit_transaction("Nothing happened").unwrap();
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn aborted_transaction_undoes_applied_commands_and_records_nothing() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        history.begin_transaction().unwrap();
+        history
+            .push(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(1_000_000));
+
+        history.abort(&mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn nested_transaction_is_rejected() {
+        let mut history = History::new(100);
+        history.begin_transaction().unwrap();
+        assert!(history.begin_transaction().is_err());
+    }
+
+    #[test]
+    fn push_without_open_transaction_errors() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+        let result = history.push(
+            Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+            &mut tl,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn composite_command_rolls_back_partial_execution_on_failure() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+
+        let bogus_id = Uuid::new_v4();
+        let composite = CompositeCommand::new(
+            vec![
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                Box::new(MoveItemCommand::new(bogus_id, TimeUs(2_000_000))),
+            ],
+            "Bad group",
+        );
+
+        let result = composite.execute(&mut tl);
+        assert!(result.is_err());
+        // The first child's move was rolled back before the error surfaced.
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+    }
+
+    // -----------------------------------------------------------------------
+    // Timestamped edits: entries() and time_travel_to().
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn entries_lists_the_active_stack_oldest_first() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(TrimInCommand::new(clip_id, TimeUs(500_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        let descriptions: Vec<&str> = history.entries().map(|(desc, _)| desc).collect();
+        assert_eq!(descriptions, vec!["Move clip", "Trim in-point"]);
+    }
+
+    #[test]
+    fn time_travel_to_now_lands_on_the_most_recent_command() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        history.time_travel_to(SystemTime::now(), &mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(2_000_000));
+    }
+
+    #[test]
+    fn time_travel_to_the_past_undoes_everything_after_it() {
+        let (mut tl, track_id, clip_id, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        let before_any_edit = SystemTime::now();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(1_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+        history
+            .execute(
+                Box::new(MoveItemCommand::new(clip_id, TimeUs(2_000_000))),
+                &mut tl,
+            )
+            .unwrap();
+
+        history.time_travel_to(before_any_edit, &mut tl).unwrap();
+        assert_eq!(tl.tracks[0].items[0].timeline_start_us(), TimeUs(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn description_with_elapsed_includes_seconds_ago() {
+        let (mut tl, track_id, _, item) = make_test_timeline();
+        tl.add_item(track_id, item).unwrap();
+        let mut history = History::new(100);
+
+        assert!(history.undo_description_with_elapsed().is_none());
+
+        let other_item = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(10_000_000),
+            source_in_us: TimeUs(0),
+            source_out_us: TimeUs(3_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        history
+            .execute(Box::new(AddItemCommand::new(track_id, other_item)), &mut tl)
+            .unwrap();
+
+        let desc = history.undo_description_with_elapsed().unwrap();
+        assert!(desc.starts_with("Add clip ("));
+        assert!(desc.ends_with("s ago)"));
+    }
 }
@@ -0,0 +1,5 @@
+//! Lightweight, dependency-free media container probing, used to derive
+//! clip durations (and other facts `Timeline` needs) without shelling out to
+//! an external tool like ffprobe.
+
+pub mod probe;
@@ -0,0 +1,497 @@
+use crate::error::{CoreError, Result};
+use crate::types::TimeUs;
+use std::path::Path;
+
+/// What an MP4 track carries, read from its `hdlr` box's handler type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackMediaKind {
+    Video,
+    Audio,
+    /// Anything else (timed text, hint tracks, ...): still counted towards
+    /// `AssetInfo::duration_us`, just not distinguished further.
+    Other,
+}
+
+/// One `trak` box's duration, independent of `AssetInfo::duration_us` (the
+/// longest track's duration), since tracks in a real-world file
+/// occasionally disagree by a frame or two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackInfo {
+    pub kind: TrackMediaKind,
+    /// The track's `mdhd` timescale, in ticks per second.
+    pub timescale: u32,
+    pub duration_us: TimeUs,
+}
+
+/// A source file's duration and per-track breakdown, probed directly from
+/// the container without an external tool. See [`probe_asset`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub duration_us: TimeUs,
+    pub tracks: Vec<TrackInfo>,
+}
+
+/// Probe an MP4/MOV file's duration by walking its `moov` box tree: each
+/// `trak`'s `mdhd` gives the timescale, and its `stbl/stts` time-to-sample
+/// table gives the total sample-delta count, converted to microseconds via
+/// `duration_us = sample_delta_sum * 1_000_000 / timescale`. The overall
+/// `AssetInfo::duration_us` is the longest track, which is what determines
+/// how long the asset plays for.
+pub fn probe_asset(path: &Path) -> Result<AssetInfo> {
+    let data = std::fs::read(path)?;
+    probe_asset_bytes(&data)
+}
+
+/// Read the sync-sample (keyframe) presentation timestamps out of an
+/// MP4/MOV container's first track, by walking `moov -> trak -> mdia ->
+/// minf -> stbl` and combining the `stss` sync-sample table with the `stts`
+/// decode-time table and (if present) the `ctts` composition-offset table,
+/// so out-of-order B-frame reordering doesn't throw off the result:
+/// `pts = dts + composition_offset`.
+///
+/// Returns `Ok(vec![])` if the track has no `stss` box, which per the MP4
+/// spec means every sample is a sync sample -- callers should treat that as
+/// "no snapping needed" rather than "no keyframes". Used by
+/// [`crate::history::SplitCommand::new_snapped`] to cut only at positions
+/// that can be decoded without re-encoding.
+pub fn read_keyframes_us(path: &Path) -> Result<Vec<TimeUs>> {
+    let data = std::fs::read(path)?;
+    read_keyframes_us_bytes(&data)
+}
+
+fn read_keyframes_us_bytes(data: &[u8]) -> Result<Vec<TimeUs>> {
+    let Some(moov) = find_box(data, b"moov") else {
+        return Ok(Vec::new());
+    };
+    let Some(trak) = find_box(moov, b"trak") else {
+        return Ok(Vec::new());
+    };
+    let Some(mdia) = find_box(trak, b"mdia") else {
+        return Ok(Vec::new());
+    };
+    let Some(mdhd) = find_box(mdia, b"mdhd") else {
+        return Ok(Vec::new());
+    };
+    let Some(minf) = find_box(mdia, b"minf") else {
+        return Ok(Vec::new());
+    };
+    let Some(stbl) = find_box(minf, b"stbl") else {
+        return Ok(Vec::new());
+    };
+
+    let timescale = mdhd_timescale(mdhd).unwrap_or(1) as f64;
+
+    let Some(stss) = find_box(stbl, b"stss") else {
+        // No sync-sample table: every sample is a keyframe, so there's
+        // nothing to snap to.
+        return Ok(Vec::new());
+    };
+    let Some(stts) = find_box(stbl, b"stts") else {
+        return Ok(Vec::new());
+    };
+
+    let sync_samples = parse_stss(stss);
+    let decode_ticks = build_sample_decode_times(stts);
+    let composition_offsets = find_box(stbl, b"ctts")
+        .map(build_composition_offsets)
+        .unwrap_or_default();
+
+    let mut keyframes_us: Vec<TimeUs> = sync_samples
+        .into_iter()
+        .filter_map(|sample_number| {
+            let index = sample_number.saturating_sub(1) as usize;
+            let dts = *decode_ticks.get(index)?;
+            let offset = composition_offsets.get(index).copied().unwrap_or(0);
+            let pts_ticks = dts as i64 + offset;
+            Some(TimeUs::from_seconds(pts_ticks as f64 / timescale))
+        })
+        .collect();
+
+    keyframes_us.sort();
+    Ok(keyframes_us)
+}
+
+/// Expand a `ctts` box's run-length `(sample_count, sample_offset)` entries
+/// into a per-sample vector of composition-time offsets (in timescale
+/// ticks), so `offsets[n]` is the offset for sample `n + 1`. Offsets are
+/// read as signed (version 1 `ctts` allows negative offsets; version 0's
+/// are always small and positive, so reinterpreting the bits is harmless).
+fn build_composition_offsets(ctts: &[u8]) -> Vec<i64> {
+    let Some(count_bytes) = ctts.get(4..8) else {
+        return Vec::new();
+    };
+    let entry_count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut offsets = Vec::new();
+    let mut cursor = 8;
+    for _ in 0..entry_count {
+        let Some(sample_count_bytes) = ctts.get(cursor..cursor + 4) else {
+            break;
+        };
+        let Some(sample_offset_bytes) = ctts.get(cursor + 4..cursor + 8) else {
+            break;
+        };
+        let sample_count = u32::from_be_bytes(sample_count_bytes.try_into().unwrap());
+        let sample_offset = i32::from_be_bytes(sample_offset_bytes.try_into().unwrap()) as i64;
+        for _ in 0..sample_count {
+            offsets.push(sample_offset);
+        }
+        cursor += 8;
+    }
+    offsets
+}
+
+/// Parse an `stss` box into the 1-based sample numbers that are sync
+/// samples.
+fn parse_stss(stss: &[u8]) -> Vec<u32> {
+    let Some(count_bytes) = stss.get(4..8) else {
+        return Vec::new();
+    };
+    let count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut entries = Vec::with_capacity(count);
+    let mut offset = 8;
+    for _ in 0..count {
+        let Some(bytes) = stss.get(offset..offset + 4) else {
+            break;
+        };
+        entries.push(u32::from_be_bytes(bytes.try_into().unwrap()));
+        offset += 4;
+    }
+    entries
+}
+
+/// Expand an `stts` box's run-length `(sample_count, sample_delta)` entries
+/// into a per-sample vector of cumulative decode timestamps (in timescale
+/// ticks), so `decode_ticks[n]` is the decode time of sample `n + 1`.
+fn build_sample_decode_times(stts: &[u8]) -> Vec<u64> {
+    let Some(count_bytes) = stts.get(4..8) else {
+        return Vec::new();
+    };
+    let entry_count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut times = Vec::new();
+    let mut decode_time = 0u64;
+    let mut offset = 8;
+    for _ in 0..entry_count {
+        let Some(sample_count_bytes) = stts.get(offset..offset + 4) else {
+            break;
+        };
+        let Some(sample_delta_bytes) = stts.get(offset + 4..offset + 8) else {
+            break;
+        };
+        let sample_count = u32::from_be_bytes(sample_count_bytes.try_into().unwrap());
+        let sample_delta = u32::from_be_bytes(sample_delta_bytes.try_into().unwrap()) as u64;
+        for _ in 0..sample_count {
+            times.push(decode_time);
+            decode_time += sample_delta;
+        }
+        offset += 8;
+    }
+    times
+}
+
+fn probe_asset_bytes(data: &[u8]) -> Result<AssetInfo> {
+    let moov = find_box(data, b"moov")
+        .ok_or_else(|| CoreError::Other("not an ISO-BMFF container (no moov box)".into()))?;
+
+    let tracks: Vec<TrackInfo> = find_all_boxes(moov, b"trak")
+        .into_iter()
+        .filter_map(probe_track)
+        .collect();
+
+    let duration_us = tracks
+        .iter()
+        .map(|t| t.duration_us)
+        .max()
+        .unwrap_or(TimeUs::ZERO);
+
+    Ok(AssetInfo {
+        duration_us,
+        tracks,
+    })
+}
+
+fn probe_track(trak: &[u8]) -> Option<TrackInfo> {
+    let mdia = find_box(trak, b"mdia")?;
+    let mdhd = find_box(mdia, b"mdhd")?;
+    let timescale = mdhd_timescale(mdhd)?;
+    let kind = find_box(mdia, b"hdlr")
+        .and_then(handler_track_kind)
+        .unwrap_or(TrackMediaKind::Other);
+
+    let stbl = find_box(mdia, b"minf").and_then(|minf| find_box(minf, b"stbl"));
+    let sample_delta_sum = stbl
+        .and_then(|stbl| find_box(stbl, b"stts"))
+        .map(stts_sample_delta_sum)
+        .unwrap_or(0);
+
+    let duration_us = if timescale > 0 {
+        TimeUs((sample_delta_sum as i64) * 1_000_000 / timescale as i64)
+    } else {
+        TimeUs::ZERO
+    };
+
+    Some(TrackInfo {
+        kind,
+        timescale,
+        duration_us,
+    })
+}
+
+/// Read the handler type fourcc out of an `hdlr` box (`version(1) + flags(3)
+/// + predefined(4) + handler_type(4)`) and map it to a [`TrackMediaKind`].
+fn handler_track_kind(hdlr: &[u8]) -> Option<TrackMediaKind> {
+    let handler_type = hdlr.get(8..12)?;
+    Some(match handler_type {
+        b"vide" => TrackMediaKind::Video,
+        b"soun" => TrackMediaKind::Audio,
+        _ => TrackMediaKind::Other,
+    })
+}
+
+/// Read the `timescale` field out of an `mdhd` box (version 0 or 1 layout).
+fn mdhd_timescale(mdhd: &[u8]) -> Option<u32> {
+    let version = *mdhd.first()?;
+    let timescale_offset = if version == 1 { 4 + 8 + 8 } else { 4 + 4 + 4 };
+    let bytes = mdhd.get(timescale_offset..timescale_offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Sum an `stts` box's run-length `(sample_count, sample_delta)` entries
+/// into the track's total duration, in timescale ticks.
+fn stts_sample_delta_sum(stts: &[u8]) -> u64 {
+    let Some(count_bytes) = stts.get(4..8) else {
+        return 0;
+    };
+    let entry_count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut total = 0u64;
+    let mut offset = 8;
+    for _ in 0..entry_count {
+        let Some(sample_count_bytes) = stts.get(offset..offset + 4) else {
+            break;
+        };
+        let Some(sample_delta_bytes) = stts.get(offset + 4..offset + 8) else {
+            break;
+        };
+        let sample_count = u32::from_be_bytes(sample_count_bytes.try_into().unwrap()) as u64;
+        let sample_delta = u32::from_be_bytes(sample_delta_bytes.try_into().unwrap()) as u64;
+        total += sample_count * sample_delta;
+        offset += 8;
+    }
+    total
+}
+
+/// Find the first top-level child box with the given four-character-code
+/// inside `data`, returning its payload (the bytes after the 8-byte header).
+fn find_box<'a>(data: &'a [u8], fourcc: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut offset = 0usize;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().ok()?) as usize;
+        let kind = &data[offset + 4..offset + 8];
+        let (header_len, box_len) = if size == 1 {
+            if offset + 16 > data.len() {
+                return None;
+            }
+            let large = u64::from_be_bytes(data[offset + 8..offset + 16].try_into().ok()?);
+            (16, large as usize)
+        } else if size == 0 {
+            (8, data.len() - offset)
+        } else {
+            (8, size)
+        };
+        if box_len < header_len || offset + box_len > data.len() {
+            return None;
+        }
+        if kind == fourcc {
+            return Some(&data[offset + header_len..offset + box_len]);
+        }
+        offset += box_len;
+    }
+    None
+}
+
+/// Like [`find_box`], but collects every top-level child box with the given
+/// fourcc instead of stopping at the first (a `moov` holds one `trak` per
+/// track).
+fn find_all_boxes<'a>(data: &'a [u8], fourcc: &[u8; 4]) -> Vec<&'a [u8]> {
+    let mut offset = 0usize;
+    let mut matches = Vec::new();
+    while offset + 8 <= data.len() {
+        let Some(size_bytes) = data.get(offset..offset + 4) else {
+            break;
+        };
+        let size = u32::from_be_bytes(size_bytes.try_into().unwrap()) as usize;
+        let kind = &data[offset + 4..offset + 8];
+        let (header_len, box_len) = if size == 1 {
+            if offset + 16 > data.len() {
+                break;
+            }
+            let large = u64::from_be_bytes(data[offset + 8..offset + 16].try_into().unwrap());
+            (16, large as usize)
+        } else if size == 0 {
+            (8, data.len() - offset)
+        } else {
+            (8, size)
+        };
+        if box_len < header_len || offset + box_len > data.len() {
+            break;
+        }
+        if kind == fourcc {
+            matches.push(&data[offset + header_len..offset + box_len]);
+        }
+        offset += box_len;
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal box with a 32-bit size header.
+    fn make_box(fourcc: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&((8 + payload.len()) as u32).to_be_bytes());
+        out.extend_from_slice(fourcc);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn make_mdhd(timescale: u32) -> Vec<u8> {
+        let mut payload = vec![0u8; 4 + 4 + 4 + 4 + 4];
+        payload[12..16].copy_from_slice(&timescale.to_be_bytes());
+        make_box(b"mdhd", &payload)
+    }
+
+    fn make_hdlr(handler_type: &[u8; 4]) -> Vec<u8> {
+        let mut payload = vec![0u8; 8];
+        payload.extend_from_slice(handler_type);
+        make_box(b"hdlr", &payload)
+    }
+
+    fn make_stts(entries: &[(u32, u32)]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&0u32.to_be_bytes()); // version + flags
+        payload.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for (count, delta) in entries {
+            payload.extend_from_slice(&count.to_be_bytes());
+            payload.extend_from_slice(&delta.to_be_bytes());
+        }
+        make_box(b"stts", &payload)
+    }
+
+    fn make_trak(timescale: u32, handler_type: &[u8; 4], stts_entries: &[(u32, u32)]) -> Vec<u8> {
+        let stbl = make_box(b"stbl", &make_stts(stts_entries));
+        let minf = make_box(b"minf", &stbl);
+        let mut mdia = Vec::new();
+        mdia.extend(make_mdhd(timescale));
+        mdia.extend(make_hdlr(handler_type));
+        mdia.extend(minf);
+        make_box(b"trak", &make_box(b"mdia", &mdia))
+    }
+
+    #[test]
+    fn probes_video_and_audio_track_durations() {
+        // 90_000 Hz timescale, 3000 ticks/frame * 90 frames = 3s video track.
+        let video_trak = make_trak(90_000, b"vide", &[(90, 90_000 / 30)]);
+        // 48_000 Hz timescale, 1024 samples/frame * ~141 frames ~= 3.008s.
+        let audio_trak = make_trak(48_000, b"soun", &[(141, 1024)]);
+        let mut moov_payload = Vec::new();
+        moov_payload.extend(video_trak);
+        moov_payload.extend(audio_trak);
+        let moov = make_box(b"moov", &moov_payload);
+
+        let info = probe_asset_bytes(&moov).unwrap();
+        assert_eq!(info.tracks.len(), 2);
+
+        let video = info
+            .tracks
+            .iter()
+            .find(|t| t.kind == TrackMediaKind::Video)
+            .unwrap();
+        assert_eq!(video.timescale, 90_000);
+        assert_eq!(video.duration_us, TimeUs(3_000_000));
+
+        let audio = info
+            .tracks
+            .iter()
+            .find(|t| t.kind == TrackMediaKind::Audio)
+            .unwrap();
+        assert_eq!(audio.timescale, 48_000);
+
+        // Overall duration is the longest track.
+        assert_eq!(info.duration_us, audio.duration_us.max(video.duration_us));
+    }
+
+    #[test]
+    fn missing_moov_box_errors() {
+        let result = probe_asset_bytes(b"not an mp4 file");
+        assert!(result.is_err());
+    }
+
+    fn make_stss(sync_samples: &[u32]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&0u32.to_be_bytes()); // version + flags
+        payload.extend_from_slice(&(sync_samples.len() as u32).to_be_bytes());
+        for s in sync_samples {
+            payload.extend_from_slice(&s.to_be_bytes());
+        }
+        make_box(b"stss", &payload)
+    }
+
+    fn make_ctts(entries: &[(u32, i32)]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&0u32.to_be_bytes()); // version + flags
+        payload.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for (count, offset) in entries {
+            payload.extend_from_slice(&count.to_be_bytes());
+            payload.extend_from_slice(&offset.to_be_bytes());
+        }
+        make_box(b"ctts", &payload)
+    }
+
+    fn make_moov_with_video_stbl(stbl_children: Vec<u8>) -> Vec<u8> {
+        let stbl = make_box(b"stbl", &stbl_children);
+        let minf = make_box(b"minf", &stbl);
+        let mut mdia = Vec::new();
+        mdia.extend(make_mdhd(90_000));
+        mdia.extend(make_hdlr(b"vide"));
+        mdia.extend(minf);
+        let trak = make_box(b"trak", &make_box(b"mdia", &mdia));
+        make_box(b"moov", &trak)
+    }
+
+    #[test]
+    fn keyframes_use_stss_and_stts_without_ctts() {
+        let mut stbl = Vec::new();
+        // 90 samples/frame-run, 3000 ticks/sample at 90_000 Hz => 1 sample = 1/30s.
+        stbl.extend(make_stts(&[(90, 3_000)]));
+        // Sync samples 1 and 31 -> presentation times 0s and 1s.
+        stbl.extend(make_stss(&[1, 31]));
+        let moov = make_moov_with_video_stbl(stbl);
+
+        let keyframes = read_keyframes_us_bytes(&moov).unwrap();
+        assert_eq!(keyframes, vec![TimeUs(0), TimeUs(1_000_000)]);
+    }
+
+    #[test]
+    fn keyframes_apply_ctts_composition_offsets() {
+        let mut stbl = Vec::new();
+        stbl.extend(make_stts(&[(60, 3_000)]));
+        stbl.extend(make_stss(&[1]));
+        // First sample's presentation time is pushed back by 2 frames' worth
+        // (here 6_000 ticks, i.e. 1/15s at 90_000 Hz) by B-frame reordering.
+        stbl.extend(make_ctts(&[(60, 6_000)]));
+        let moov = make_moov_with_video_stbl(stbl);
+
+        let keyframes = read_keyframes_us_bytes(&moov).unwrap();
+        assert_eq!(keyframes, vec![TimeUs::from_seconds(6_000.0 / 90_000.0)]);
+    }
+
+    #[test]
+    fn no_stss_box_means_every_sample_is_a_keyframe_and_is_a_noop() {
+        let stbl = make_stts(&[(90, 3_000)]);
+        let moov = make_moov_with_video_stbl(stbl);
+
+        let keyframes = read_keyframes_us_bytes(&moov).unwrap();
+        assert!(keyframes.is_empty());
+    }
+}
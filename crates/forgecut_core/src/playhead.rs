@@ -0,0 +1,382 @@
+use crate::types::*;
+use std::cell::Cell;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// A source of monotonic time for the [`Playhead`] engine. Injected so
+/// playback can be driven by the wall clock in production and by a
+/// hand-set clock in tests, without real sleeps.
+pub trait Clocks {
+    /// The current time, as measured from some arbitrary epoch. Only
+    /// differences between two calls are meaningful.
+    fn now_monotonic(&self) -> TimeUs;
+}
+
+/// A [`Clocks`] backed by [`Instant::now`], timestamped relative to its own
+/// construction.
+#[derive(Debug)]
+pub struct SystemClock {
+    epoch: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clocks for SystemClock {
+    fn now_monotonic(&self) -> TimeUs {
+        TimeUs::from_seconds(self.epoch.elapsed().as_secs_f64())
+    }
+}
+
+/// A [`Clocks`] whose time is set manually, so playback logic can be
+/// exercised deterministically in tests.
+#[derive(Debug)]
+pub struct TestClock {
+    now: Cell<TimeUs>,
+}
+
+impl TestClock {
+    pub fn new(start_us: TimeUs) -> Self {
+        Self {
+            now: Cell::new(start_us),
+        }
+    }
+
+    /// Jump directly to `time_us`.
+    pub fn set(&self, time_us: TimeUs) {
+        self.now.set(time_us);
+    }
+
+    /// Move the clock forward (or backward, for a negative delta) by
+    /// `delta_us`.
+    pub fn advance(&self, delta_us: TimeUs) {
+        self.now.set(self.now.get() + delta_us);
+    }
+}
+
+impl Clocks for TestClock {
+    fn now_monotonic(&self) -> TimeUs {
+        self.now.get()
+    }
+}
+
+/// One track's active item changing as the playhead crosses a clip
+/// boundary, reported by [`Playhead::poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveItemChange {
+    pub track_id: Uuid,
+    pub previous: Option<Uuid>,
+    pub current: Option<Uuid>,
+}
+
+/// Drives a playback position over a [`Timeline`] using an injected
+/// [`Clocks`], and reports which [`Item`] is active on each track as the
+/// position advances. Holds no reference to the `Timeline` itself --
+/// callers pass it to [`active_item`](Self::active_item) / [`poll`](Self::poll)
+/// each time, so the same `Playhead` can drive preview for a timeline that's
+/// still being edited.
+#[derive(Debug)]
+pub struct Playhead<C: Clocks> {
+    clock: C,
+    /// The position when paused, or the position at the moment `play()` was
+    /// last called while playing.
+    base_position_us: TimeUs,
+    /// The clock reading at the moment `play()` was last called. `None`
+    /// while paused.
+    played_at: Option<TimeUs>,
+    /// Last item reported active per track, by track id, for diffing in
+    /// [`poll`](Self::poll).
+    last_active: Vec<(Uuid, Option<Uuid>)>,
+}
+
+impl<C: Clocks> Playhead<C> {
+    /// Create a paused playhead at `TimeUs::ZERO`.
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            base_position_us: TimeUs::ZERO,
+            played_at: None,
+            last_active: Vec::new(),
+        }
+    }
+
+    /// The current playhead position: advancing in real time while
+    /// playing, frozen while paused.
+    pub fn position(&self) -> TimeUs {
+        match self.played_at {
+            Some(started_at) => self.base_position_us + (self.clock.now_monotonic() - started_at),
+            None => self.base_position_us,
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.played_at.is_some()
+    }
+
+    /// Jump to `position_us`, preserving play/pause state.
+    pub fn seek(&mut self, position_us: TimeUs) {
+        self.base_position_us = position_us;
+        if self.played_at.is_some() {
+            self.played_at = Some(self.clock.now_monotonic());
+        }
+    }
+
+    /// Start (or resume) advancing the playhead from its current position.
+    /// A no-op if already playing.
+    pub fn play(&mut self) {
+        if self.played_at.is_none() {
+            self.base_position_us = self.position();
+            self.played_at = Some(self.clock.now_monotonic());
+        }
+    }
+
+    /// Freeze the playhead at its current position. A no-op if already
+    /// paused.
+    pub fn pause(&mut self) {
+        if self.played_at.is_some() {
+            self.base_position_us = self.position();
+            self.played_at = None;
+        }
+    }
+
+    /// The item active on `track` at the current position, or `None` if the
+    /// position falls in a gap. Items on a track never overlap, so at most
+    /// one can match.
+    pub fn active_item(&self, track: &Track) -> Option<Uuid> {
+        let position_us = self.position();
+        track
+            .items
+            .iter()
+            .find(|item| {
+                item.timeline_start_us() <= position_us && position_us < item.timeline_end_us()
+            })
+            .map(|item| item.id())
+    }
+
+    /// The active item across every track in `timeline`, resolving
+    /// overlapping tracks (e.g. an overlay above a base video track) by
+    /// track order: the *last* track in [`Timeline::tracks`] that has an
+    /// active item wins, mirroring how later tracks composite on top.
+    pub fn topmost_active_item(&self, timeline: &Timeline) -> Option<Uuid> {
+        timeline
+            .tracks
+            .iter()
+            .rev()
+            .find_map(|track| self.active_item(track))
+    }
+
+    /// Recompute the active item on every track in `timeline` and return the
+    /// ones that changed since the last call to `poll`, in track order. The
+    /// first call reports a change for every track whose active item isn't
+    /// `None`.
+    pub fn poll(&mut self, timeline: &Timeline) -> Vec<ActiveItemChange> {
+        let mut changes = Vec::new();
+        let mut next_active = Vec::with_capacity(timeline.tracks.len());
+
+        for track in &timeline.tracks {
+            let current = self.active_item(track);
+            let previous = self
+                .last_active
+                .iter()
+                .find(|(track_id, _)| *track_id == track.id)
+                .map(|(_, item_id)| *item_id)
+                .unwrap_or(None);
+
+            if current != previous {
+                changes.push(ActiveItemChange {
+                    track_id: track.id,
+                    previous,
+                    current,
+                });
+            }
+            next_active.push((track.id, current));
+        }
+
+        self.last_active = next_active;
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_clip(track_id: Uuid, start_us: i64, end_us: i64) -> Item {
+        Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(start_us),
+            source_in_us: TimeUs::ZERO,
+            source_out_us: TimeUs(end_us - start_us),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        }
+    }
+
+    fn make_track(id: Uuid, items: Vec<Item>) -> Track {
+        Track {
+            id,
+            kind: TrackKind::Video,
+            items,
+            transitions: vec![],
+            subtitles: None,
+        }
+    }
+
+    #[test]
+    fn paused_playhead_does_not_advance() {
+        let clock = TestClock::new(TimeUs::ZERO);
+        let playhead = Playhead::new(clock);
+
+        playhead.clock.advance(TimeUs::from_seconds(5.0));
+
+        assert_eq!(playhead.position(), TimeUs::ZERO);
+        assert!(!playhead.is_playing());
+    }
+
+    #[test]
+    fn playing_advances_with_the_clock() {
+        let clock = TestClock::new(TimeUs::ZERO);
+        let mut playhead = Playhead::new(clock);
+
+        playhead.play();
+        playhead.clock.advance(TimeUs::from_seconds(2.0));
+
+        assert_eq!(playhead.position(), TimeUs::from_seconds(2.0));
+    }
+
+    #[test]
+    fn pause_freezes_the_current_position() {
+        let clock = TestClock::new(TimeUs::ZERO);
+        let mut playhead = Playhead::new(clock);
+
+        playhead.play();
+        playhead.clock.advance(TimeUs::from_seconds(3.0));
+        playhead.pause();
+        playhead.clock.advance(TimeUs::from_seconds(10.0));
+
+        assert_eq!(playhead.position(), TimeUs::from_seconds(3.0));
+        assert!(!playhead.is_playing());
+    }
+
+    #[test]
+    fn seek_while_playing_continues_from_new_position() {
+        let clock = TestClock::new(TimeUs::ZERO);
+        let mut playhead = Playhead::new(clock);
+
+        playhead.play();
+        playhead.seek(TimeUs::from_seconds(10.0));
+        playhead.clock.advance(TimeUs::from_seconds(1.0));
+
+        assert_eq!(playhead.position(), TimeUs::from_seconds(11.0));
+    }
+
+    #[test]
+    fn active_item_resolves_by_position_within_a_track() {
+        let track_id = Uuid::new_v4();
+        let clip_a = make_clip(track_id, 0, 2_000_000);
+        let clip_b = make_clip(track_id, 2_000_000, 4_000_000);
+        let clip_a_id = clip_a.id();
+        let clip_b_id = clip_b.id();
+        let track = make_track(track_id, vec![clip_a, clip_b]);
+
+        let clock = TestClock::new(TimeUs(1_000_000));
+        let playhead = Playhead::new(clock);
+        assert_eq!(playhead.active_item(&track), Some(clip_a_id));
+
+        let clock = TestClock::new(TimeUs(3_000_000));
+        let playhead = Playhead::new(clock);
+        assert_eq!(playhead.active_item(&track), Some(clip_b_id));
+    }
+
+    #[test]
+    fn active_item_is_none_in_a_gap() {
+        let track_id = Uuid::new_v4();
+        let track = make_track(track_id, vec![make_clip(track_id, 1_000_000, 2_000_000)]);
+
+        let clock = TestClock::new(TimeUs(5_000_000));
+        let playhead = Playhead::new(clock);
+        assert_eq!(playhead.active_item(&track), None);
+    }
+
+    #[test]
+    fn topmost_active_item_prefers_the_later_track() {
+        let base_track_id = Uuid::new_v4();
+        let overlay_track_id = Uuid::new_v4();
+        let base_clip = make_clip(base_track_id, 0, 5_000_000);
+        let overlay_clip = make_clip(overlay_track_id, 1_000_000, 2_000_000);
+        let overlay_clip_id = overlay_clip.id();
+
+        let timeline = Timeline {
+            tracks: vec![
+                make_track(base_track_id, vec![base_clip]),
+                make_track(overlay_track_id, vec![overlay_clip]),
+            ],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let clock = TestClock::new(TimeUs(1_500_000));
+        let playhead = Playhead::new(clock);
+        assert_eq!(
+            playhead.topmost_active_item(&timeline),
+            Some(overlay_clip_id)
+        );
+    }
+
+    #[test]
+    fn poll_reports_only_changed_tracks() {
+        let track_id = Uuid::new_v4();
+        let clip_a = make_clip(track_id, 0, 2_000_000);
+        let clip_b = make_clip(track_id, 2_000_000, 4_000_000);
+        let clip_a_id = clip_a.id();
+        let clip_b_id = clip_b.id();
+        let timeline = Timeline {
+            tracks: vec![make_track(track_id, vec![clip_a, clip_b])],
+            markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
+        };
+
+        let clock = TestClock::new(TimeUs::ZERO);
+        let mut playhead = Playhead::new(clock);
+
+        let first = playhead.poll(&timeline);
+        assert_eq!(
+            first,
+            vec![ActiveItemChange {
+                track_id,
+                previous: None,
+                current: Some(clip_a_id),
+            }]
+        );
+
+        // No movement: nothing changed.
+        assert!(playhead.poll(&timeline).is_empty());
+
+        playhead.seek(TimeUs(2_000_000));
+        let second = playhead.poll(&timeline);
+        assert_eq!(
+            second,
+            vec![ActiveItemChange {
+                track_id,
+                previous: Some(clip_a_id),
+                current: Some(clip_b_id),
+            }]
+        );
+    }
+}
@@ -12,6 +12,8 @@ impl Project {
             settings,
             assets: vec![],
             timeline: Timeline::new(),
+            intro: None,
+            outro: None,
         }
     }
 
@@ -39,6 +41,8 @@ impl Timeline {
         Self {
             tracks: vec![],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         }
     }
 }
@@ -54,7 +58,7 @@ pub fn preset_1080p() -> ProjectSettings {
     ProjectSettings {
         width: 1920,
         height: 1080,
-        fps: 30.0,
+        fps: FrameRate::whole(30),
         sample_rate: 48000,
     }
 }
@@ -64,7 +68,7 @@ pub fn preset_shorts() -> ProjectSettings {
     ProjectSettings {
         width: 1080,
         height: 1920,
-        fps: 30.0,
+        fps: FrameRate::whole(30),
         sample_rate: 48000,
     }
 }
@@ -74,7 +78,7 @@ pub fn preset_720p() -> ProjectSettings {
     ProjectSettings {
         width: 1280,
         height: 720,
-        fps: 30.0,
+        fps: FrameRate::whole(30),
         sample_rate: 48000,
     }
 }
@@ -84,7 +88,7 @@ pub fn preset_4k() -> ProjectSettings {
     ProjectSettings {
         width: 3840,
         height: 2160,
-        fps: 30.0,
+        fps: FrameRate::whole(30),
         sample_rate: 48000,
     }
 }
@@ -94,7 +98,7 @@ pub fn preset_1080p_60() -> ProjectSettings {
     ProjectSettings {
         width: 1920,
         height: 1080,
-        fps: 60.0,
+        fps: FrameRate::whole(60),
         sample_rate: 48000,
     }
 }
@@ -150,11 +154,21 @@ mod tests {
                 duration_us: TimeUs(10_000_000),
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 codec: "h264".to_string(),
                 audio_channels: 2,
                 audio_sample_rate: 48000,
+                keyframes_us: vec![],
+                streams: vec![],
+                rotation_deg: 0,
+                display_width: 0,
+                display_height: 0,
+                metadata: Default::default(),
+                frame_count: None,
+                color: Default::default(),
             }),
+            tags: Default::default(),
+            source_url: None,
         });
         project.timeline.tracks.push(Track {
             id: track_id,
@@ -166,7 +180,12 @@ mod tests {
                 timeline_start_us: TimeUs(0),
                 source_in_us: TimeUs(0),
                 source_out_us: TimeUs(5_000_000),
+                speed: 1.0,
+                fade_in_us: TimeUs::ZERO,
+                fade_out_us: TimeUs::ZERO,
             }],
+            transitions: vec![],
+            subtitles: None,
         });
 
         project.save_to_file(&path).unwrap();
@@ -185,31 +204,31 @@ mod tests {
         let p1080 = preset_1080p();
         assert_eq!(p1080.width, 1920);
         assert_eq!(p1080.height, 1080);
-        assert_eq!(p1080.fps, 30.0);
+        assert_eq!(p1080.fps, FrameRate::whole(30));
         assert_eq!(p1080.sample_rate, 48000);
 
         let shorts = preset_shorts();
         assert_eq!(shorts.width, 1080);
         assert_eq!(shorts.height, 1920);
-        assert_eq!(shorts.fps, 30.0);
+        assert_eq!(shorts.fps, FrameRate::whole(30));
         assert_eq!(shorts.sample_rate, 48000);
 
         let p720 = preset_720p();
         assert_eq!(p720.width, 1280);
         assert_eq!(p720.height, 720);
-        assert_eq!(p720.fps, 30.0);
+        assert_eq!(p720.fps, FrameRate::whole(30));
         assert_eq!(p720.sample_rate, 48000);
 
         let p4k = preset_4k();
         assert_eq!(p4k.width, 3840);
         assert_eq!(p4k.height, 2160);
-        assert_eq!(p4k.fps, 30.0);
+        assert_eq!(p4k.fps, FrameRate::whole(30));
         assert_eq!(p4k.sample_rate, 48000);
 
         let p1080_60 = preset_1080p_60();
         assert_eq!(p1080_60.width, 1920);
         assert_eq!(p1080_60.height, 1080);
-        assert_eq!(p1080_60.fps, 60.0);
+        assert_eq!(p1080_60.fps, FrameRate::whole(60));
         assert_eq!(p1080_60.sample_rate, 48000);
     }
 
@@ -1,4 +1,74 @@
 use crate::types::*;
+use std::collections::BTreeSet;
+
+/// Maintains a sorted, deduplicated set of snap points -- harvested from a
+/// timeline's item edges and markers plus the playhead -- so a caller (e.g.
+/// a drag interaction) can query the nearest one without rebuilding the set
+/// on every call. Kept up to date incrementally via [`insert`](Self::insert),
+/// [`remove`](Self::remove), and [`replace`](Self::replace) as items are
+/// added/removed/moved, rather than rebuilt from [`from_timeline`](Self::from_timeline)
+/// each time.
+#[derive(Debug, Clone, Default)]
+pub struct SnapModel {
+    points: BTreeSet<TimeUs>,
+}
+
+impl SnapModel {
+    /// Build a model from a timeline's current item/marker edges plus the
+    /// playhead position.
+    pub fn from_timeline(timeline: &Timeline, playhead_us: TimeUs) -> Self {
+        let mut points: BTreeSet<TimeUs> =
+            collect_snap_points(timeline, None).into_iter().collect();
+        points.insert(playhead_us);
+        Self { points }
+    }
+
+    /// Add a single snap point.
+    pub fn insert(&mut self, point_us: TimeUs) {
+        self.points.insert(point_us);
+    }
+
+    /// Remove a single snap point. No-op if the position isn't present.
+    /// Positions are not reference-counted: the point is dropped even if
+    /// another item's edge or a marker still shares it.
+    pub fn remove(&mut self, point_us: TimeUs) {
+        self.points.remove(&point_us);
+    }
+
+    /// Replace one snap point with another, e.g. an item edge that moved.
+    pub fn replace(&mut self, old_us: TimeUs, new_us: TimeUs) {
+        self.points.remove(&old_us);
+        self.points.insert(new_us);
+    }
+
+    /// Return the nearest snap point to `candidate_us` within `radius_us`
+    /// (binary search over the sorted set via its two surrounding
+    /// neighbors), or `candidate_us` unchanged if none is in range. The
+    /// neighbor with the smaller absolute delta wins; when both are equally
+    /// far, the smaller point is returned.
+    pub fn snap(&self, candidate_us: TimeUs, radius_us: TimeUs) -> TimeUs {
+        let mut best: Option<(TimeUs, i64)> = None;
+
+        if let Some(&below) = self.points.range(..=candidate_us).next_back() {
+            best = Some((below, (candidate_us.0 - below.0).abs()));
+        }
+        if let Some(&above) = self.points.range(candidate_us..).next() {
+            let dist = (candidate_us.0 - above.0).abs();
+            let better = match best {
+                Some((_, best_dist)) => dist < best_dist,
+                None => true,
+            };
+            if better {
+                best = Some((above, dist));
+            }
+        }
+
+        match best {
+            Some((point, dist)) if dist <= radius_us.0 => point,
+            _ => candidate_us,
+        }
+    }
+}
 
 /// Find the nearest snap point within the threshold.
 /// Returns the snapped position if within threshold, otherwise the original position.
@@ -25,10 +95,65 @@ pub fn find_snap_point(
     }
 }
 
+/// Round `time_us` to the nearest frame boundary at `fps`, via
+/// [`FrameRate::frame_index`]/[`FrameRate::frame_time`]'s exact integer
+/// arithmetic rather than a `seconds * fps` float round-trip, so long
+/// timelines at a rate like 29.97fps don't drift off their true frame
+/// boundaries.
+pub fn quantize_to_frame(time_us: TimeUs, fps: FrameRate) -> TimeUs {
+    let index = fps.frame_index(time_us);
+    let below = fps.frame_time(index);
+    let above = fps.frame_time(index + 1);
+    if (above.0 - time_us.0) < (time_us.0 - below.0) {
+        above
+    } else {
+        below
+    }
+}
+
+/// Like [`find_snap_point`], but also considers the nearest frame boundary
+/// at `fps` as a candidate snap target. Export and mpv seeking are both
+/// frame-oriented, so a plain microsecond-granularity snap can still leave
+/// the playhead or a clip edge between frames; this lets a drag snap to
+/// whichever of {timeline snap point, frame boundary} is closest within
+/// `threshold_us`.
+pub fn find_snap_point_with_grid(
+    position_us: TimeUs,
+    snap_points: &[TimeUs],
+    threshold_us: TimeUs,
+    fps: FrameRate,
+) -> TimeUs {
+    let frame_boundary_us = quantize_to_frame(position_us, fps);
+    let mut candidates: Vec<TimeUs> = snap_points.to_vec();
+    candidates.push(frame_boundary_us);
+    find_snap_point(position_us, &candidates, threshold_us)
+}
+
 /// Collect all snap points from a timeline (clip edges, markers).
 pub fn collect_snap_points(
     timeline: &Timeline,
     exclude_item_id: Option<uuid::Uuid>,
+) -> Vec<TimeUs> {
+    collect_snap_points_with_grid(timeline, exclude_item_id, None)
+}
+
+/// A regular frame-grid of snap marks, for "snap to frame" independent of
+/// clip edges/markers. `every_n_frames` of `1` offers every frame; a larger
+/// value (e.g. every 10 frames) keeps the candidate list small on long
+/// timelines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSnap {
+    pub fps: FrameRate,
+    pub every_n_frames: u32,
+}
+
+/// Like [`collect_snap_points`], optionally injecting regular grid marks
+/// every `grid.every_n_frames` frames (at `grid.fps`) across the timeline's
+/// span, so the UI can offer "snap to frame" independently of clip edges.
+pub fn collect_snap_points_with_grid(
+    timeline: &Timeline,
+    exclude_item_id: Option<uuid::Uuid>,
+    grid: Option<GridSnap>,
 ) -> Vec<TimeUs> {
     let mut points = Vec::new();
 
@@ -50,6 +175,26 @@ pub fn collect_snap_points(
         points.push(marker.time_us);
     }
 
+    if let Some(grid) = grid {
+        if grid.fps.num > 0 && grid.every_n_frames > 0 {
+            let end_us = timeline
+                .tracks
+                .iter()
+                .flat_map(|t| &t.items)
+                .map(|item| item.timeline_end_us())
+                .max()
+                .unwrap_or(TimeUs::ZERO);
+            let step_us = grid.fps.frame_time(grid.every_n_frames as i64);
+            if step_us > TimeUs::ZERO {
+                let mut mark = TimeUs::ZERO;
+                while mark <= end_us {
+                    points.push(mark);
+                    mark = mark + step_us;
+                }
+            }
+        }
+    }
+
     points.sort();
     points.dedup();
     points
@@ -74,6 +219,9 @@ mod tests {
                         timeline_start_us: TimeUs(1_000_000),
                         source_in_us: TimeUs::ZERO,
                         source_out_us: TimeUs(3_000_000),
+                        speed: 1.0,
+                        fade_in_us: TimeUs::ZERO,
+                        fade_out_us: TimeUs::ZERO,
                     },
                     Item::VideoClip {
                         id: Uuid::new_v4(),
@@ -82,14 +230,21 @@ mod tests {
                         timeline_start_us: TimeUs(5_000_000),
                         source_in_us: TimeUs::ZERO,
                         source_out_us: TimeUs(2_000_000),
+                        speed: 1.0,
+                        fade_in_us: TimeUs::ZERO,
+                        fade_out_us: TimeUs::ZERO,
                     },
                 ],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![Marker {
                 id: Uuid::new_v4(),
                 time_us: TimeUs(10_000_000),
                 label: "marker1".to_string(),
             }],
+            config: TimelineConfig::default(),
+            groups: vec![],
         }
     }
 
@@ -154,6 +309,9 @@ mod tests {
                         timeline_start_us: TimeUs(1_000_000),
                         source_in_us: TimeUs::ZERO,
                         source_out_us: TimeUs(2_000_000),
+                        speed: 1.0,
+                        fade_in_us: TimeUs::ZERO,
+                        fade_out_us: TimeUs::ZERO,
                     },
                     Item::VideoClip {
                         id: Uuid::new_v4(),
@@ -162,10 +320,17 @@ mod tests {
                         timeline_start_us: TimeUs(5_000_000),
                         source_in_us: TimeUs::ZERO,
                         source_out_us: TimeUs(1_000_000),
+                        speed: 1.0,
+                        fade_in_us: TimeUs::ZERO,
+                        fade_out_us: TimeUs::ZERO,
                     },
                 ],
+                transitions: vec![],
+                subtitles: None,
             }],
             markers: vec![],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
 
         let points = collect_snap_points(&timeline, Some(item_id));
@@ -211,4 +376,169 @@ mod tests {
         let result = find_snap_point(TimeUs(1_700_000), &points, threshold);
         assert_eq!(result, TimeUs(2_000_000));
     }
+
+    // -----------------------------------------------------------------------
+    // SnapModel
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn snap_model_from_timeline_includes_playhead() {
+        let timeline = make_timeline_with_clips();
+        let model = SnapModel::from_timeline(&timeline, TimeUs(20_000_000));
+
+        let result = model.snap(TimeUs(20_100_000), TimeUs(200_000));
+        assert_eq!(result, TimeUs(20_000_000));
+    }
+
+    #[test]
+    fn snap_model_snaps_to_nearest_within_radius() {
+        let timeline = make_timeline_with_clips();
+        let model = SnapModel::from_timeline(&timeline, TimeUs(0));
+
+        // Clip edges from make_timeline_with_clips: 0, 1M, 4M, 5M, 7M, 10M (marker)
+        let result = model.snap(TimeUs(4_100_000), TimeUs(200_000));
+        assert_eq!(result, TimeUs(4_000_000));
+    }
+
+    #[test]
+    fn snap_model_returns_candidate_unchanged_outside_radius() {
+        let timeline = make_timeline_with_clips();
+        let model = SnapModel::from_timeline(&timeline, TimeUs(0));
+
+        let result = model.snap(TimeUs(2_500_000), TimeUs(200_000));
+        assert_eq!(result, TimeUs(2_500_000));
+    }
+
+    #[test]
+    fn snap_model_breaks_ties_toward_smaller_point() {
+        let mut model = SnapModel::default();
+        model.insert(TimeUs(1_000_000));
+        model.insert(TimeUs(2_000_000));
+
+        // 1_500_000 is equidistant (500k) from both.
+        let result = model.snap(TimeUs(1_500_000), TimeUs(600_000));
+        assert_eq!(result, TimeUs(1_000_000));
+    }
+
+    #[test]
+    fn snap_model_replace_moves_a_point() {
+        let mut model = SnapModel::default();
+        model.insert(TimeUs(1_000_000));
+
+        model.replace(TimeUs(1_000_000), TimeUs(3_000_000));
+
+        assert_eq!(
+            model.snap(TimeUs(3_100_000), TimeUs(200_000)),
+            TimeUs(3_000_000)
+        );
+        // The old position is gone, so a nearby candidate stays unchanged.
+        assert_eq!(
+            model.snap(TimeUs(1_100_000), TimeUs(200_000)),
+            TimeUs(1_100_000)
+        );
+    }
+
+    #[test]
+    fn snap_model_remove_drops_a_point() {
+        let mut model = SnapModel::default();
+        model.insert(TimeUs(1_000_000));
+        model.insert(TimeUs(5_000_000));
+
+        model.remove(TimeUs(1_000_000));
+
+        assert_eq!(
+            model.snap(TimeUs(1_100_000), TimeUs(200_000)),
+            TimeUs(1_100_000)
+        );
+    }
+
+    #[test]
+    fn snap_model_empty_returns_candidate_unchanged() {
+        let model = SnapModel::default();
+        let result = model.snap(TimeUs(5_000_000), TimeUs(1_000_000));
+        assert_eq!(result, TimeUs(5_000_000));
+    }
+
+    // -----------------------------------------------------------------------
+    // Frame-grid snapping
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn quantize_to_frame_rounds_to_nearest_frame_boundary() {
+        // At 30fps, a frame is ~33_333us. 40_000us is closer to frame 1
+        // (33_333us) than frame 2 (66_667us).
+        assert_eq!(
+            quantize_to_frame(TimeUs(40_000), FrameRate::whole(30)),
+            TimeUs(33_333)
+        );
+        assert_eq!(
+            quantize_to_frame(TimeUs::ZERO, FrameRate::whole(30)),
+            TimeUs::ZERO
+        );
+    }
+
+    #[test]
+    fn find_snap_point_with_grid_snaps_to_frame_boundary() {
+        let snap_points = vec![TimeUs(0), TimeUs(5_000_000)];
+        // 40_000us is nowhere near a timeline snap point, but it's within
+        // threshold of the 30fps frame boundary at 33_333us.
+        let result = find_snap_point_with_grid(
+            TimeUs(40_000),
+            &snap_points,
+            TimeUs(10_000),
+            FrameRate::whole(30),
+        );
+        assert_eq!(result, TimeUs(33_333));
+    }
+
+    #[test]
+    fn find_snap_point_with_grid_prefers_timeline_point_on_tie() {
+        // A timeline snap point that coincides with the frame boundary
+        // should win the same as it would without the grid.
+        let snap_points = vec![TimeUs(33_333)];
+        let result = find_snap_point_with_grid(
+            TimeUs(40_000),
+            &snap_points,
+            TimeUs(10_000),
+            FrameRate::whole(30),
+        );
+        assert_eq!(result, TimeUs(33_333));
+    }
+
+    #[test]
+    fn find_snap_point_with_grid_falls_back_to_original_outside_threshold() {
+        let snap_points: Vec<TimeUs> = vec![];
+        // 1_000_017us sits far (in frame terms) from any 30fps frame
+        // boundary relative to a 1us threshold.
+        let result = find_snap_point_with_grid(
+            TimeUs(1_000_017),
+            &snap_points,
+            TimeUs(1),
+            FrameRate::whole(30),
+        );
+        assert_eq!(result, TimeUs(1_000_017));
+    }
+
+    #[test]
+    fn collect_snap_points_with_grid_injects_regular_marks() {
+        let timeline = make_timeline_with_clips();
+        let grid = GridSnap {
+            fps: FrameRate::whole(10),
+            every_n_frames: 10,
+        }; // one mark per second
+        let points = collect_snap_points_with_grid(&timeline, None, Some(grid));
+
+        assert!(points.contains(&TimeUs(1_000_000)));
+        assert!(points.contains(&TimeUs(2_000_000)));
+        assert!(points.contains(&TimeUs(3_000_000)));
+    }
+
+    #[test]
+    fn collect_snap_points_without_grid_matches_plain_collect_snap_points() {
+        let timeline = make_timeline_with_clips();
+        assert_eq!(
+            collect_snap_points_with_grid(&timeline, None, None),
+            collect_snap_points(&timeline, None)
+        );
+    }
 }
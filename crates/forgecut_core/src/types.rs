@@ -8,7 +8,9 @@ use uuid::Uuid;
 // TimeUs
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub struct TimeUs(pub i64);
 
 impl TimeUs {
@@ -69,6 +71,110 @@ impl fmt::Display for TimeUs {
     }
 }
 
+// ---------------------------------------------------------------------------
+// FrameRate
+// ---------------------------------------------------------------------------
+
+/// An exact rational frame rate (e.g. `30000/1001` for 29.97fps), stored in
+/// lowest terms. Broadcast rates like 29.97 and 23.976 are themselves exact
+/// fractions; storing them as `f64` loses that exactness and accumulates
+/// drift in frame-boundary math over a long render, so every computation
+/// that needs an exact frame boundary (`compile()`'s `-r`/`fps=` filter
+/// output, [`FrameRate::frame_index`]/[`FrameRate::frame_time`]) goes
+/// through this type instead. Use [`FrameRate::as_f64`] only for display.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct FrameRate {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl FrameRate {
+    pub const NTSC_24: Self = Self {
+        num: 24000,
+        den: 1001,
+    };
+    pub const NTSC_30: Self = Self {
+        num: 30000,
+        den: 1001,
+    };
+    pub const NTSC_60: Self = Self {
+        num: 60000,
+        den: 1001,
+    };
+
+    /// An integer frame rate, e.g. `FrameRate::whole(30)` for a plain 30fps.
+    pub fn whole(fps: u32) -> Self {
+        Self::new(fps, 1)
+    }
+
+    /// Construct from a numerator/denominator pair, reduced to lowest terms.
+    pub fn new(num: u32, den: u32) -> Self {
+        if num == 0 || den == 0 {
+            return Self {
+                num,
+                den: den.max(1),
+            };
+        }
+        let divisor = gcd(num, den);
+        Self {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    /// Lossy `f64` approximation for display purposes only -- never use this
+    /// for frame-boundary math, that's what [`FrameRate::frame_index`] and
+    /// [`FrameRate::frame_time`] are for.
+    pub fn as_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// The index of the frame containing `t`, computed with exact integer
+    /// arithmetic so it never drifts regardless of how far into the render
+    /// `t` falls.
+    pub fn frame_index(&self, t: TimeUs) -> i64 {
+        if self.num == 0 {
+            return 0;
+        }
+        let numerator = t.0 as i128 * self.num as i128;
+        let denominator = self.den as i128 * 1_000_000;
+        (numerator / denominator) as i64
+    }
+
+    /// The exact start time of `frame`, the inverse of [`FrameRate::frame_index`].
+    pub fn frame_time(&self, frame: i64) -> TimeUs {
+        if self.num == 0 {
+            return TimeUs::ZERO;
+        }
+        let numerator = frame as i128 * self.den as i128 * 1_000_000;
+        TimeUs((numerator / self.num as i128) as i64)
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self::whole(30)
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // AssetKind
 // ---------------------------------------------------------------------------
@@ -78,6 +184,10 @@ pub enum AssetKind {
     Video,
     Audio,
     Image,
+    /// A GIF/APNG/animated WebP: behaves like a short looping video on the
+    /// timeline rather than a static image, so the renderer loops/trims it
+    /// by frame count instead of treating it as a single still.
+    AnimatedImage,
 }
 
 // ---------------------------------------------------------------------------
@@ -89,10 +199,149 @@ pub struct ProbeResult {
     pub duration_us: TimeUs,
     pub width: u32,
     pub height: u32,
-    pub fps: f64,
+    pub fps: FrameRate,
     pub codec: String,
     pub audio_channels: u32,
     pub audio_sample_rate: u32,
+    /// Decode timestamps of keyframes (MP4 sync samples), sorted ascending.
+    /// Empty means either no index could be built or every sample is a
+    /// keyframe (e.g. no `stss` box), in which case seeks need no snapping.
+    #[serde(default)]
+    pub keyframes_us: Vec<TimeUs>,
+    /// Every stream the container reports, in ffprobe's original order. The
+    /// top-level `width`/`height`/`fps`/`codec`/`audio_channels`/
+    /// `audio_sample_rate` fields above are convenience copies of the first
+    /// selected video/audio stream; this list is what lets an editor offer
+    /// alternate audio tracks or language dubs.
+    #[serde(default)]
+    pub streams: Vec<StreamInfo>,
+    /// Display rotation in degrees, normalized to one of {0, 90, 180, 270},
+    /// read from the stream's display matrix side data (or the legacy
+    /// `tags.rotate` value). `width`/`height` above are the raw pixel
+    /// dimensions; use `display_width`/`display_height` for layout.
+    #[serde(default)]
+    pub rotation_deg: u16,
+    /// `width`/`height` swapped when `rotation_deg` is 90 or 270, so
+    /// downstream layout code always sees the correct playback aspect ratio.
+    #[serde(default)]
+    pub display_width: u32,
+    #[serde(default)]
+    pub display_height: u32,
+    /// Container/stream tags that don't fit the top-level convenience
+    /// fields above: creation time, encoder, and per-stream language/title.
+    #[serde(default)]
+    pub metadata: Metadata,
+    /// Total frame count of the primary video stream, when ffprobe reports
+    /// one (`nb_frames`). Used to tell a single-frame image apart from a
+    /// multi-frame animated one; `None` when unknown.
+    #[serde(default)]
+    pub frame_count: Option<u64>,
+    /// Pixel format and color characteristics of the primary video stream,
+    /// used to detect HDR content that needs tone-mapping before mixing
+    /// with SDR assets on the same timeline.
+    #[serde(default)]
+    pub color: ColorInfo,
+}
+
+// ---------------------------------------------------------------------------
+// ColorInfo
+// ---------------------------------------------------------------------------
+
+/// Pixel format and color characteristics of a video stream. `is_hdr` is
+/// derived from `color_transfer`: true for `smpte2084` (HDR10/PQ) or
+/// `arib-std-b67` (HLG), false otherwise, including when unknown.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ColorInfo {
+    pub pix_fmt: Option<String>,
+    /// Bits per sample, read from `bits_per_raw_sample` when ffprobe reports
+    /// it, otherwise inferred from the `pix_fmt` suffix (e.g. `yuv420p10le`
+    /// implies 10).
+    pub bit_depth: Option<u8>,
+    pub color_space: Option<String>,
+    pub color_transfer: Option<String>,
+    pub color_primaries: Option<String>,
+    #[serde(default)]
+    pub is_hdr: bool,
+}
+
+// ---------------------------------------------------------------------------
+// StreamInfo
+// ---------------------------------------------------------------------------
+
+/// One stream within a probed container (a video, audio, or subtitle track).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamInfo {
+    /// ffprobe's stream index within the container.
+    pub index: u32,
+    pub codec_type: String,
+    pub codec: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub channels: Option<u32>,
+    /// Set for a video stream that is an attached thumbnail/cover image
+    /// rather than actual video content.
+    #[serde(default)]
+    pub is_cover_art: bool,
+    /// Video-only; parsed from `r_frame_rate`, `None` for audio/subtitle
+    /// streams or when ffprobe didn't report one.
+    #[serde(default)]
+    pub frame_rate: Option<FrameRate>,
+    /// Audio-only sample rate in Hz.
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+    /// Per-stream bit rate in bits/second, when ffprobe reports it
+    /// separately from the container's overall bit rate.
+    #[serde(default)]
+    pub bit_rate: Option<u64>,
+    /// This stream's own duration, which can differ slightly from the
+    /// container-level `ProbeResult::duration_us` (e.g. a shorter audio
+    /// track, or a muxer that only timestamps one stream precisely).
+    #[serde(default)]
+    pub duration_us: Option<TimeUs>,
+    /// Color/transfer characteristics, populated for video streams.
+    #[serde(default)]
+    pub color: Option<ColorInfo>,
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+/// Container/stream tags surfaced for display and sorting, as opposed to the
+/// codec/geometry fields used for rendering. Missing or unparsable values
+/// are `None` rather than an error, since these tags are written by whatever
+/// tool last touched the file and are frequently absent or malformed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Metadata {
+    /// The container's `creation_time` tag, parsed from ISO-8601/RFC-3339
+    /// into Unix seconds, so imported clips can be sorted chronologically.
+    pub creation_time_unix_s: Option<i64>,
+    /// The encoder/muxer tag (e.g. "Lavf60.16.100").
+    pub encoder: Option<String>,
+    /// Language/title tags for each audio stream, so an editor can label
+    /// alternate audio tracks and dubs. Keyed by the stream's ffprobe index.
+    #[serde(default)]
+    pub audio_stream_tags: Vec<StreamTags>,
+}
+
+/// Language/title tags for a single stream, matched to `StreamInfo::index`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamTags {
+    pub stream_index: u32,
+    pub language: Option<String>,
+    pub title: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// MediaTags
+// ---------------------------------------------------------------------------
+
+/// Container-level metadata tags embedded in the source file, when present.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MediaTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub date: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -106,6 +355,13 @@ pub struct Asset {
     pub path: PathBuf,
     pub kind: AssetKind,
     pub probe: Option<ProbeResult>,
+    #[serde(default)]
+    pub tags: MediaTags,
+    /// Origin URL for assets imported from a remote source, so a later
+    /// GC/refresh pass knows where the cached file came from. `None` for
+    /// assets imported from a local path.
+    #[serde(default)]
+    pub source_url: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -118,12 +374,17 @@ pub enum TrackKind {
     Audio,
     OverlayImage,
     OverlayText,
+    Subtitles,
 }
 
 // ---------------------------------------------------------------------------
 // Item
 // ---------------------------------------------------------------------------
 
+fn default_speed() -> f64 {
+    1.0
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Item {
     VideoClip {
@@ -133,6 +394,19 @@ pub enum Item {
         timeline_start_us: TimeUs,
         source_in_us: TimeUs,
         source_out_us: TimeUs,
+        /// Playback rate factor: the timeline duration of the clip is
+        /// `(source_out_us - source_in_us) / speed`, so `speed > 1.0` is
+        /// fast-forward and `speed < 1.0` is slow motion. The source range
+        /// itself stays in untouched source time.
+        #[serde(default = "default_speed")]
+        speed: f64,
+        /// Crossfade length at the head/tail of the clip, set when an
+        /// [`OverlapMode::Crossfade`] overlap creates a [`Transition`] with
+        /// a neighboring clip. Zero when the clip has no active crossfade.
+        #[serde(default)]
+        fade_in_us: TimeUs,
+        #[serde(default)]
+        fade_out_us: TimeUs,
     },
     AudioClip {
         id: Uuid,
@@ -142,6 +416,13 @@ pub enum Item {
         source_in_us: TimeUs,
         source_out_us: TimeUs,
         volume: f64,
+        /// See [`Item::VideoClip`]'s `speed` field.
+        #[serde(default = "default_speed")]
+        speed: f64,
+        #[serde(default)]
+        fade_in_us: TimeUs,
+        #[serde(default)]
+        fade_out_us: TimeUs,
     },
     ImageOverlay {
         id: Uuid,
@@ -166,6 +447,18 @@ pub enum Item {
         x: i32,
         y: i32,
     },
+    /// A nested sub-sequence (a saved group of clips/overlays) that moves,
+    /// trims, and splits as a single unit, like any other clip. `source_in_us`
+    /// and `source_out_us` trim the window of `sequence` that plays back,
+    /// the same way they trim a source file for `VideoClip`/`AudioClip`.
+    CompoundClip {
+        id: Uuid,
+        track_id: Uuid,
+        timeline_start_us: TimeUs,
+        source_in_us: TimeUs,
+        source_out_us: TimeUs,
+        sequence: Box<Timeline>,
+    },
 }
 
 impl Item {
@@ -175,28 +468,64 @@ impl Item {
             Item::AudioClip { id, .. } => *id,
             Item::ImageOverlay { id, .. } => *id,
             Item::TextOverlay { id, .. } => *id,
+            Item::CompoundClip { id, .. } => *id,
         }
     }
 
     pub fn timeline_start_us(&self) -> TimeUs {
         match self {
-            Item::VideoClip { timeline_start_us, .. } => *timeline_start_us,
-            Item::AudioClip { timeline_start_us, .. } => *timeline_start_us,
-            Item::ImageOverlay { timeline_start_us, .. } => *timeline_start_us,
-            Item::TextOverlay { timeline_start_us, .. } => *timeline_start_us,
+            Item::VideoClip {
+                timeline_start_us, ..
+            } => *timeline_start_us,
+            Item::AudioClip {
+                timeline_start_us, ..
+            } => *timeline_start_us,
+            Item::ImageOverlay {
+                timeline_start_us, ..
+            } => *timeline_start_us,
+            Item::TextOverlay {
+                timeline_start_us, ..
+            } => *timeline_start_us,
+            Item::CompoundClip {
+                timeline_start_us, ..
+            } => *timeline_start_us,
         }
     }
 
     pub fn duration_us(&self) -> TimeUs {
         match self {
-            Item::VideoClip { source_in_us, source_out_us, .. } => {
-                TimeUs(source_out_us.0 - source_in_us.0)
-            }
-            Item::AudioClip { source_in_us, source_out_us, .. } => {
-                TimeUs(source_out_us.0 - source_in_us.0)
-            }
+            Item::VideoClip {
+                source_in_us,
+                source_out_us,
+                speed,
+                ..
+            } => TimeUs((((source_out_us.0 - source_in_us.0) as f64) / speed).round() as i64),
+            Item::AudioClip {
+                source_in_us,
+                source_out_us,
+                speed,
+                ..
+            } => TimeUs((((source_out_us.0 - source_in_us.0) as f64) / speed).round() as i64),
             Item::ImageOverlay { duration_us, .. } => *duration_us,
             Item::TextOverlay { duration_us, .. } => *duration_us,
+            Item::CompoundClip {
+                source_in_us,
+                source_out_us,
+                ..
+            } => TimeUs(source_out_us.0 - source_in_us.0),
+        }
+    }
+
+    /// The playback rate factor for `VideoClip`/`AudioClip` -- see
+    /// [`Item::VideoClip`]'s `speed` field. `None` for item kinds that don't
+    /// time-remap.
+    pub fn speed(&self) -> Option<f64> {
+        match self {
+            Item::VideoClip { speed, .. } => Some(*speed),
+            Item::AudioClip { speed, .. } => Some(*speed),
+            Item::ImageOverlay { .. } | Item::TextOverlay { .. } | Item::CompoundClip { .. } => {
+                None
+            }
         }
     }
 
@@ -210,6 +539,7 @@ impl Item {
             Item::AudioClip { track_id, .. } => *track_id,
             Item::ImageOverlay { track_id, .. } => *track_id,
             Item::TextOverlay { track_id, .. } => *track_id,
+            Item::CompoundClip { track_id, .. } => *track_id,
         }
     }
 
@@ -219,6 +549,53 @@ impl Item {
             Item::AudioClip { asset_id, .. } => Some(*asset_id),
             Item::ImageOverlay { asset_id, .. } => Some(*asset_id),
             Item::TextOverlay { .. } => None,
+            Item::CompoundClip { .. } => None,
+        }
+    }
+
+    /// The in-point within the source asset, for clips that trim a source
+    /// range (`VideoClip`/`AudioClip`), or the in-point within a
+    /// `CompoundClip`'s nested sequence. `None` for overlay items, which
+    /// have no source range of their own.
+    pub fn source_in_us(&self) -> Option<TimeUs> {
+        match self {
+            Item::VideoClip { source_in_us, .. } => Some(*source_in_us),
+            Item::AudioClip { source_in_us, .. } => Some(*source_in_us),
+            Item::ImageOverlay { .. } => None,
+            Item::TextOverlay { .. } => None,
+            Item::CompoundClip { source_in_us, .. } => Some(*source_in_us),
+        }
+    }
+
+    /// The out-point within the source asset. See [`Item::source_in_us`].
+    pub fn source_out_us(&self) -> Option<TimeUs> {
+        match self {
+            Item::VideoClip { source_out_us, .. } => Some(*source_out_us),
+            Item::AudioClip { source_out_us, .. } => Some(*source_out_us),
+            Item::ImageOverlay { .. } => None,
+            Item::TextOverlay { .. } => None,
+            Item::CompoundClip { source_out_us, .. } => Some(*source_out_us),
+        }
+    }
+
+    /// The active crossfade length at the head of the clip, if any. `None`
+    /// for item kinds that can't carry a [`Transition`] (overlays, compound
+    /// clips).
+    pub fn fade_in_us(&self) -> Option<TimeUs> {
+        match self {
+            Item::VideoClip { fade_in_us, .. } => Some(*fade_in_us),
+            Item::AudioClip { fade_in_us, .. } => Some(*fade_in_us),
+            _ => None,
+        }
+    }
+
+    /// The active crossfade length at the tail of the clip. See
+    /// [`Item::fade_in_us`].
+    pub fn fade_out_us(&self) -> Option<TimeUs> {
+        match self {
+            Item::VideoClip { fade_out_us, .. } => Some(*fade_out_us),
+            Item::AudioClip { fade_out_us, .. } => Some(*fade_out_us),
+            _ => None,
         }
     }
 }
@@ -232,6 +609,142 @@ pub struct Track {
     pub id: Uuid,
     pub kind: TrackKind,
     pub items: Vec<Item>,
+    /// Crossfade transitions created when [`OverlapMode::Crossfade`] turns an
+    /// overlap between two adjacent clips into a fade instead of rejecting
+    /// it. Empty when the track has no overlapping clips.
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+    /// The external subtitle file burned in as captions when `kind` is
+    /// [`TrackKind::Subtitles`]. `None` for any other track kind.
+    #[serde(default)]
+    pub subtitles: Option<SubtitleTrack>,
+}
+
+// ---------------------------------------------------------------------------
+// SubtitleTrack
+// ---------------------------------------------------------------------------
+
+/// An SRT/WebVTT file burned in as styled captions, referenced directly by
+/// path rather than through [`Asset`]/`asset_id` -- a subtitle file isn't
+/// probed or previewed like a media asset, so it doesn't need the asset
+/// pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubtitleTrack {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub style: CaptionStyle,
+}
+
+/// How a subtitle track's cues are drawn: font size, an opaque background
+/// box behind the text (matching the "question overlay" look common in
+/// lecture-recording tools), and where on the frame the box sits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CaptionStyle {
+    #[serde(default = "default_caption_font_size")]
+    pub font_size: u32,
+    /// An ffmpeg color name or `#RRGGBB` hex, e.g. `"black"` or `"#1a1a2e"`.
+    #[serde(default = "default_caption_box_color")]
+    pub box_color: String,
+    #[serde(default = "default_caption_box_opacity")]
+    pub box_opacity: f64,
+    #[serde(default = "default_caption_box_border_width")]
+    pub box_border_width: u32,
+    #[serde(default)]
+    pub anchor: CaptionAnchor,
+}
+
+impl Default for CaptionStyle {
+    fn default() -> Self {
+        Self {
+            font_size: default_caption_font_size(),
+            box_color: default_caption_box_color(),
+            box_opacity: default_caption_box_opacity(),
+            box_border_width: default_caption_box_border_width(),
+            anchor: CaptionAnchor::default(),
+        }
+    }
+}
+
+fn default_caption_font_size() -> u32 {
+    36
+}
+
+fn default_caption_box_color() -> String {
+    "black".to_string()
+}
+
+fn default_caption_box_opacity() -> f64 {
+    0.6
+}
+
+fn default_caption_box_border_width() -> u32 {
+    10
+}
+
+/// Where a caption's background box sits on the frame.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum CaptionAnchor {
+    #[default]
+    BottomCenter,
+    TopCenter,
+}
+
+// ---------------------------------------------------------------------------
+// Transition
+// ---------------------------------------------------------------------------
+
+/// A crossfade between two adjacent clips on the same track, created when an
+/// overlap is accepted under [`OverlapMode::Crossfade`] instead of rejected.
+/// `out_item` is the earlier clip (fading out), `in_item` the later one
+/// (fading in); `region_us` is the overlapping span, `[in_item.start,
+/// out_item.end)`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transition {
+    pub out_item: Uuid,
+    pub in_item: Uuid,
+    pub region_us: (TimeUs, TimeUs),
+    /// The visual style a renderer should use for this crossfade (ffmpeg's
+    /// `xfade` transition name). Defaults to `CrossDissolve` for transitions
+    /// recorded before this field existed.
+    #[serde(default)]
+    pub kind: TransitionKind,
+}
+
+/// The visual style of a [`Transition`], matching ffmpeg's `xfade` filter's
+/// `transition` names closely enough that a renderer can map one to the
+/// other directly.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TransitionKind {
+    #[default]
+    CrossDissolve,
+    Fade,
+    WipeLeft,
+    SlideLeft,
+}
+
+// ---------------------------------------------------------------------------
+// OverlapMode / TimelineConfig
+// ---------------------------------------------------------------------------
+
+/// How [`Timeline`] editing methods handle a new overlap between two clips
+/// on the same track.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum OverlapMode {
+    /// Reject the edit with `CoreError::OverlapDetected` (the original
+    /// behavior).
+    #[default]
+    Reject,
+    /// Accept an overlap shaped like a simple tail/head crossover between
+    /// two clips, turning it into a `Transition` with clamped fade lengths
+    /// instead of erroring. Overlaps that aren't this shape (e.g. one clip
+    /// fully containing another) are still rejected.
+    Crossfade,
+}
+
+/// Per-timeline editing configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TimelineConfig {
+    pub overlap_mode: OverlapMode,
 }
 
 // ---------------------------------------------------------------------------
@@ -245,6 +758,42 @@ pub struct Marker {
     pub label: String,
 }
 
+// ---------------------------------------------------------------------------
+// Group
+// ---------------------------------------------------------------------------
+
+/// A set of item ids, possibly spanning multiple tracks, that move, trim,
+/// and split together as a single unit -- e.g. a video clip and its
+/// matching audio clip. See [`Timeline::group_items`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Group {
+    pub id: Uuid,
+    pub item_ids: Vec<Uuid>,
+}
+
+// ---------------------------------------------------------------------------
+// Clipboard
+// ---------------------------------------------------------------------------
+
+/// One copied item plus the [`TrackKind`] of the track it was copied from, so
+/// [`Timeline::paste`] can land it on a destination track of matching kind.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClipboardItem {
+    pub item: Item,
+    pub source_track_kind: TrackKind,
+}
+
+/// A captured selection of items, ready to be re-inserted elsewhere with
+/// [`Timeline::paste`] while preserving their spacing relative to one
+/// another. Built by [`Timeline::copy_items`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Clipboard {
+    pub items: Vec<ClipboardItem>,
+    /// The smallest `timeline_start_us` among the copied items -- the zero
+    /// point `paste` offsets everything from.
+    pub min_start_us: TimeUs,
+}
+
 // ---------------------------------------------------------------------------
 // Timeline
 // ---------------------------------------------------------------------------
@@ -253,6 +802,10 @@ pub struct Marker {
 pub struct Timeline {
     pub tracks: Vec<Track>,
     pub markers: Vec<Marker>,
+    #[serde(default)]
+    pub config: TimelineConfig,
+    #[serde(default)]
+    pub groups: Vec<Group>,
 }
 
 // ---------------------------------------------------------------------------
@@ -263,10 +816,35 @@ pub struct Timeline {
 pub struct ProjectSettings {
     pub width: u32,
     pub height: u32,
-    pub fps: f64,
+    pub fps: FrameRate,
     pub sample_rate: u32,
 }
 
+// ---------------------------------------------------------------------------
+// Bumper (intro/outro title cards)
+// ---------------------------------------------------------------------------
+
+/// What a [`Bumper`] is rendered over.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BumperBackground {
+    /// An ffmpeg color name or `#RRGGBB` hex, e.g. `"black"` or `"#1a1a2e"`.
+    Color(String),
+    /// A still image asset, scaled to fill the frame and held for the
+    /// bumper's full duration.
+    Image { asset_id: Uuid },
+}
+
+/// A synthesized title-card or end-card segment with no source asset of its
+/// own -- `forgecut_render` generates it from a `color`/`anullsrc` (or
+/// looped image) lavfi source and splices it onto the front
+/// (`Project.intro`) or back (`Project.outro`) of the compiled timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bumper {
+    pub text: String,
+    pub background: BumperBackground,
+    pub duration_us: TimeUs,
+}
+
 // ---------------------------------------------------------------------------
 // Project
 // ---------------------------------------------------------------------------
@@ -278,6 +856,10 @@ pub struct Project {
     pub settings: ProjectSettings,
     pub assets: Vec<Asset>,
     pub timeline: Timeline,
+    #[serde(default)]
+    pub intro: Option<Bumper>,
+    #[serde(default)]
+    pub outro: Option<Bumper>,
 }
 
 // ---------------------------------------------------------------------------
@@ -350,11 +932,21 @@ mod tests {
                 duration_us: TimeUs(10_000_000),
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 codec: "h264".to_string(),
                 audio_channels: 2,
                 audio_sample_rate: 48000,
+                keyframes_us: vec![],
+                streams: vec![],
+                rotation_deg: 0,
+                display_width: 0,
+                display_height: 0,
+                metadata: Default::default(),
+                frame_count: None,
+                color: Default::default(),
             }),
+            tags: Default::default(),
+            source_url: None,
         };
         let json = serde_json::to_string(&asset).unwrap();
         let back: Asset = serde_json::from_str(&json).unwrap();
@@ -370,6 +962,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs(0),
             source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
         let json = serde_json::to_string(&item).unwrap();
         let back: Item = serde_json::from_str(&json).unwrap();
@@ -382,6 +977,8 @@ mod tests {
             id: Uuid::new_v4(),
             kind: TrackKind::Video,
             items: vec![],
+            transitions: vec![],
+            subtitles: None,
         };
         let json = serde_json::to_string(&track).unwrap();
         let back: Track = serde_json::from_str(&json).unwrap();
@@ -397,6 +994,8 @@ mod tests {
                 time_us: TimeUs(1_000_000),
                 label: "intro".to_string(),
             }],
+            config: TimelineConfig::default(),
+            groups: vec![],
         };
         let json = serde_json::to_string(&timeline).unwrap();
         let back: Timeline = serde_json::from_str(&json).unwrap();
@@ -411,14 +1010,18 @@ mod tests {
             settings: ProjectSettings {
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 sample_rate: 48000,
             },
             assets: vec![],
             timeline: Timeline {
                 tracks: vec![],
                 markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
             },
+            intro: None,
+            outro: None,
         };
         let json = serde_json::to_string(&project).unwrap();
         let back: Project = serde_json::from_str(&json).unwrap();
@@ -438,6 +1041,9 @@ mod tests {
             timeline_start_us: TimeUs(1_000_000),
             source_in_us: TimeUs(0),
             source_out_us: TimeUs(5_000_000),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         assert_eq!(video.id(), item_id);
@@ -478,6 +1084,9 @@ mod tests {
             source_in_us: TimeUs(1_000_000),
             source_out_us: TimeUs(4_000_000),
             volume: 0.8,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         assert_eq!(audio.id(), item_id);
@@ -512,5 +1121,4 @@ mod tests {
         assert_eq!(img.timeline_end_us(), TimeUs(2_000_000));
         assert_eq!(img.asset_id(), Some(asset_id));
     }
-
 }
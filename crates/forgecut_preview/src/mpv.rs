@@ -1,52 +1,82 @@
 use serde_json::json;
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
 
-pub struct MpvController {
-    process: Option<Child>,
-    socket_path: PathBuf,
-    xlib: Option<x11_dl::xlib::Xlib>,
-    display: Option<*mut x11_dl::xlib::Display>,
-    child_window: Option<u64>,
+/// Properties observed on the persistent event connection as soon as it's
+/// opened, keyed by the `observe_property` id each is registered under
+/// (index + 1, since mpv reserves id 0 for "no id").
+const OBSERVED_PROPERTIES: &[&str] = &["time-pos", "pause", "eof-reached", "duration"];
+
+/// A spontaneous message from mpv's persistent event connection -- either an
+/// observed property changing value, or end-of-file on the current clip.
+/// Dispatched through the channel returned by
+/// [`MpvController::start_events`] so a playhead can stay in sync without
+/// polling [`MpvController::get_position`].
+#[derive(Debug, Clone)]
+pub enum MpvEvent {
+    /// `id` is the `observe_property` id assigned in [`OBSERVED_PROPERTIES`]
+    /// order (1-based), so callers can tell which property changed without
+    /// string-matching `name` on the hot path.
+    PropertyChange {
+        id: u64,
+        name: String,
+        data: serde_json::Value,
+    },
+    EndFile,
 }
 
-// Safety: Only accessed behind Mutex in AppState
-unsafe impl Send for MpvController {}
+type PendingReplies = Arc<Mutex<HashMap<u64, mpsc::Sender<serde_json::Value>>>>;
 
-impl Default for MpvController {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Creates and manages the on-screen surface mpv renders into, abstracting
+/// over how "embedding" works per display protocol: X11 can create a real
+/// child window that mpv takes over via `--wid`, Wayland has no equivalent
+/// embedding API for an arbitrary client, and headless/non-Linux hosts have
+/// no embedding at all. [`MpvController::new`] picks one from the session's
+/// `WAYLAND_DISPLAY`/`DISPLAY` environment so the rest of the IPC/command
+/// surface (`load_file`, `seek`, geometry updates) stays backend-agnostic.
+pub trait PlayerBackend: Send {
+    /// Prepare this backend to embed mpv at `parent`/`(x, y, w, h)` (creating
+    /// a child window, choosing a GPU surface, etc. as the backend needs)
+    /// and return the extra `mpv` CLI args that point it at the result.
+    fn embed(&mut self, parent: u64, x: i32, y: i32, w: u32, h: u32)
+        -> Result<Vec<String>, String>;
+
+    /// Reposition/resize the embedded surface after mpv has started.
+    fn update_geometry(&self, x: i32, y: i32, w: u32, h: u32);
+
+    /// Tear down anything [`embed`](Self::embed) created (windows, displays,
+    /// contexts). Safe to call even if `embed` was never called.
+    fn teardown(&mut self);
 }
 
-impl MpvController {
-    pub fn new() -> Self {
-        let socket_path =
-            std::env::temp_dir().join(format!("forgecut-mpv-{}", std::process::id()));
-        Self {
-            process: None,
-            socket_path,
-            xlib: None,
-            display: None,
-            child_window: None,
-        }
-    }
+/// X11 child-window embedding: the original (and only) approach this
+/// controller used before [`PlayerBackend`] existed. Creates a child window
+/// of `parent` via Xlib and points mpv at it with `--wid`.
+#[derive(Default)]
+struct X11Backend {
+    xlib: Option<x11_dl::xlib::Xlib>,
+    display: Option<*mut x11_dl::xlib::Display>,
+    child_window: Option<u64>,
+}
 
-    /// Start mpv embedded as a child window of the given X11 parent window.
-    /// Creates an X11 child window at (x, y) with size (w, h) inside the parent,
-    /// then starts mpv with --wid pointing to the child window.
-    pub fn start_embedded(
+// Safety: the raw Xlib display pointer is only touched from behind the
+// Mutex<MpvController> in AppState.
+unsafe impl Send for X11Backend {}
+
+impl PlayerBackend for X11Backend {
+    fn embed(
         &mut self,
-        parent_xid: u64,
+        parent: u64,
         x: i32,
         y: i32,
         w: u32,
         h: u32,
-    ) -> Result<(), String> {
-        self.stop();
-
+    ) -> Result<Vec<String>, String> {
         let xlib = x11_dl::xlib::Xlib::open().map_err(|e| format!("Failed to open Xlib: {e}"))?;
 
         let display = unsafe { (xlib.XOpenDisplay)(std::ptr::null()) };
@@ -60,12 +90,12 @@ impl MpvController {
         let child_xid = unsafe {
             (xlib.XCreateSimpleWindow)(
                 display,
-                parent_xid as x11_dl::xlib::Window,
+                parent as x11_dl::xlib::Window,
                 x,
                 y,
                 w,
                 h,
-                0,          // border width
+                0, // border width
                 black_pixel,
                 black_pixel,
             )
@@ -83,22 +113,163 @@ impl MpvController {
         self.display = Some(display);
         self.child_window = Some(child_xid);
 
+        Ok(vec![format!("--wid={child_xid}")])
+    }
+
+    fn update_geometry(&self, x: i32, y: i32, w: u32, h: u32) {
+        if let (Some(ref xlib), Some(display), Some(child_xid)) =
+            (&self.xlib, self.display, self.child_window)
+        {
+            unsafe {
+                (xlib.XMoveResizeWindow)(display, child_xid as x11_dl::xlib::Window, x, y, w, h);
+                (xlib.XFlush)(display);
+            }
+        }
+    }
+
+    fn teardown(&mut self) {
+        if let (Some(ref xlib), Some(display), Some(child_xid)) =
+            (&self.xlib, self.display, self.child_window)
+        {
+            unsafe {
+                (xlib.XDestroyWindow)(display, child_xid as x11_dl::xlib::Window);
+                (xlib.XCloseDisplay)(display);
+            }
+        }
+        self.child_window = None;
+        self.display = None;
+        self.xlib = None;
+    }
+}
+
+/// Wayland fallback: there's no portable protocol for embedding an
+/// arbitrary client's surface into another process's window the way X11's
+/// reparenting trick does, so this points mpv at a Wayland-native GPU
+/// context instead of a `--wid` and lets it open its own top-level window.
+/// `update_geometry`/`teardown` are no-ops -- true embedding needs a
+/// compositor-specific protocol (e.g. `xdg_foreign`) to hand mpv a surface
+/// the caller's window actually owns, which is out of scope here.
+#[derive(Default)]
+struct WaylandBackend;
+
+impl PlayerBackend for WaylandBackend {
+    fn embed(
+        &mut self,
+        _parent: u64,
+        _x: i32,
+        _y: i32,
+        _w: u32,
+        _h: u32,
+    ) -> Result<Vec<String>, String> {
+        Ok(vec!["--gpu-context=wayland".to_string()])
+    }
+
+    fn update_geometry(&self, _x: i32, _y: i32, _w: u32, _h: u32) {}
+
+    fn teardown(&mut self) {}
+}
+
+/// Detached fallback for hosts with no supported embedding protocol (no
+/// `DISPLAY`/`WAYLAND_DISPLAY`, or non-Linux): mpv opens its own,
+/// unembedded window and geometry/teardown are no-ops.
+#[derive(Default)]
+struct DetachedBackend;
+
+impl PlayerBackend for DetachedBackend {
+    fn embed(
+        &mut self,
+        _parent: u64,
+        _x: i32,
+        _y: i32,
+        _w: u32,
+        _h: u32,
+    ) -> Result<Vec<String>, String> {
+        Ok(vec![])
+    }
+
+    fn update_geometry(&self, _x: i32, _y: i32, _w: u32, _h: u32) {}
+
+    fn teardown(&mut self) {}
+}
+
+/// Choose a backend from the session's display environment: Wayland first
+/// (mpv prefers it when both are set), then X11, then detached.
+fn select_backend() -> Box<dyn PlayerBackend> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Box::new(WaylandBackend)
+    } else if std::env::var_os("DISPLAY").is_some() {
+        Box::<X11Backend>::default()
+    } else {
+        Box::new(DetachedBackend)
+    }
+}
+
+pub struct MpvController {
+    process: Option<Child>,
+    socket_path: PathBuf,
+    backend: Box<dyn PlayerBackend>,
+    /// Write half of the persistent connection opened by
+    /// [`start_events`](Self::start_events); `None` until then.
+    event_writer: Option<UnixStream>,
+    next_request_id: AtomicU64,
+    /// Command replies awaited on the event connection, keyed by the
+    /// `request_id` each was sent with. The reader thread pops and fulfills
+    /// these as replies arrive, leaving everything else (spontaneous events,
+    /// which never carry `request_id`) to flow to the caller's channel.
+    pending_replies: PendingReplies,
+}
+
+impl Default for MpvController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MpvController {
+    pub fn new() -> Self {
+        let socket_path = std::env::temp_dir().join(format!("forgecut-mpv-{}", std::process::id()));
+        Self {
+            process: None,
+            socket_path,
+            backend: select_backend(),
+            event_writer: None,
+            next_request_id: AtomicU64::new(0),
+            pending_replies: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start mpv embedded at `(x, y, w, h)` inside `parent`, using whichever
+    /// [`PlayerBackend`] this controller selected for the current session.
+    pub fn start_embedded(
+        &mut self,
+        parent: u64,
+        x: i32,
+        y: i32,
+        w: u32,
+        h: u32,
+    ) -> Result<(), String> {
+        self.stop();
+
+        let backend_args = self.backend.embed(parent, x, y, w, h)?;
+
         let log_path =
             std::env::temp_dir().join(format!("forgecut-mpv-{}.log", std::process::id()));
         let log_file = std::fs::File::create(&log_path).ok();
-        tracing::info!("[mpv] starting embedded in child xid={child_xid}, parent={parent_xid}");
+        tracing::info!("[mpv] starting embedded for parent={parent}, args={backend_args:?}");
         tracing::info!("[mpv] log: {}", log_path.display());
 
+        let mut args = vec![
+            "--idle=yes".to_string(),
+            "--keep-open=yes".to_string(),
+            "--osc=no".to_string(),
+            "--osd-level=0".to_string(),
+            "--no-focus-on-open".to_string(),
+        ];
+        args.extend(backend_args);
+        args.push(format!("--input-ipc-server={}", self.socket_path.display()));
+
         let child = Command::new("mpv")
-            .args([
-                "--idle=yes",
-                "--keep-open=yes",
-                "--osc=no",
-                "--osd-level=0",
-                "--no-focus-on-open",
-                &format!("--wid={child_xid}"),
-                &format!("--input-ipc-server={}", self.socket_path.display()),
-            ])
+            .args(&args)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(log_file.map(Stdio::from).unwrap_or(Stdio::null()))
@@ -117,23 +288,9 @@ impl MpvController {
         Err("mpv socket did not appear".into())
     }
 
-    /// Reposition and resize the X11 child window.
+    /// Reposition and resize the embedded surface, via the active backend.
     pub fn update_geometry(&self, x: i32, y: i32, w: u32, h: u32) {
-        if let (Some(ref xlib), Some(display), Some(child_xid)) =
-            (&self.xlib, self.display, self.child_window)
-        {
-            unsafe {
-                (xlib.XMoveResizeWindow)(
-                    display,
-                    child_xid as x11_dl::xlib::Window,
-                    x,
-                    y,
-                    w,
-                    h,
-                );
-                (xlib.XFlush)(display);
-            }
-        }
+        self.backend.update_geometry(x, y, w, h);
     }
 
     fn send_command(&self, command: serde_json::Value) -> Result<serde_json::Value, String> {
@@ -188,25 +345,97 @@ impl MpvController {
         self.process.is_some()
     }
 
+    /// Open a persistent IPC connection and start observing
+    /// [`OBSERVED_PROPERTIES`], dispatching mpv's asynchronous
+    /// property-change and end-of-file events through the returned channel.
+    ///
+    /// Unlike [`send_command`](Self::send_command), which opens a fresh
+    /// connection per call and reads exactly one reply line, this connection
+    /// stays open for the controller's lifetime so a background reader
+    /// thread can see events mpv pushes unprompted, not just replies to
+    /// requests we made.
+    pub fn start_events(&mut self) -> Result<mpsc::Receiver<MpvEvent>, String> {
+        let read_half = UnixStream::connect(&self.socket_path)
+            .map_err(|e| format!("Failed to connect to mpv: {e}"))?;
+        let write_half = read_half
+            .try_clone()
+            .map_err(|e| format!("Failed to clone socket: {e}"))?;
+
+        let (event_tx, event_rx) = mpsc::channel();
+        let pending_replies = self.pending_replies.clone();
+        let reader = BufReader::new(read_half);
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                let Ok(msg) = serde_json::from_str::<serde_json::Value>(&line) else {
+                    continue;
+                };
+
+                if let Some(event) = msg.get("event").and_then(|e| e.as_str()) {
+                    let dispatched = parse_event(event, &msg);
+                    if let Some(dispatched) = dispatched {
+                        if event_tx.send(dispatched).is_err() {
+                            break;
+                        }
+                    }
+                    continue;
+                }
+
+                if let Some(request_id) = msg.get("request_id").and_then(|r| r.as_u64()) {
+                    if let Some(tx) = pending_replies.lock().unwrap().remove(&request_id) {
+                        let _ = tx.send(msg);
+                    }
+                }
+            }
+        });
+
+        self.event_writer = Some(write_half);
+
+        for (index, name) in OBSERVED_PROPERTIES.iter().enumerate() {
+            let observe_id = index as u64 + 1;
+            self.send_on_event_connection(json!({
+                "command": ["observe_property", observe_id, name],
+            }))?;
+        }
+
+        Ok(event_rx)
+    }
+
+    /// Send a command on the persistent event connection and wait for its
+    /// reply, matched by a unique `request_id` so it can't be confused with
+    /// an event the reader thread sees in the meantime.
+    fn send_on_event_connection(
+        &mut self,
+        mut command: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel();
+        self.pending_replies.lock().unwrap().insert(request_id, tx);
+        command["request_id"] = json!(request_id);
+
+        let writer = self
+            .event_writer
+            .as_mut()
+            .ok_or("event connection not started")?;
+        let msg = format!("{command}\n");
+        if let Err(e) = writer.write_all(msg.as_bytes()) {
+            self.pending_replies.lock().unwrap().remove(&request_id);
+            return Err(format!("Write failed: {e}"));
+        }
+
+        rx.recv_timeout(std::time::Duration::from_secs(2))
+            .map_err(|_| "mpv did not reply on event connection".to_string())
+    }
+
     pub fn stop(&mut self) {
         if let Some(mut child) = self.process.take() {
             let _ = child.kill();
             let _ = child.wait();
         }
+        self.event_writer = None;
+        self.pending_replies.lock().unwrap().clear();
         let _ = std::fs::remove_file(&self.socket_path);
-
-        // Destroy X11 child window and close display
-        if let (Some(ref xlib), Some(display), Some(child_xid)) =
-            (&self.xlib, self.display, self.child_window)
-        {
-            unsafe {
-                (xlib.XDestroyWindow)(display, child_xid as x11_dl::xlib::Window);
-                (xlib.XCloseDisplay)(display);
-            }
-        }
-        self.child_window = None;
-        self.display = None;
-        self.xlib = None;
+        self.backend.teardown();
     }
 }
 
@@ -215,3 +444,19 @@ impl Drop for MpvController {
         self.stop();
     }
 }
+
+/// Turn a raw mpv IPC event message into an [`MpvEvent`], or `None` for
+/// event types we don't observe (e.g. `"seek"`, `"file-loaded"`) or a
+/// malformed `property-change` missing its id/name.
+fn parse_event(event: &str, msg: &serde_json::Value) -> Option<MpvEvent> {
+    match event {
+        "property-change" => {
+            let id = msg.get("id").and_then(|i| i.as_u64())?;
+            let name = msg.get("name").and_then(|n| n.as_str())?.to_string();
+            let data = msg.get("data").cloned().unwrap_or(serde_json::Value::Null);
+            Some(MpvEvent::PropertyChange { id, name, data })
+        }
+        "end-file" => Some(MpvEvent::EndFile),
+        _ => None,
+    }
+}
@@ -0,0 +1,331 @@
+use forgecut_core::types::{Item, TimeUs, Timeline};
+use gst::prelude::*;
+use raw_window_handle::HasWindowHandle;
+
+/// A single clip to decode and composite for the current preview window.
+#[derive(Debug, Clone)]
+pub struct PreviewClip {
+    pub path: std::path::PathBuf,
+    pub source_in_us: TimeUs,
+    pub source_out_us: TimeUs,
+    pub timeline_start_us: TimeUs,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub opacity: f64,
+    pub volume: f64,
+    pub has_video: bool,
+    pub has_audio: bool,
+}
+
+/// Position/playing-state update pushed out while the pipeline runs.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct PlaybackPosition {
+    pub position_us: i64,
+    pub eos: bool,
+}
+
+/// Drives a GStreamer pipeline that composites the clips active at a given
+/// playhead and renders them into the embedded preview window.
+///
+/// Safety: only accessed behind a Mutex in AppState, so the non-Send window
+/// handle captured by the sink element is never touched concurrently.
+pub struct PlaybackController {
+    pipeline: Option<gst::Pipeline>,
+    position_tx: Option<tokio::sync::watch::Sender<PlaybackPosition>>,
+}
+
+unsafe impl Send for PlaybackController {}
+
+impl Default for PlaybackController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackController {
+    pub fn new() -> Self {
+        gst::init().ok();
+        Self {
+            pipeline: None,
+            position_tx: None,
+        }
+    }
+
+    /// Build and start a compositing pipeline for `clips`, rendering into
+    /// the window identified by `window`.
+    /// Returns a receiver that yields position updates as the pipeline plays.
+    pub fn start(
+        &mut self,
+        clips: &[PreviewClip],
+        window: &impl HasWindowHandle,
+    ) -> Result<tokio::sync::watch::Receiver<PlaybackPosition>, String> {
+        self.stop();
+
+        let pipeline = gst::Pipeline::new();
+
+        let compositor = gst::ElementFactory::make("compositor")
+            .build()
+            .map_err(|e| format!("failed to create compositor: {e}"))?;
+        let audiomixer = gst::ElementFactory::make("audiomixer")
+            .build()
+            .map_err(|e| format!("failed to create audiomixer: {e}"))?;
+        let video_sink = gst::ElementFactory::make("autovideosink")
+            .build()
+            .map_err(|e| format!("failed to create video sink: {e}"))?;
+        let audio_sink = gst::ElementFactory::make("autoaudiosink")
+            .build()
+            .map_err(|e| format!("failed to create audio sink: {e}"))?;
+
+        pipeline
+            .add_many([&compositor, &audiomixer, &video_sink, &audio_sink])
+            .map_err(|e| format!("failed to add compositing elements: {e}"))?;
+        compositor
+            .link(&video_sink)
+            .map_err(|e| format!("failed to link compositor to sink: {e}"))?;
+        audiomixer
+            .link(&audio_sink)
+            .map_err(|e| format!("failed to link audiomixer to sink: {e}"))?;
+
+        set_window_handle(&video_sink, window)?;
+
+        for (i, clip) in clips.iter().enumerate() {
+            add_clip(&pipeline, &compositor, &audiomixer, clip, i)?;
+        }
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| format!("failed to start pipeline: {e}"))?;
+
+        let (tx, rx) = tokio::sync::watch::channel(PlaybackPosition::default());
+        self.position_tx = Some(tx);
+        self.pipeline = Some(pipeline);
+        Ok(rx)
+    }
+
+    /// Seek the running pipeline to an absolute playhead position.
+    pub fn seek(&self, position_us: i64) -> Result<(), String> {
+        let pipeline = self.pipeline.as_ref().ok_or("preview is not running")?;
+        pipeline
+            .seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::ClockTime::from_useconds(position_us.max(0) as u64),
+            )
+            .map_err(|e| format!("seek failed: {e}"))
+    }
+
+    /// Poll the pipeline for its current position and publish it to subscribers.
+    pub fn poll_position(&self) {
+        let Some(pipeline) = &self.pipeline else {
+            return;
+        };
+        let Some(tx) = &self.position_tx else {
+            return;
+        };
+        if let Some(position) = pipeline.query_position::<gst::ClockTime>() {
+            let _ = tx.send(PlaybackPosition {
+                position_us: position.useconds() as i64,
+                eos: false,
+            });
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(pipeline) = self.pipeline.take() {
+            let _ = pipeline.set_state(gst::State::Null);
+        }
+        self.position_tx = None;
+    }
+}
+
+impl Drop for PlaybackController {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Point the video sink's window overlay handle at the Tauri window surface.
+fn set_window_handle(
+    video_sink: &gst::Element,
+    window: &impl HasWindowHandle,
+) -> Result<(), String> {
+    use gst_video::prelude::VideoOverlayExtManual;
+
+    let handle = window
+        .window_handle()
+        .map_err(|e| format!("window handle error: {e}"))?;
+    let raw_handle = match handle.as_ref() {
+        raw_window_handle::RawWindowHandle::Xlib(h) => h.window as usize,
+        raw_window_handle::RawWindowHandle::Xcb(h) => h.window.get() as usize,
+        other => return Err(format!("unsupported window handle type: {other:?}")),
+    };
+
+    let overlay = video_sink
+        .dynamic_cast_ref::<gst_video::VideoOverlay>()
+        .ok_or("video sink does not support window overlay")?;
+    unsafe {
+        overlay.set_window_handle(raw_handle);
+    }
+    Ok(())
+}
+
+/// Decode one clip's source file and link its video/audio pads into the
+/// shared compositor/audiomixer once `decodebin` exposes them.
+fn add_clip(
+    pipeline: &gst::Pipeline,
+    compositor: &gst::Element,
+    audiomixer: &gst::Element,
+    clip: &PreviewClip,
+    index: usize,
+) -> Result<(), String> {
+    let src = gst::ElementFactory::make("filesrc")
+        .property("location", clip.path.to_string_lossy().as_ref())
+        .build()
+        .map_err(|e| format!("failed to create filesrc: {e}"))?;
+    let decodebin = gst::ElementFactory::make("decodebin")
+        .name(format!("decode_{index}"))
+        .build()
+        .map_err(|e| format!("failed to create decodebin: {e}"))?;
+
+    pipeline
+        .add_many([&src, &decodebin])
+        .map_err(|e| format!("failed to add clip elements: {e}"))?;
+    src.link(&decodebin)
+        .map_err(|e| format!("failed to link filesrc to decodebin: {e}"))?;
+
+    let compositor = compositor.clone();
+    let audiomixer = audiomixer.clone();
+    let pipeline_weak = pipeline.downgrade();
+    let clip = clip.clone();
+
+    decodebin.connect_pad_added(move |_decodebin, src_pad| {
+        let Some(pipeline) = pipeline_weak.upgrade() else {
+            return;
+        };
+        let Some(caps) = src_pad.current_caps() else {
+            return;
+        };
+        let Some(structure) = caps.structure(0) else {
+            return;
+        };
+        let name = structure.name();
+
+        if name.starts_with("video/") {
+            link_video_branch(&pipeline, &compositor, src_pad, &clip, index);
+        } else if name.starts_with("audio/") {
+            link_audio_branch(&pipeline, &audiomixer, src_pad, &clip);
+        }
+    });
+
+    Ok(())
+}
+
+fn link_video_branch(
+    pipeline: &gst::Pipeline,
+    compositor: &gst::Element,
+    src_pad: &gst::Pad,
+    clip: &PreviewClip,
+    index: usize,
+) {
+    let queue = match gst::ElementFactory::make("queue").build() {
+        Ok(q) => q,
+        Err(_) => return,
+    };
+    let scale = match gst::ElementFactory::make("videoscale").build() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let caps_filter = match gst::ElementFactory::make("capsfilter")
+        .property(
+            "caps",
+            gst::Caps::builder("video/x-raw")
+                .field("width", clip.width as i32)
+                .field("height", clip.height as i32)
+                .build(),
+        )
+        .build()
+    {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    if pipeline.add_many([&queue, &scale, &caps_filter]).is_err() {
+        return;
+    }
+    let _ = gst::Element::link_many([&queue, &scale, &caps_filter]);
+
+    let sink_pad = src_pad.link(&queue.static_pad("sink").unwrap());
+    if sink_pad.is_err() {
+        return;
+    }
+
+    let compositor_pad = compositor.request_pad_simple("sink_%u");
+    if let Some(pad) = compositor_pad {
+        pad.set_property("xpos", clip.x);
+        pad.set_property("ypos", clip.y);
+        pad.set_property("alpha", clip.opacity);
+        let _ = caps_filter.static_pad("src").unwrap().link(&pad);
+    }
+
+    for e in [&queue, &scale, &caps_filter] {
+        let _ = e.sync_state_with_parent();
+    }
+    let _ = index; // reserved for per-clip z-ordering once compositor supports it
+}
+
+fn link_audio_branch(
+    pipeline: &gst::Pipeline,
+    audiomixer: &gst::Element,
+    src_pad: &gst::Pad,
+    clip: &PreviewClip,
+) {
+    let queue = match gst::ElementFactory::make("queue").build() {
+        Ok(q) => q,
+        Err(_) => return,
+    };
+    let convert = match gst::ElementFactory::make("audioconvert").build() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    let volume = match gst::ElementFactory::make("volume")
+        .property("volume", clip.volume)
+        .build()
+    {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    if pipeline.add_many([&queue, &convert, &volume]).is_err() {
+        return;
+    }
+    let _ = gst::Element::link_many([&queue, &convert, &volume]);
+
+    let _ = src_pad.link(&queue.static_pad("sink").unwrap());
+
+    if let Some(pad) = audiomixer.request_pad_simple("sink_%u") {
+        let _ = volume.static_pad("src").unwrap().link(&pad);
+    }
+
+    for e in [&queue, &convert, &volume] {
+        let _ = e.sync_state_with_parent();
+    }
+}
+
+/// Collect every item, across all tracks, that is active at `playhead_us`,
+/// each paired with the playhead's offset from the item's
+/// `timeline_start_us`. The offset is in timeline time: the item's source
+/// in-point and speed are not applied to it.
+pub fn clips_at_playhead(timeline: &Timeline, playhead_us: TimeUs) -> Vec<(Item, TimeUs)> {
+    let mut active = Vec::new();
+    for track in &timeline.tracks {
+        for item in &track.items {
+            let start = item.timeline_start_us();
+            let end = item.timeline_end_us();
+            if playhead_us >= start && playhead_us < end {
+                active.push((item.clone(), TimeUs(playhead_us.0 - start.0)));
+            }
+        }
+    }
+    active
+}
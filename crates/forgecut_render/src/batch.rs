@@ -0,0 +1,186 @@
+//! Fan out independent per-asset ffmpeg jobs (proxy generation, thumbnail
+//! extraction, ...) across a bounded worker pool, reusing the same
+//! work-stealing `std::thread::scope` + atomic index pattern
+//! [`crate::chunked_export::render_to_file`] uses for segment-parallel
+//! rendering. Each job is already a single-asset, CPU-bound ffmpeg process,
+//! so spreading a folder import's jobs across cores gives near-linear
+//! speedup instead of serializing one ffmpeg invocation at a time.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::error::Result;
+use crate::proxy::generate_proxy;
+use crate::thumbnails::extract_thumbnails;
+use crate::waveform::{extract_waveform, WaveformData};
+
+/// Worker pool size for a batch of `job_count` independent jobs: as many as
+/// the machine has cores for, minus one reserved for the caller's own
+/// thread, never more workers than there are jobs, never fewer than one.
+fn determine_batch_workers(job_count: usize) -> usize {
+    let available = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    available.saturating_sub(1).max(1).min(job_count.max(1))
+}
+
+/// Run `job` for every item in `items` across a bounded worker pool sized by
+/// [`determine_batch_workers`], returning each item's result in `items`
+/// order regardless of which worker finishes first or whether a job errors
+/// -- one failure doesn't abort the rest of the batch. `on_progress` is
+/// invoked once per completed job, from whichever worker thread finished
+/// it, with the number of jobs completed so far and the total.
+fn run_batch<T: Sync, R: Send>(
+    items: &[T],
+    on_progress: &(dyn Fn(usize, usize) + Send + Sync),
+    job: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let total = items.len();
+    let next_index = AtomicUsize::new(0);
+    let completed = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+    let worker_count = determine_batch_workers(total);
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                let i = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(i) else {
+                    return;
+                };
+                let result = job(item);
+                results.lock().unwrap()[i] = Some(result);
+                let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                on_progress(done, total);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every index is claimed exactly once by fetch_add"))
+        .collect()
+}
+
+/// Generate a 720p proxy for every `(source_path, asset_id)` pair in
+/// `assets`, fanned out across a bounded worker pool (see
+/// [`determine_batch_workers`]). Returns one [`Result`] per asset, in the
+/// same order as `assets`, so a single failed encode doesn't abort the rest
+/// of the batch. `on_progress` is called as each job completes with
+/// `(completed, total)`.
+pub fn generate_proxies(
+    assets: &[(PathBuf, String)],
+    proxy_dir: &Path,
+    on_progress: impl Fn(usize, usize) + Send + Sync,
+) -> Vec<Result<PathBuf>> {
+    run_batch(assets, &on_progress, |(source_path, asset_id)| {
+        generate_proxy(source_path, proxy_dir, asset_id)
+    })
+}
+
+/// One asset's thumbnail-extraction request for [`generate_thumbnails_batch`]:
+/// its source path, cache key, and duration -- the interval and width are
+/// shared across the whole batch instead.
+pub struct ThumbnailBatchRequest {
+    pub source_path: PathBuf,
+    pub asset_id: String,
+    pub duration_seconds: f64,
+}
+
+/// Extract thumbnails for every asset in `requests` at `interval_seconds`
+/// spacing and `thumb_width`, fanned out the same way as
+/// [`generate_proxies`]. Returns one [`Result`] per asset, in `requests`
+/// order.
+pub fn generate_thumbnails_batch(
+    requests: &[ThumbnailBatchRequest],
+    cache_dir: &Path,
+    interval_seconds: f64,
+    thumb_width: u32,
+    on_progress: impl Fn(usize, usize) + Send + Sync,
+) -> Vec<Result<Vec<(f64, PathBuf)>>> {
+    run_batch(requests, &on_progress, |req| {
+        extract_thumbnails(
+            &req.source_path,
+            cache_dir,
+            &req.asset_id,
+            req.duration_seconds,
+            interval_seconds,
+            thumb_width,
+        )
+    })
+}
+
+/// Extract whole-file waveform peaks for every `(source_path, asset_id)` pair
+/// in `assets` at `samples_per_peak` resolution, fanned out the same way as
+/// [`generate_proxies`]. Returns one [`Result`] per asset, in `assets` order.
+pub fn generate_waveforms_batch(
+    assets: &[(PathBuf, String)],
+    cache_dir: &Path,
+    samples_per_peak: u32,
+    on_progress: impl Fn(usize, usize) + Send + Sync,
+) -> Vec<Result<WaveformData>> {
+    run_batch(assets, &on_progress, |(source_path, asset_id)| {
+        extract_waveform(source_path, cache_dir, asset_id, samples_per_peak)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn determine_batch_workers_reserves_one_core_and_clamps_to_job_count() {
+        assert!(determine_batch_workers(1000) >= 1);
+        assert_eq!(determine_batch_workers(1), 1);
+        assert_eq!(determine_batch_workers(0), 1);
+    }
+
+    #[test]
+    fn run_batch_preserves_result_order_regardless_of_completion_order() {
+        let items: Vec<i32> = (0..20).collect();
+        let on_progress = |_done: usize, _total: usize| {};
+        let results = run_batch(&items, &on_progress, |n| *n * 2);
+        assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn run_batch_collects_every_item_even_when_some_fail() {
+        let items: Vec<i32> = (0..10).collect();
+        let on_progress = |_done: usize, _total: usize| {};
+        let results: Vec<Result<i32, String>> = run_batch(&items, &on_progress, |n| {
+            if n % 3 == 0 {
+                Err(format!("failed on {n}"))
+            } else {
+                Ok(*n)
+            }
+        });
+        assert_eq!(results.len(), 10);
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 4);
+        assert_eq!(results[1], Ok(1));
+        assert_eq!(results[3], Err("failed on 3".to_string()));
+    }
+
+    #[test]
+    fn run_batch_reports_progress_once_per_completed_job() {
+        let items: Vec<i32> = (0..7).collect();
+        let completed_count = AtomicU32::new(0);
+        let on_progress = |_done: usize, total: usize| {
+            assert_eq!(total, 7);
+            completed_count.fetch_add(1, Ordering::SeqCst);
+        };
+        run_batch(&items, &on_progress, |n| *n);
+        assert_eq!(completed_count.load(Ordering::SeqCst), 7);
+    }
+
+    #[test]
+    fn run_batch_handles_empty_input() {
+        let items: Vec<i32> = vec![];
+        let on_progress = |_done: usize, _total: usize| {};
+        let results = run_batch(&items, &on_progress, |n| *n);
+        assert!(results.is_empty());
+    }
+}
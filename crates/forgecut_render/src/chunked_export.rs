@@ -0,0 +1,557 @@
+//! Parallel chunked rendering of a [`Project`] to a single output file,
+//! borrowing Av1an's chunked-parallel model: split the timeline at clip and
+//! marker boundaries, encode each segment on its own worker thread, then
+//! losslessly concatenate the finished segments with ffmpeg's concat
+//! demuxer.
+//!
+//! Segment boundaries come from the same cut points
+//! [`collect_snap_points`](forgecut_core::snapping::collect_snap_points)
+//! already knows about (clip edges and markers), so every segment starts on
+//! a clean cut and concat never has to re-encode across a join.
+//!
+//! Note: each worker still runs [`compile`]'s full per-project filter graph,
+//! trimmed to its segment with output-side `-ss`/`-to` -- true Av1an-style
+//! chunking re-splits the filter graph itself so each worker only decodes
+//! its own segment's frames, which would need `compile` to be segment-aware.
+//! That's future work; this still parallelizes the encode pass across
+//! segments (one ffmpeg process per worker) and keeps cuts seamless.
+
+use crate::error::{RenderError, Result};
+use crate::render::{self, RenderPlan};
+use forgecut_core::snapping::collect_snap_points;
+use forgecut_core::types::*;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Encoder knobs for a chunked render. Target resolution/fps are not here --
+/// they come from the project's own [`ProjectSettings`] via [`compile`].
+#[derive(Debug, Clone)]
+pub struct RenderSettings {
+    pub video_codec: String,
+    pub crf: u32,
+    pub preset: String,
+    pub audio_bitrate: String,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            video_codec: "libx264".to_string(),
+            crf: 20,
+            preset: "medium".to_string(),
+            audio_bitrate: "192k".to_string(),
+        }
+    }
+}
+
+/// A worker's most recently reported progress for one segment, tracked so
+/// per-segment updates can be summed into a single overall
+/// [`render::RenderProgress`].
+#[derive(Debug, Clone, Copy, Default)]
+struct SegmentState {
+    completed_secs: f64,
+    frame: u64,
+    fps: f64,
+}
+
+/// One independently-encoded span of the timeline, cut on a clip or marker
+/// boundary so it can be concatenated losslessly with its neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    index: usize,
+    start_us: TimeUs,
+    end_us: TimeUs,
+}
+
+/// Cut the project's overall duration at every clip edge and marker across
+/// every track, producing ordered, non-overlapping segment windows.
+fn plan_segments(project: &Project) -> Vec<Segment> {
+    // collect_snap_points already walks every track's clip edges and every
+    // timeline marker, which are exactly the cut points we want.
+    let mut cuts = collect_snap_points(&project.timeline, None);
+    cuts.sort();
+    cuts.dedup();
+
+    let total_end_us = project
+        .timeline
+        .tracks
+        .iter()
+        .flat_map(|t| &t.items)
+        .map(|item| item.timeline_end_us())
+        .max()
+        .unwrap_or(TimeUs::ZERO);
+    if cuts.last().copied() != Some(total_end_us) {
+        cuts.push(total_end_us);
+    }
+
+    cuts.windows(2)
+        .enumerate()
+        .filter(|(_, w)| w[1] > w[0])
+        .map(|(index, w)| Segment {
+            index,
+            start_us: w[0],
+            end_us: w[1],
+        })
+        .collect()
+}
+
+/// Worker pool size for a chunked render: as many segments can be encoded at
+/// once as the machine has cores for, but never more workers than there are
+/// segments to hand out. `segment_count` must be at least 1 (callers only
+/// reach this after confirming there's at least one segment to render).
+/// `override_workers` (from [`RenderMode::Parallel`]) takes precedence over
+/// `available_parallelism` when given, still clamped to `segment_count`.
+fn determine_workers(segment_count: usize, override_workers: Option<usize>) -> usize {
+    let requested = override_workers.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    requested.max(1).min(segment_count)
+}
+
+/// Selects how [`render_to_file`] turns a project into an output file.
+#[derive(Debug, Clone, Copy)]
+pub enum RenderMode {
+    /// Encode the whole timeline as a single ffmpeg process, like
+    /// [`render::compile`]/[`render::execute`] but routed through this
+    /// module's synchronous, progress-reporting encode path.
+    Sequential,
+    /// The default: split at clip/marker boundaries (see [`plan_segments`])
+    /// and encode segments concurrently before concatenating them.
+    /// `workers` overrides [`determine_workers`]'s
+    /// `available_parallelism`-based default when given.
+    Parallel { workers: Option<usize> },
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        Self::Parallel { workers: None }
+    }
+}
+
+fn ffmpeg_available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+fn encode_segment(
+    plan: &RenderPlan,
+    segment: &Segment,
+    settings: &RenderSettings,
+    out_path: &Path,
+    mut on_chunk_progress: impl FnMut(f64, u64, f64),
+) -> Result<()> {
+    if !ffmpeg_available() {
+        return Err(RenderError::FfmpegNotFound);
+    }
+
+    let mut args: Vec<String> = vec!["-y".to_string()];
+    for input in &plan.inputs {
+        args.push("-i".to_string());
+        args.push(input.path.to_string_lossy().to_string());
+    }
+    args.push("-filter_complex".to_string());
+    args.push(plan.filter_graph.clone());
+    args.push("-ss".to_string());
+    args.push(segment.start_us.as_seconds().to_string());
+    args.push("-to".to_string());
+    args.push(segment.end_us.as_seconds().to_string());
+    args.push("-c:v".to_string());
+    args.push(settings.video_codec.clone());
+    args.push("-crf".to_string());
+    args.push(settings.crf.to_string());
+    args.push("-preset".to_string());
+    args.push(settings.preset.clone());
+    args.push("-c:a".to_string());
+    args.push("aac".to_string());
+    args.push("-b:a".to_string());
+    args.push(settings.audio_bitrate.clone());
+    args.push(out_path.to_string_lossy().to_string());
+
+    let mut child = Command::new("ffmpeg")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RenderError::Io)?;
+
+    let stderr = child.stderr.take().unwrap();
+    let mut reader = std::io::BufReader::new(stderr);
+    let segment_secs = (segment.end_us - segment.start_us).as_seconds();
+
+    let mut buf = Vec::new();
+    let mut last_frame = 0u64;
+    let mut last_fps = 0.0f64;
+    loop {
+        buf.clear();
+        let n =
+            std::io::BufRead::read_until(&mut reader, b'\r', &mut buf).map_err(RenderError::Io)?;
+        if n == 0 {
+            break;
+        }
+        let chunk = String::from_utf8_lossy(&buf);
+        for line in chunk.split(['\r', '\n']) {
+            if let Some(progress) = render::parse_progress(line.trim(), segment_secs) {
+                let completed_secs = progress.percent / 100.0 * segment_secs;
+                last_frame = progress.frame;
+                last_fps = progress.fps;
+                on_chunk_progress(completed_secs, last_frame, last_fps);
+            }
+        }
+    }
+
+    let status = child.wait().map_err(RenderError::Io)?;
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(format!(
+            "segment {} failed to encode",
+            segment.index
+        )));
+    }
+    on_chunk_progress(segment_secs, last_frame, last_fps);
+    Ok(())
+}
+
+fn concat_segments(segment_paths: &[PathBuf], output_path: &Path) -> Result<()> {
+    let list_path = output_path.with_extension("concat.txt");
+    let list_contents: String = segment_paths
+        .iter()
+        .map(|p| format!("file '{}'\n", p.to_string_lossy()))
+        .collect();
+    std::fs::write(&list_path, list_contents).map_err(RenderError::Io)?;
+
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "concat",
+            "-safe",
+            "0",
+            "-i",
+            &list_path.to_string_lossy(),
+            "-c",
+            "copy",
+            &output_path.to_string_lossy(),
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    let _ = std::fs::remove_file(&list_path);
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed("concat failed".into()));
+    }
+    Ok(())
+}
+
+/// Render `project` to `output_path`. Under [`RenderMode::Parallel`] (the
+/// default), splits the timeline into clip/marker-bounded segments (which
+/// already include any scene cuts that were pushed onto the timeline as
+/// markers -- see [`forgecut_render::scene_detect`]) and encodes up to
+/// [`determine_workers`] of them concurrently before concatenating the
+/// results losslessly; under [`RenderMode::Sequential`], encodes the whole
+/// timeline as one segment. `on_progress` is called with the render's
+/// aggregate progress -- completed seconds summed across every segment's
+/// most recent update -- each time any worker reports new ffmpeg progress.
+/// Intermediate segment files live in a dropped temp dir, so they're
+/// cleaned up whether this returns `Ok` or `Err`.
+///
+/// Not a `Project` method: rendering depends on ffmpeg and lives in this
+/// crate, which `forgecut_core::Project` cannot depend on.
+pub fn render_to_file(
+    project: &Project,
+    output_path: &Path,
+    settings: &RenderSettings,
+    mode: RenderMode,
+    on_progress: impl Fn(render::RenderProgress) + Send + Sync,
+) -> Result<()> {
+    let segments = match mode {
+        RenderMode::Sequential => {
+            let total_end_us = project
+                .timeline
+                .tracks
+                .iter()
+                .flat_map(|t| &t.items)
+                .map(|item| item.timeline_end_us())
+                .max()
+                .unwrap_or(TimeUs::ZERO);
+            if total_end_us == TimeUs::ZERO {
+                vec![]
+            } else {
+                vec![Segment {
+                    index: 0,
+                    start_us: TimeUs::ZERO,
+                    end_us: total_end_us,
+                }]
+            }
+        }
+        RenderMode::Parallel { .. } => plan_segments(project),
+    };
+    if segments.is_empty() {
+        return Err(RenderError::NoClips);
+    }
+
+    let plan = render::compile(project)?;
+    let temp_dir = tempfile::TempDir::new().map_err(RenderError::Io)?;
+    let total = segments.len();
+    let total_secs: f64 = segments
+        .iter()
+        .map(|s| (s.end_us - s.start_us).as_seconds())
+        .sum();
+
+    let next_segment = AtomicUsize::new(0);
+    let first_error: Mutex<Option<RenderError>> = Mutex::new(None);
+    let segment_states: Mutex<Vec<SegmentState>> = Mutex::new(vec![SegmentState::default(); total]);
+    let worker_count = match mode {
+        RenderMode::Sequential => 1,
+        RenderMode::Parallel { workers } => determine_workers(total, workers),
+    };
+    let start = std::time::Instant::now();
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                if first_error.lock().unwrap().is_some() {
+                    return;
+                }
+                let i = next_segment.fetch_add(1, Ordering::SeqCst);
+                let Some(segment) = segments.get(i) else {
+                    return;
+                };
+                let out_path = temp_dir
+                    .path()
+                    .join(format!("segment-{:05}.mp4", segment.index));
+                let result = encode_segment(
+                    &plan,
+                    segment,
+                    settings,
+                    &out_path,
+                    |completed_secs, frame, fps| {
+                        let mut states = segment_states.lock().unwrap();
+                        states[segment.index] = SegmentState {
+                            completed_secs,
+                            frame,
+                            fps,
+                        };
+                        on_progress(aggregate_progress(
+                            &states,
+                            total_secs,
+                            start.elapsed().as_secs_f64(),
+                        ));
+                    },
+                );
+                if let Err(e) = result {
+                    *first_error.lock().unwrap() = Some(e);
+                }
+            });
+        }
+    });
+
+    if let Some(e) = first_error.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    let segment_paths: Vec<PathBuf> = segments
+        .iter()
+        .map(|s| temp_dir.path().join(format!("segment-{:05}.mp4", s.index)))
+        .collect();
+
+    if segment_paths.len() == 1 {
+        // A single segment already covers the whole timeline -- it IS the
+        // output, no lossless concat needed.
+        std::fs::rename(&segment_paths[0], output_path).map_err(RenderError::Io)
+    } else {
+        concat_segments(&segment_paths, output_path)
+    }
+}
+
+/// Sum each segment's most recently reported progress into a single overall
+/// [`render::RenderProgress`]. `fps` is the combined throughput of every
+/// segment currently encoding; `eta_seconds` extrapolates from the overall
+/// rate of completed seconds per wall-clock second elapsed so far.
+fn aggregate_progress(
+    states: &[SegmentState],
+    total_secs: f64,
+    elapsed_secs: f64,
+) -> render::RenderProgress {
+    let completed_secs: f64 = states.iter().map(|s| s.completed_secs).sum();
+    let frame: u64 = states.iter().map(|s| s.frame).sum();
+    let fps: f64 = states.iter().map(|s| s.fps).sum();
+
+    let percent = if total_secs > 0.0 {
+        (completed_secs / total_secs * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+
+    let eta_seconds = if completed_secs > 0.0 && elapsed_secs > 0.0 {
+        let overall_rate = completed_secs / elapsed_secs;
+        if overall_rate > 0.0 {
+            Some((total_secs - completed_secs).max(0.0) / overall_rate)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    render::RenderProgress {
+        percent,
+        frame,
+        fps,
+        speed: String::new(),
+        eta_seconds,
+        total_size: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn make_clip(track_id: Uuid, start_us: i64, end_us: i64) -> Item {
+        Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(start_us),
+            source_in_us: TimeUs::ZERO,
+            source_out_us: TimeUs(end_us - start_us),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        }
+    }
+
+    fn make_project(items: Vec<Item>, markers: Vec<Marker>) -> Project {
+        let track_id = Uuid::new_v4();
+        let mut project = Project::new(
+            "chunked export test",
+            forgecut_core::project::preset_1080p(),
+        );
+        project.timeline.tracks.push(Track {
+            id: track_id,
+            kind: TrackKind::Video,
+            items,
+            transitions: vec![],
+            subtitles: None,
+        });
+        project.timeline.markers = markers;
+        project
+    }
+
+    #[test]
+    fn segments_are_cut_at_clip_boundaries() {
+        let track_id = Uuid::new_v4();
+        let project = make_project(
+            vec![
+                make_clip(track_id, 0, 2_000_000),
+                make_clip(track_id, 2_000_000, 5_000_000),
+            ],
+            vec![],
+        );
+
+        let segments = plan_segments(&project);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start_us, TimeUs::ZERO);
+        assert_eq!(segments[0].end_us, TimeUs(2_000_000));
+        assert_eq!(segments[1].start_us, TimeUs(2_000_000));
+        assert_eq!(segments[1].end_us, TimeUs(5_000_000));
+    }
+
+    #[test]
+    fn segments_are_further_cut_at_markers() {
+        let track_id = Uuid::new_v4();
+        let project = make_project(
+            vec![make_clip(track_id, 0, 4_000_000)],
+            vec![Marker {
+                id: Uuid::new_v4(),
+                time_us: TimeUs(1_500_000),
+                label: "cut".into(),
+            }],
+        );
+
+        let segments = plan_segments(&project);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].end_us, TimeUs(1_500_000));
+        assert_eq!(segments[1].start_us, TimeUs(1_500_000));
+    }
+
+    #[test]
+    fn empty_timeline_yields_no_segments() {
+        let project = make_project(vec![], vec![]);
+        assert!(plan_segments(&project).is_empty());
+    }
+
+    #[test]
+    fn render_to_file_without_clips_errors_before_touching_ffmpeg() {
+        let project = make_project(vec![], vec![]);
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = dir.path().join("out.mp4");
+
+        let result = render_to_file(
+            &project,
+            &out,
+            &RenderSettings::default(),
+            RenderMode::default(),
+            |_| {},
+        );
+        assert!(matches!(result, Err(RenderError::NoClips)));
+    }
+
+    #[test]
+    fn determine_workers_never_exceeds_segment_count() {
+        assert!(determine_workers(1, None) <= 1);
+        assert!(determine_workers(1000, None) >= 1);
+    }
+
+    #[test]
+    fn determine_workers_honors_override_clamped_to_segment_count() {
+        assert_eq!(determine_workers(10, Some(4)), 4);
+        assert_eq!(determine_workers(2, Some(99)), 2);
+        assert_eq!(determine_workers(5, Some(0)), 1);
+    }
+
+    #[test]
+    fn aggregate_progress_sums_completed_seconds_across_segments() {
+        let states = [
+            SegmentState {
+                completed_secs: 3.0,
+                frame: 90,
+                fps: 30.0,
+            },
+            SegmentState {
+                completed_secs: 1.0,
+                frame: 30,
+                fps: 30.0,
+            },
+        ];
+        let progress = aggregate_progress(&states, 8.0, 1.0);
+        assert!((progress.percent - 50.0).abs() < 0.01);
+        assert_eq!(progress.frame, 120);
+        assert!((progress.fps - 60.0).abs() < 0.01);
+        assert!(progress.eta_seconds.is_some());
+    }
+
+    #[test]
+    fn aggregate_progress_clamps_percent_at_one_hundred() {
+        let states = [SegmentState {
+            completed_secs: 10.0,
+            frame: 0,
+            fps: 0.0,
+        }];
+        let progress = aggregate_progress(&states, 5.0, 1.0);
+        assert!((progress.percent - 100.0).abs() < 0.01);
+    }
+}
@@ -24,6 +24,27 @@ pub enum RenderError {
     #[error("asset not found: {0}")]
     AssetNotFound(uuid::Uuid),
 
+    #[error("track not found: {0}")]
+    TrackNotFound(uuid::Uuid),
+
+    #[error("download failed: {0}")]
+    DownloadFailed(String),
+
+    #[error("image processing failed: {0}")]
+    Image(String),
+
+    #[error("media rejected: {reason}")]
+    MediaRejected { reason: String },
+
+    #[error("ffmpeg exceeded timeout of {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("subtitle parse error: {0}")]
+    SubtitleParse(String),
+
+    #[error("VMAF score parse error: {0}")]
+    VmafParse(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
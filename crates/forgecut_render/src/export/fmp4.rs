@@ -0,0 +1,578 @@
+//! Fragmented MP4 (fMP4) segment export: walk a [`Timeline`] and emit an
+//! ISO-BMFF init segment (`ftyp` + `moov`) followed by one `moof`+`mdat`
+//! fragment per clip-bounded span, so a downstream packager can cut the
+//! stream at segment boundaries without re-muxing.
+//!
+//! This module only plans fragment boundaries and assembles the container
+//! boxes around them; it does not encode samples. As with
+//! [`crate::render`] (which shells out to ffmpeg for the actual encode),
+//! callers supply each fragment's already-encoded payload bytes; the init
+//! segment carries no `trak`/`stsd` sample description. One fragment is written as a single `trun`
+//! sample spanning the whole payload -- real per-frame granularity needs
+//! wiring to the encoder's actual sample boundaries, which is out of scope
+//! here.
+//!
+//! [`write_edts`] additionally builds the per-track `edts`/`elst` edit list
+//! that keeps a clip's `source_in_us` trim and (for audio) encoder priming
+//! samples sample-exact in the output, for the caller to splice into `trak`.
+
+use crate::error::{RenderError, Result};
+use forgecut_core::types::*;
+use uuid::Uuid;
+
+/// One output track's export settings.
+#[derive(Debug, Clone)]
+pub struct TrackExportConfig {
+    pub track_id: Uuid,
+    /// 1-based `track_ID` in the output file, referenced by `tfhd`/`trex`.
+    pub track_number: u32,
+    pub timescale: u32,
+}
+
+/// A clip-bounded (and marker-bounded) span of one track, to be emitted as
+/// a single `moof`+`mdat` fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    pub track_id: Uuid,
+    pub item_id: Uuid,
+    /// 1-based, increasing across the whole export; written into `mfhd`.
+    pub sequence_number: u32,
+    /// Base media decode time of this fragment in the track's output
+    /// timescale, derived from its timeline position; written into `tfdt`.
+    pub base_decode_time: u64,
+    /// The portion of `[source_in_us, source_out_us)` this fragment covers.
+    pub source_range_us: (TimeUs, TimeUs),
+}
+
+/// Plan fragment boundaries for `config.track_id` against `timeline`:
+/// one fragment per video/audio clip, further split at any marker that
+/// falls strictly inside it, in timeline order. Only `VideoClip`/`AudioClip`
+/// items are considered -- overlays have no `[source_in_us, source_out_us)`
+/// range to slice.
+pub fn plan_fragments(timeline: &Timeline, config: &TrackExportConfig) -> Result<Vec<Fragment>> {
+    let track = timeline
+        .tracks
+        .iter()
+        .find(|t| t.id == config.track_id)
+        .ok_or(RenderError::TrackNotFound(config.track_id))?;
+
+    let mut fragments = Vec::new();
+    let mut sequence_number = 1u32;
+
+    for item in &track.items {
+        let (source_in_us, source_out_us, speed) = match item {
+            Item::VideoClip {
+                source_in_us,
+                source_out_us,
+                speed,
+                ..
+            }
+            | Item::AudioClip {
+                source_in_us,
+                source_out_us,
+                speed,
+                ..
+            } => (*source_in_us, *source_out_us, *speed),
+            _ => continue,
+        };
+
+        let clip_start_us = item.timeline_start_us();
+        let clip_end_us = item.timeline_end_us();
+
+        let mut cuts = vec![clip_start_us];
+        cuts.extend(
+            timeline
+                .markers
+                .iter()
+                .map(|m| m.time_us)
+                .filter(|t| *t > clip_start_us && *t < clip_end_us),
+        );
+        cuts.sort();
+        cuts.dedup();
+        cuts.push(clip_end_us);
+
+        for window in cuts.windows(2) {
+            let (span_start_us, span_end_us) = (window[0], window[1]);
+
+            let source_span_in_us = source_in_us
+                + TimeUs::from_seconds((span_start_us - clip_start_us).as_seconds() * speed);
+            let source_span_out_us = source_in_us
+                + TimeUs::from_seconds((span_end_us - clip_start_us).as_seconds() * speed);
+            let source_span_out_us = source_span_out_us.min(source_out_us);
+
+            fragments.push(Fragment {
+                track_id: config.track_id,
+                item_id: item.id(),
+                sequence_number,
+                base_decode_time: (span_start_us.as_seconds() * config.timescale as f64).round()
+                    as u64,
+                source_range_us: (source_span_in_us, source_span_out_us),
+            });
+            sequence_number += 1;
+        }
+    }
+
+    Ok(fragments)
+}
+
+fn write_box(fourcc: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + payload.len());
+    out.extend_from_slice(&((8 + payload.len()) as u32).to_be_bytes());
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// The `ftyp` box identifying this as a fragmented-MP4-capable file.
+pub fn write_ftyp() -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(b"isom");
+    payload.extend_from_slice(&512u32.to_be_bytes());
+    for brand in [b"isom", b"iso5", b"iso6", b"mp41"] {
+        payload.extend_from_slice(brand);
+    }
+    write_box(b"ftyp", &payload)
+}
+
+/// The init segment's `trex` default-sample-flags entry for one track,
+/// nested under `moov/mvex`.
+fn write_trex(track: &TrackExportConfig) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&0u32.to_be_bytes()); // version + flags
+    payload.extend_from_slice(&track.track_number.to_be_bytes());
+    payload.extend_from_slice(&1u32.to_be_bytes()); // default_sample_description_index
+    payload.extend_from_slice(&0u32.to_be_bytes()); // default_sample_duration
+    payload.extend_from_slice(&0u32.to_be_bytes()); // default_sample_size
+    payload.extend_from_slice(&0u32.to_be_bytes()); // default_sample_flags
+    write_box(b"trex", &payload)
+}
+
+/// The init segment: `ftyp` + `moov` with one `mvex/trex` per track. The
+/// `moov` holds only `mvex`: no `mvhd` and no per-track `trak` (and so no
+/// codec-specific `stsd`) are written.
+pub fn write_init_segment(tracks: &[TrackExportConfig]) -> Vec<u8> {
+    let mut mvex_payload = Vec::new();
+    for track in tracks {
+        mvex_payload.extend(write_trex(track));
+    }
+    let mvex = write_box(b"mvex", &mvex_payload);
+
+    let mut moov_payload = Vec::new();
+    moov_payload.extend(mvex);
+    let moov = write_box(b"moov", &moov_payload);
+
+    let mut out = write_ftyp();
+    out.extend(moov);
+    out
+}
+
+/// `trun` flags: data-offset-present | sample-duration-present | sample-size-present.
+const TRUN_FLAGS: u32 = 0x0000_0301;
+
+/// One `moof`+`mdat` fragment, with `tfdt` set from [`Fragment::base_decode_time`]
+/// and a single `trun` sample spanning `sample_data` in its entirety.
+pub fn write_moof_mdat(
+    fragment: &Fragment,
+    config: &TrackExportConfig,
+    sample_data: &[u8],
+) -> Vec<u8> {
+    let mut mfhd_payload = Vec::new();
+    mfhd_payload.extend_from_slice(&0u32.to_be_bytes());
+    mfhd_payload.extend_from_slice(&fragment.sequence_number.to_be_bytes());
+    let mfhd = write_box(b"mfhd", &mfhd_payload);
+
+    let mut tfhd_payload = Vec::new();
+    tfhd_payload.extend_from_slice(&0u32.to_be_bytes()); // version + flags
+    tfhd_payload.extend_from_slice(&config.track_number.to_be_bytes());
+    let tfhd = write_box(b"tfhd", &tfhd_payload);
+
+    let mut tfdt_payload = Vec::new();
+    tfdt_payload.extend_from_slice(&0x0100_0000u32.to_be_bytes()); // version 1: 64-bit time
+    tfdt_payload.extend_from_slice(&fragment.base_decode_time.to_be_bytes());
+    let tfdt = write_box(b"tfdt", &tfdt_payload);
+
+    let duration_us = fragment.source_range_us.1 - fragment.source_range_us.0;
+    let sample_duration = (duration_us.as_seconds() * config.timescale as f64).round() as u32;
+
+    let mut trun_payload = Vec::new();
+    trun_payload.extend_from_slice(&TRUN_FLAGS.to_be_bytes());
+    trun_payload.extend_from_slice(&1u32.to_be_bytes()); // sample_count
+    let data_offset_in_payload = trun_payload.len();
+    trun_payload.extend_from_slice(&0i32.to_be_bytes()); // data_offset, patched below
+    trun_payload.extend_from_slice(&sample_duration.to_be_bytes());
+    trun_payload.extend_from_slice(&(sample_data.len() as u32).to_be_bytes());
+    let trun = write_box(b"trun", &trun_payload);
+
+    let mut traf_payload = Vec::new();
+    traf_payload.extend_from_slice(&tfhd);
+    traf_payload.extend_from_slice(&tfdt);
+    let trun_offset_in_traf_payload = traf_payload.len();
+    traf_payload.extend_from_slice(&trun);
+    let traf = write_box(b"traf", &traf_payload);
+
+    let mut moof_payload = Vec::new();
+    moof_payload.extend_from_slice(&mfhd);
+    let traf_offset_in_moof_payload = moof_payload.len();
+    moof_payload.extend_from_slice(&traf);
+    let mut moof = write_box(b"moof", &moof_payload);
+
+    // Absolute offset, from the start of moof, of the data_offset field
+    // inside trun: moof header + traf's offset + traf header + trun's
+    // offset + trun header + the field's offset within trun's payload.
+    let data_offset_field_at = 8
+        + traf_offset_in_moof_payload
+        + 8
+        + trun_offset_in_traf_payload
+        + 8
+        + data_offset_in_payload;
+
+    // data_offset is relative to the start of moof; mdat's own 8-byte
+    // header follows moof immediately, so the sample data starts there.
+    let data_offset = (moof.len() + 8) as i32;
+    moof[data_offset_field_at..data_offset_field_at + 4]
+        .copy_from_slice(&data_offset.to_be_bytes());
+
+    let mut out = moof;
+    out.extend(write_box(b"mdat", sample_data));
+    out
+}
+
+/// One entry of an `elst` (edit list) box, in microseconds. `media_time_us`
+/// of `-1` marks an "empty edit" -- skip `segment_duration_us` of decoder
+/// output without consuming any media time, which is how
+/// [`audio_priming_entries`] discards encoder priming samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditListEntry {
+    pub segment_duration_us: TimeUs,
+    pub media_time_us: i64,
+}
+
+/// The edit-list entry for a trimmed clip: media starts at `source_in_us`
+/// into the decoded stream and occupies `timeline_end_us - timeline_start_us`
+/// of the presentation timeline. This is exactly what removes ForgeCut's
+/// microsecond-accurate `source_in_us` trim from the delivered file, the
+/// same way muxers use edit lists to represent a non-zero start time
+/// without re-encoding.
+pub fn clip_edit_list_entry(
+    source_in_us: TimeUs,
+    timeline_start_us: TimeUs,
+    timeline_end_us: TimeUs,
+) -> EditListEntry {
+    EditListEntry {
+        segment_duration_us: timeline_end_us - timeline_start_us,
+        media_time_us: source_in_us.0,
+    }
+}
+
+/// Prefix an audio track's edit list with an empty edit that skips
+/// `priming_samples` of encoder priming/padding at `sample_rate`, so the
+/// decoder's initial padding samples are skipped rather than played instead
+/// of being left for the player to mix in as audible silence/ringing.
+/// A no-op (returns `clip_entry` unchanged) when there's nothing to prime.
+pub fn audio_priming_entries(
+    priming_samples: u32,
+    sample_rate: u32,
+    clip_entry: EditListEntry,
+) -> Vec<EditListEntry> {
+    if priming_samples == 0 || sample_rate == 0 {
+        return vec![clip_entry];
+    }
+    let priming_duration_us = TimeUs::from_seconds(priming_samples as f64 / sample_rate as f64);
+    vec![
+        EditListEntry {
+            segment_duration_us: priming_duration_us,
+            media_time_us: -1,
+        },
+        clip_entry,
+    ]
+}
+
+fn write_elst(entries: &[EditListEntry], timescale: u32) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&0x0100_0000u32.to_be_bytes()); // version 1 (64-bit fields), flags 0
+    payload.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+    for entry in entries {
+        let segment_duration =
+            (entry.segment_duration_us.as_seconds() * timescale as f64).round() as u64;
+        payload.extend_from_slice(&segment_duration.to_be_bytes());
+
+        let media_time: i64 = if entry.media_time_us < 0 {
+            -1
+        } else {
+            (TimeUs(entry.media_time_us).as_seconds() * timescale as f64).round() as i64
+        };
+        payload.extend_from_slice(&media_time.to_be_bytes());
+        payload.extend_from_slice(&1i16.to_be_bytes()); // media_rate_integer
+        payload.extend_from_slice(&0i16.to_be_bytes()); // media_rate_fraction
+    }
+    write_box(b"elst", &payload)
+}
+
+/// The `edts` box wrapping `elst`, to be spliced into a track's `trak` box
+/// by the caller -- this module only assembles fragment-level boxes and
+/// doesn't own `trak`/`moov` track assembly (see the module docs).
+pub fn write_edts(entries: &[EditListEntry], timescale: u32) -> Vec<u8> {
+    let elst = write_elst(entries, timescale);
+    write_box(b"edts", &elst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_clip(track_id: Uuid, start_us: i64, source_in_us: i64, source_out_us: i64) -> Item {
+        Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs(start_us),
+            source_in_us: TimeUs(source_in_us),
+            source_out_us: TimeUs(source_out_us),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        }
+    }
+
+    fn make_timeline(track_id: Uuid, items: Vec<Item>, markers: Vec<Marker>) -> Timeline {
+        Timeline {
+            tracks: vec![Track {
+                id: track_id,
+                kind: TrackKind::Video,
+                items,
+                transitions: vec![],
+                subtitles: None,
+            }],
+            markers,
+            config: TimelineConfig::default(),
+            groups: vec![],
+        }
+    }
+
+    #[test]
+    fn one_fragment_per_clip_without_markers() {
+        let track_id = Uuid::new_v4();
+        let timeline = make_timeline(
+            track_id,
+            vec![
+                make_clip(track_id, 0, 0, 2_000_000),
+                make_clip(track_id, 2_000_000, 0, 1_000_000),
+            ],
+            vec![],
+        );
+        let config = TrackExportConfig {
+            track_id,
+            track_number: 1,
+            timescale: 1000,
+        };
+
+        let fragments = plan_fragments(&timeline, &config).unwrap();
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0].sequence_number, 1);
+        assert_eq!(fragments[1].sequence_number, 2);
+        assert_eq!(fragments[0].base_decode_time, 0);
+        assert_eq!(fragments[1].base_decode_time, 2000);
+    }
+
+    #[test]
+    fn marker_inside_a_clip_splits_it_into_two_fragments() {
+        let track_id = Uuid::new_v4();
+        let timeline = make_timeline(
+            track_id,
+            vec![make_clip(track_id, 0, 0, 4_000_000)],
+            vec![Marker {
+                id: Uuid::new_v4(),
+                time_us: TimeUs(1_500_000),
+                label: "cut here".into(),
+            }],
+        );
+        let config = TrackExportConfig {
+            track_id,
+            track_number: 1,
+            timescale: 1000,
+        };
+
+        let fragments = plan_fragments(&timeline, &config).unwrap();
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(
+            fragments[0].source_range_us,
+            (TimeUs::ZERO, TimeUs(1_500_000))
+        );
+        assert_eq!(
+            fragments[1].source_range_us,
+            (TimeUs(1_500_000), TimeUs(4_000_000))
+        );
+        assert_eq!(fragments[1].base_decode_time, 1500);
+    }
+
+    #[test]
+    fn marker_outside_clip_bounds_is_ignored() {
+        let track_id = Uuid::new_v4();
+        let timeline = make_timeline(
+            track_id,
+            vec![make_clip(track_id, 0, 0, 2_000_000)],
+            vec![Marker {
+                id: Uuid::new_v4(),
+                time_us: TimeUs(5_000_000),
+                label: "later".into(),
+            }],
+        );
+        let config = TrackExportConfig {
+            track_id,
+            track_number: 1,
+            timescale: 1000,
+        };
+
+        let fragments = plan_fragments(&timeline, &config).unwrap();
+        assert_eq!(fragments.len(), 1);
+    }
+
+    #[test]
+    fn overlay_items_are_not_fragmented() {
+        let track_id = Uuid::new_v4();
+        let overlay = Item::TextOverlay {
+            id: Uuid::new_v4(),
+            track_id,
+            timeline_start_us: TimeUs::ZERO,
+            duration_us: TimeUs(1_000_000),
+            text: "hi".into(),
+            font_size: 32,
+            color: "#fff".into(),
+            x: 0,
+            y: 0,
+        };
+        let timeline = make_timeline(track_id, vec![overlay], vec![]);
+        let config = TrackExportConfig {
+            track_id,
+            track_number: 1,
+            timescale: 1000,
+        };
+
+        let fragments = plan_fragments(&timeline, &config).unwrap();
+        assert!(fragments.is_empty());
+    }
+
+    #[test]
+    fn unknown_track_errors() {
+        let track_id = Uuid::new_v4();
+        let timeline = make_timeline(track_id, vec![], vec![]);
+        let config = TrackExportConfig {
+            track_id: Uuid::new_v4(),
+            track_number: 1,
+            timescale: 1000,
+        };
+
+        assert!(plan_fragments(&timeline, &config).is_err());
+    }
+
+    #[test]
+    fn ftyp_box_has_a_valid_size_header() {
+        let ftyp = write_ftyp();
+        let size = u32::from_be_bytes(ftyp[0..4].try_into().unwrap());
+        assert_eq!(size as usize, ftyp.len());
+        assert_eq!(&ftyp[4..8], b"ftyp");
+    }
+
+    #[test]
+    fn init_segment_contains_one_trex_per_track() {
+        let tracks = vec![
+            TrackExportConfig {
+                track_id: Uuid::new_v4(),
+                track_number: 1,
+                timescale: 1000,
+            },
+            TrackExportConfig {
+                track_id: Uuid::new_v4(),
+                track_number: 2,
+                timescale: 48000,
+            },
+        ];
+        let init = write_init_segment(&tracks);
+        let occurrences = init.windows(4).filter(|w| *w == b"trex").count();
+        assert_eq!(occurrences, 2);
+    }
+
+    #[test]
+    fn moof_mdat_trun_data_offset_points_at_mdat_payload() {
+        let config = TrackExportConfig {
+            track_id: Uuid::new_v4(),
+            track_number: 1,
+            timescale: 1000,
+        };
+        let fragment = Fragment {
+            track_id: config.track_id,
+            item_id: Uuid::new_v4(),
+            sequence_number: 1,
+            base_decode_time: 0,
+            source_range_us: (TimeUs::ZERO, TimeUs(1_000_000)),
+        };
+        let sample_data = b"fake-encoded-sample-bytes";
+
+        let out = write_moof_mdat(&fragment, &config, sample_data);
+
+        let moof_size = u32::from_be_bytes(out[0..4].try_into().unwrap()) as usize;
+        assert_eq!(&out[4..8], b"moof");
+        assert_eq!(&out[moof_size + 4..moof_size + 8], b"mdat");
+
+        let trun_offset_pos = out
+            .windows(4)
+            .position(|w| w == b"trun")
+            .unwrap()
+            + 4 // fourcc
+            + 4 // version+flags
+            + 4; // sample_count
+        let data_offset = i32::from_be_bytes(
+            out[trun_offset_pos..trun_offset_pos + 4]
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(
+            out[data_offset as usize..data_offset as usize + sample_data.len()],
+            *sample_data
+        );
+    }
+
+    #[test]
+    fn clip_edit_list_entry_uses_source_in_as_media_time() {
+        let entry = clip_edit_list_entry(TimeUs(500_000), TimeUs(1_000_000), TimeUs(3_000_000));
+        assert_eq!(entry.media_time_us, 500_000);
+        assert_eq!(entry.segment_duration_us, TimeUs(2_000_000));
+    }
+
+    #[test]
+    fn audio_priming_entries_without_priming_is_a_single_entry() {
+        let clip_entry = clip_edit_list_entry(TimeUs::ZERO, TimeUs::ZERO, TimeUs(1_000_000));
+        let entries = audio_priming_entries(0, 48000, clip_entry);
+        assert_eq!(entries, vec![clip_entry]);
+    }
+
+    #[test]
+    fn audio_priming_entries_prefixes_an_empty_edit() {
+        let clip_entry = clip_edit_list_entry(TimeUs::ZERO, TimeUs::ZERO, TimeUs(1_000_000));
+        let entries = audio_priming_entries(1024, 48000, clip_entry);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].media_time_us, -1);
+        // 1024 samples @ 48kHz ~= 21_333us
+        assert_eq!(entries[0].segment_duration_us, TimeUs(21_333));
+        assert_eq!(entries[1], clip_entry);
+    }
+
+    #[test]
+    fn elst_box_roundtrips_entry_count_and_empty_edit_media_time() {
+        let clip_entry = clip_edit_list_entry(TimeUs(500_000), TimeUs::ZERO, TimeUs(2_000_000));
+        let entries = audio_priming_entries(1024, 48000, clip_entry);
+
+        let edts = write_edts(&entries, 48000);
+        assert_eq!(&edts[4..8], b"edts");
+        assert_eq!(&edts[12..16], b"elst");
+
+        let version_flags = u32::from_be_bytes(edts[16..20].try_into().unwrap());
+        assert_eq!(version_flags, 0x0100_0000);
+        let entry_count = u32::from_be_bytes(edts[20..24].try_into().unwrap());
+        assert_eq!(entry_count, 2);
+
+        // First (empty-edit) entry: 8-byte duration, then 8-byte media_time == -1.
+        let first_media_time = i64::from_be_bytes(edts[32..40].try_into().unwrap());
+        assert_eq!(first_media_time, -1);
+    }
+}
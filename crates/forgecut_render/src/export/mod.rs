@@ -0,0 +1,4 @@
+//! Streaming-friendly timeline export formats, as opposed to the single
+//! flat-file ffmpeg pipeline in [`crate::render`].
+
+pub mod fmp4;
@@ -1,6 +1,9 @@
-use forgecut_core::types::{Asset, AssetKind, ProbeResult, TimeUs};
+use forgecut_core::types::{
+    Asset, AssetKind, ColorInfo, FrameRate, MediaTags, Metadata, ProbeResult, StreamInfo,
+    StreamTags, TimeUs,
+};
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 use crate::error::{RenderError, Result};
@@ -17,6 +20,8 @@ struct FfprobeOutput {
 
 #[derive(Debug, Deserialize)]
 struct FfprobeStream {
+    #[serde(default)]
+    index: u32,
     codec_type: String,
     codec_name: Option<String>,
     width: Option<u32>,
@@ -24,24 +29,448 @@ struct FfprobeStream {
     r_frame_rate: Option<String>,
     channels: Option<u32>,
     sample_rate: Option<String>,
+    nb_frames: Option<String>,
+    pix_fmt: Option<String>,
+    bits_per_raw_sample: Option<String>,
+    color_space: Option<String>,
+    color_transfer: Option<String>,
+    color_primaries: Option<String>,
+    bit_rate: Option<String>,
+    duration: Option<String>,
+    #[serde(default)]
+    disposition: FfprobeDisposition,
+    #[serde(default)]
+    side_data_list: Vec<FfprobeSideData>,
+    #[serde(default)]
+    tags: FfprobeStreamTags,
 }
 
-#[derive(Debug, Deserialize)]
+/// Flags ffprobe reports about how a stream is meant to be used. Only the
+/// one flag we act on is modeled; the rest (`default`, `forced`, etc.) are
+/// ignored by serde.
+#[derive(Debug, Default, Deserialize)]
+struct FfprobeDisposition {
+    #[serde(default)]
+    attached_pic: u32,
+}
+
+/// One entry of a stream's `side_data_list`. Only the "Display Matrix"
+/// entry's `rotation` field is modeled; other side data kinds are ignored.
+#[derive(Debug, Default, Deserialize)]
+struct FfprobeSideData {
+    rotation: Option<f64>,
+}
+
+/// Stream-level tags, as distinct from the container-level tags in
+/// `FfprobeFormat`. `rotate` is the legacy way players/encoders signal
+/// display rotation before side-data display matrices were standard.
+#[derive(Debug, Default, Deserialize)]
+struct FfprobeStreamTags {
+    rotate: Option<String>,
+    language: Option<String>,
+    title: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
 struct FfprobeFormat {
     duration: Option<String>,
+    #[serde(default)]
+    tags: FfprobeTags,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FfprobeTags {
+    title: Option<String>,
+    artist: Option<String>,
+    date: Option<String>,
+    encoder: Option<String>,
+    creation_time: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// MediaHandler registry
+// ---------------------------------------------------------------------------
+
+/// A format-specific probing strategy. Implementations are tried in order by
+/// [`import_asset`]; the first one whose `can_handle` accepts the path wins.
+trait MediaHandler {
+    /// Whether this handler recognizes files at `path`, based on extension.
+    fn can_handle(&self, path: &Path) -> bool;
+    /// Probe technical stream details (dimensions, fps, codec, keyframes, ...).
+    fn probe(&self, path: &Path) -> Result<ProbeResult>;
+    /// Extract container-level tags (title, artist, date), if present.
+    fn extract_tags(&self, path: &Path) -> Result<MediaTags>;
+}
+
+/// MP4/MOV-family containers: ffprobe for stream details plus an `stss`-based
+/// keyframe index for accurate trim snapping.
+struct Mp4Handler;
+
+impl MediaHandler for Mp4Handler {
+    fn can_handle(&self, path: &Path) -> bool {
+        matches!(extension(path).as_str(), "mp4" | "mov" | "m4v")
+    }
+
+    fn probe(&self, path: &Path) -> Result<ProbeResult> {
+        let mut result = probe_asset(path)?;
+        result.keyframes_us = read_keyframes_us(path)?;
+        Ok(result)
+    }
+
+    fn extract_tags(&self, path: &Path) -> Result<MediaTags> {
+        Ok(parse_tags(&run_ffprobe(path)?))
+    }
+}
+
+/// Plain audio containers: ffprobe for stream details, no keyframe index.
+struct AudioHandler;
+
+impl MediaHandler for AudioHandler {
+    fn can_handle(&self, path: &Path) -> bool {
+        matches!(
+            extension(path).as_str(),
+            "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" | "wma"
+        )
+    }
+
+    fn probe(&self, path: &Path) -> Result<ProbeResult> {
+        parse_probe_output(&run_ffprobe(path)?)
+    }
+
+    fn extract_tags(&self, path: &Path) -> Result<MediaTags> {
+        Ok(parse_tags(&run_ffprobe(path)?))
+    }
+}
+
+/// Still images: ffprobe reports a single video-like stream with dimensions
+/// but no meaningful fps/keyframes.
+struct ImageHandler;
+
+impl MediaHandler for ImageHandler {
+    fn can_handle(&self, path: &Path) -> bool {
+        matches!(
+            extension(path).as_str(),
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "tiff" | "svg"
+        )
+    }
+
+    fn probe(&self, path: &Path) -> Result<ProbeResult> {
+        parse_probe_output(&run_ffprobe(path)?)
+    }
+
+    fn extract_tags(&self, path: &Path) -> Result<MediaTags> {
+        Ok(parse_tags(&run_ffprobe(path)?))
+    }
+}
+
+/// Catch-all for unrecognized extensions: probe with ffprobe the same way
+/// the other handlers do and let [`detect_asset_kind`] infer the kind from
+/// the resulting stream data.
+struct GenericHandler;
+
+impl MediaHandler for GenericHandler {
+    fn can_handle(&self, _path: &Path) -> bool {
+        true
+    }
+
+    fn probe(&self, path: &Path) -> Result<ProbeResult> {
+        parse_probe_output(&run_ffprobe(path)?)
+    }
+
+    fn extract_tags(&self, path: &Path) -> Result<MediaTags> {
+        Ok(parse_tags(&run_ffprobe(path)?))
+    }
+}
+
+/// Registered handlers in dispatch order. `GenericHandler` is last and always
+/// matches, so every path resolves to some handler.
+fn registered_handlers() -> Vec<Box<dyn MediaHandler>> {
+    vec![
+        Box::new(Mp4Handler),
+        Box::new(AudioHandler),
+        Box::new(ImageHandler),
+        Box::new(GenericHandler),
+    ]
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
 }
 
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
-/// Run ffprobe on a media file and parse the result into a `ProbeResult`.
+/// Which backend produced a `ProbeResult`. Callers that don't care can use
+/// [`probe_asset`]; this is surfaced for diagnostics and tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeBackend {
+    /// Shelled out to the `ffprobe` binary.
+    Ffprobe,
+    /// Parsed the container's ISO-BMFF box tree directly, without ffprobe.
+    Native,
+}
+
+/// Probe a media file, preferring `ffprobe` and falling back to a pure-Rust
+/// ISO-BMFF (mp4/mov/m4a) parser when `ffprobe` is unavailable or fails.
+/// Returns the backend that actually produced the result; [`probe_asset`]
+/// below is the common entry point for callers that only want the result.
+pub fn probe_asset_with_backend(path: impl AsRef<Path>) -> Result<(ProbeResult, ProbeBackend)> {
+    let path = path.as_ref();
+    match run_ffprobe(path).and_then(|output| parse_probe_output(&output)) {
+        Ok(result) => Ok((result, ProbeBackend::Ffprobe)),
+        Err(ffprobe_err) => match probe_iso_bmff(path) {
+            Ok(result) => Ok((result, ProbeBackend::Native)),
+            Err(_) => Err(ffprobe_err),
+        },
+    }
+}
+
+/// Run ffprobe on a media file and parse the result into a `ProbeResult`,
+/// falling back to the native ISO-BMFF parser if ffprobe can't be used.
 pub fn probe_asset(path: impl AsRef<Path>) -> Result<ProbeResult> {
+    probe_asset_with_backend(path).map(|(result, _)| result)
+}
+
+/// Limits enforced against a candidate asset before it's accepted, so a
+/// render service can't be handed a pathological input (a 16K frame, a
+/// 100k-frame GIF, a multi-gigabyte file) that would exhaust memory during
+/// decode. Each limit is `None` by default, meaning unrestricted.
+#[derive(Debug, Clone, Default)]
+pub struct ImportLimits {
+    /// Maximum `width * height` pixel area.
+    pub max_pixel_area: Option<u64>,
+    /// Maximum `frame_count` (animated images and videos that report one).
+    pub max_frame_count: Option<u64>,
+    /// Maximum `duration_us`.
+    pub max_duration_us: Option<TimeUs>,
+    /// Maximum file size in bytes.
+    pub max_file_size_bytes: Option<u64>,
+    /// Codecs accepted, matched against `ProbeResult::codec`. Any codec is
+    /// accepted when this is `None`.
+    pub allowed_codecs: Option<Vec<String>>,
+    /// Container extensions accepted (lowercase, no leading dot). Any
+    /// extension is accepted when this is `None`.
+    pub allowed_extensions: Option<Vec<String>>,
+}
+
+/// Import an asset with no limits enforced, matching the behavior of every
+/// existing caller. Prefer [`import_asset_with_limits`] for anything that
+/// accepts files from an untrusted source.
+pub fn import_asset(path: impl AsRef<Path>) -> Result<Asset> {
+    import_asset_with_limits(path, &ImportLimits::default())
+}
+
+/// Import an asset, rejecting it with `RenderError::MediaRejected` if the
+/// probed stream data or file size violates `limits`.
+pub fn import_asset_with_limits(path: impl AsRef<Path>, limits: &ImportLimits) -> Result<Asset> {
     let path = path.as_ref();
     if !path.exists() {
         return Err(RenderError::FileNotFound(path.to_path_buf()));
     }
 
+    if let Some(max) = limits.max_file_size_bytes {
+        let file_size = std::fs::metadata(path)?.len();
+        if file_size > max {
+            return Err(RenderError::MediaRejected {
+                reason: format!("file size {file_size} bytes exceeds limit of {max} bytes"),
+            });
+        }
+    }
+
+    if let Some(allowed) = &limits.allowed_extensions {
+        let ext = extension(path);
+        if !allowed.iter().any(|e| e == &ext) {
+            return Err(RenderError::MediaRejected {
+                reason: format!("extension \"{ext}\" is not in the allowed list"),
+            });
+        }
+    }
+
+    let handler = registered_handlers()
+        .into_iter()
+        .find(|h| h.can_handle(path))
+        .expect("GenericHandler always matches");
+
+    let probe = handler.probe(path)?;
+    let tags = handler.extract_tags(path)?;
+    let kind = detect_asset_kind(path, &probe);
+
+    if let Some(allowed) = &limits.allowed_codecs {
+        if !allowed.iter().any(|c| c == &probe.codec) {
+            return Err(RenderError::MediaRejected {
+                reason: format!("codec \"{}\" is not in the allowed list", probe.codec),
+            });
+        }
+    }
+
+    if let Some(max) = limits.max_pixel_area {
+        let pixel_area = probe.width as u64 * probe.height as u64;
+        if pixel_area > max {
+            return Err(RenderError::MediaRejected {
+                reason: format!("pixel area {pixel_area} exceeds limit of {max}"),
+            });
+        }
+    }
+
+    if let (Some(max), Some(frames)) = (limits.max_frame_count, probe.frame_count) {
+        if frames > max {
+            return Err(RenderError::MediaRejected {
+                reason: format!("frame count {frames} exceeds limit of {max}"),
+            });
+        }
+    }
+
+    if let Some(max) = limits.max_duration_us {
+        if probe.duration_us > max {
+            return Err(RenderError::MediaRejected {
+                reason: format!(
+                    "duration {} exceeds limit of {}",
+                    probe.duration_us, max
+                ),
+            });
+        }
+    }
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(Asset {
+        id: Uuid::new_v4(),
+        name,
+        path: path.to_path_buf(),
+        kind,
+        probe: Some(probe),
+        tags,
+        source_url: None,
+    })
+}
+
+/// Hosts handled via `yt-dlp` rather than treated as a direct media file URL.
+const VIDEO_PLATFORM_HOSTS: &[&str] = &[
+    "youtube.com",
+    "www.youtube.com",
+    "m.youtube.com",
+    "youtu.be",
+    "vimeo.com",
+    "www.vimeo.com",
+    "twitter.com",
+    "x.com",
+    "tiktok.com",
+    "www.tiktok.com",
+];
+
+/// Whether `url` points at a video-hosting platform (as opposed to a direct
+/// media file URL) and should therefore be fetched via `yt-dlp`.
+fn is_video_platform_url(url: &str) -> bool {
+    url.split("://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .map(|host| VIDEO_PLATFORM_HOSTS.contains(&host))
+        .unwrap_or(false)
+}
+
+/// Derive a short, stable cache key from a URL so repeated imports of the
+/// same URL reuse the same cached file instead of downloading it again.
+fn cache_key_for_url(url: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Find a previously downloaded file for `key` in `cache_dir`, regardless of
+/// the extension `yt-dlp`/`curl` gave it.
+fn find_cached_file(cache_dir: &Path, key: &str) -> Option<PathBuf> {
+    std::fs::read_dir(cache_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|p| p.file_stem().and_then(|s| s.to_str()) == Some(key))
+}
+
+/// Download `url` into `cache_dir`, deduplicating repeated imports of the
+/// same URL by hashing it into the cache key. Platform URLs (YouTube, Vimeo,
+/// ...) go through `yt-dlp` with `format_selector` (yt-dlp's own default is
+/// used when `None`); anything else is treated as a direct media file and
+/// fetched with `curl`.
+fn download_to_cache(url: &str, cache_dir: &Path, format_selector: Option<&str>) -> Result<PathBuf> {
+    std::fs::create_dir_all(cache_dir)?;
+    let key = cache_key_for_url(url);
+
+    if let Some(cached) = find_cached_file(cache_dir, &key) {
+        return Ok(cached);
+    }
+
+    if is_video_platform_url(url) {
+        let output_template = cache_dir.join(format!("{key}.%(ext)s"));
+        let mut cmd = std::process::Command::new("yt-dlp");
+        if let Some(format) = format_selector {
+            cmd.args(["-f", format]);
+        }
+        cmd.args(["-o", &output_template.to_string_lossy()]).arg(url);
+        let output = cmd
+            .output()
+            .map_err(|e| RenderError::DownloadFailed(e.to_string()))?;
+        if !output.status.success() {
+            return Err(RenderError::DownloadFailed(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+    } else {
+        let ext = Path::new(url)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("bin");
+        let dest = cache_dir.join(format!("{key}.{ext}"));
+        let output = std::process::Command::new("curl")
+            .args(["-L", "-s", "-o"])
+            .arg(&dest)
+            .arg(url)
+            .output()
+            .map_err(|e| RenderError::DownloadFailed(e.to_string()))?;
+        if !output.status.success() {
+            return Err(RenderError::DownloadFailed(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+    }
+
+    find_cached_file(cache_dir, &key).ok_or_else(|| {
+        RenderError::DownloadFailed(format!("no file produced for {url}"))
+    })
+}
+
+/// Import a media asset from a remote URL: download into `cache_dir` (keyed
+/// by a hash of the URL so repeated imports are deduplicated), then probe the
+/// cached file the same way a local import would. The original URL is kept
+/// on the resulting `Asset` so a later GC/refresh pass knows its provenance.
+pub fn import_remote_asset(
+    url: &str,
+    cache_dir: impl AsRef<Path>,
+    format_selector: Option<&str>,
+) -> Result<Asset> {
+    let cached_path = download_to_cache(url, cache_dir.as_ref(), format_selector)?;
+    let mut asset = import_asset(&cached_path)?;
+    asset.source_url = Some(url.to_string());
+    Ok(asset)
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// Shell out to ffprobe and parse its JSON report for `path`.
+fn run_ffprobe(path: &Path) -> Result<FfprobeOutput> {
+    if !path.exists() {
+        return Err(RenderError::FileNotFound(path.to_path_buf()));
+    }
+
     let output = std::process::Command::new("ffprobe")
         .args([
             "-v",
@@ -50,6 +479,8 @@ pub fn probe_asset(path: impl AsRef<Path>) -> Result<ProbeResult> {
             "json",
             "-show_format",
             "-show_streams",
+            "-show_entries",
+            "stream_side_data_list",
         ])
         .arg(path)
         .output()
@@ -60,44 +491,131 @@ pub fn probe_asset(path: impl AsRef<Path>) -> Result<ProbeResult> {
         return Err(RenderError::FfprobeFailed(stderr.into_owned()));
     }
 
-    let probe: FfprobeOutput = serde_json::from_slice(&output.stdout)?;
-    parse_probe_output(&probe)
+    Ok(serde_json::from_slice(&output.stdout)?)
 }
 
-/// Import a media file: probe it and create an `Asset`.
-pub fn import_asset(path: impl AsRef<Path>) -> Result<Asset> {
-    let path = path.as_ref();
-    let probe = probe_asset(path)?;
+/// Pull title/artist/date tags out of an ffprobe format report.
+fn parse_tags(probe: &FfprobeOutput) -> MediaTags {
+    MediaTags {
+        title: probe.format.tags.title.clone(),
+        artist: probe.format.tags.artist.clone(),
+        date: probe.format.tags.date.clone(),
+    }
+}
 
-    let kind = detect_asset_kind(path, &probe);
+/// Pull the creation time, encoder, and per-audio-stream language/title tags
+/// out of an ffprobe report. Missing or unparsable values become `None`
+/// rather than an error, since these tags are written by whatever tool last
+/// touched the file and are frequently absent or malformed.
+fn parse_metadata(probe: &FfprobeOutput) -> Metadata {
+    let creation_time_unix_s = probe
+        .format
+        .tags
+        .creation_time
+        .as_deref()
+        .and_then(parse_creation_time_unix_s);
 
-    let name = path
-        .file_name()
-        .map(|n| n.to_string_lossy().into_owned())
-        .unwrap_or_else(|| "unknown".to_string());
+    let audio_stream_tags = probe
+        .streams
+        .iter()
+        .filter(|s| s.codec_type == "audio")
+        .map(|s| StreamTags {
+            stream_index: s.index,
+            language: s.tags.language.clone(),
+            title: s.tags.title.clone(),
+        })
+        .collect();
 
-    Ok(Asset {
-        id: Uuid::new_v4(),
-        name,
-        path: path.to_path_buf(),
-        kind,
-        probe: Some(probe),
-    })
+    Metadata {
+        creation_time_unix_s,
+        encoder: probe.format.tags.encoder.clone(),
+        audio_stream_tags,
+    }
 }
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
+/// Parse an ffprobe `creation_time` tag (ISO-8601/RFC-3339, e.g.
+/// `"2024-03-01T12:34:56.000000Z"`) into Unix seconds. Returns `None` for
+/// anything that doesn't match the expected layout rather than erroring,
+/// since this is a display/sort convenience, not load-bearing data.
+fn parse_creation_time_unix_s(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if s.len() < 19 || s.as_bytes().get(4) != Some(&b'-') || s.as_bytes().get(10) != Some(&b'T') {
+        return None;
+    }
+    let year: i64 = s.get(0..4)?.parse().ok()?;
+    let month: i64 = s.get(5..7)?.parse().ok()?;
+    let day: i64 = s.get(8..10)?.parse().ok()?;
+    let hour: i64 = s.get(11..13)?.parse().ok()?;
+    let minute: i64 = s.get(14..16)?.parse().ok()?;
+    let second: i64 = s.get(17..19)?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Days since the Unix epoch for a proleptic-Gregorian calendar date.
+/// Howard Hinnant's `days_from_civil` algorithm, used here instead of a
+/// date/time crate since this codebase has no such dependency.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
 
 fn parse_probe_output(probe: &FfprobeOutput) -> Result<ProbeResult> {
-    let video_stream = probe
-        .streams
-        .iter()
-        .find(|s| s.codec_type == "video");
-    let audio_stream = probe
-        .streams
-        .iter()
-        .find(|s| s.codec_type == "audio");
+    // Bucket streams by type in a single pass (the pict-rs `into_parts`
+    // approach) instead of calling `.find()` once per stream kind, so every
+    // stream is captured rather than just the first video/audio match.
+    let mut streams = Vec::with_capacity(probe.streams.len());
+    let mut video_streams = Vec::new();
+    let mut audio_streams = Vec::new();
+    for s in &probe.streams {
+        let is_cover_art = s.codec_type == "video" && s.disposition.attached_pic != 0;
+        let frame_rate = if s.codec_type == "video" {
+            s.r_frame_rate.as_deref().and_then(parse_frame_rate)
+        } else {
+            None
+        };
+        let sample_rate = s.sample_rate.as_deref().and_then(|r| r.parse::<u32>().ok());
+        let bit_rate = s.bit_rate.as_deref().and_then(|b| b.parse::<u64>().ok());
+        let duration_us = s
+            .duration
+            .as_deref()
+            .and_then(|d| d.parse::<f64>().ok())
+            .map(TimeUs::from_seconds);
+        let color = (s.codec_type == "video").then(|| stream_color_info(s));
+        streams.push(StreamInfo {
+            index: s.index,
+            codec_type: s.codec_type.clone(),
+            codec: s.codec_name.clone(),
+            width: s.width,
+            height: s.height,
+            channels: s.channels,
+            is_cover_art,
+            frame_rate,
+            sample_rate,
+            bit_rate,
+            duration_us,
+            color,
+        });
+        match s.codec_type.as_str() {
+            "video" if !is_cover_art => video_streams.push(s),
+            "audio" => audio_streams.push(s),
+            _ => {}
+        }
+    }
+
+    // A video stream that is only an attached thumbnail/cover image is never
+    // the primary video, even if it happens to come first in the container.
+    let video_stream = video_streams.first().copied();
+    let audio_stream = audio_streams.first().copied();
 
     let duration_us = probe
         .format
@@ -113,7 +631,7 @@ fn parse_probe_output(probe: &FfprobeOutput) -> Result<ProbeResult> {
     let fps = video_stream
         .and_then(|s| s.r_frame_rate.as_deref())
         .and_then(parse_frame_rate)
-        .unwrap_or(0.0);
+        .unwrap_or(FrameRate::new(0, 1));
 
     let codec = video_stream
         .and_then(|s| s.codec_name.clone())
@@ -127,6 +645,19 @@ fn parse_probe_output(probe: &FfprobeOutput) -> Result<ProbeResult> {
         .and_then(|r| r.parse::<u32>().ok())
         .unwrap_or(0);
 
+    let rotation_deg = video_stream.map(stream_rotation_deg).unwrap_or(0);
+    let (display_width, display_height) = if rotation_deg == 90 || rotation_deg == 270 {
+        (height, width)
+    } else {
+        (width, height)
+    };
+
+    let frame_count = video_stream
+        .and_then(|s| s.nb_frames.as_deref())
+        .and_then(|n| n.parse::<u64>().ok());
+
+    let color = video_stream.map(stream_color_info).unwrap_or_default();
+
     Ok(ProbeResult {
         duration_us,
         width,
@@ -135,84 +666,579 @@ fn parse_probe_output(probe: &FfprobeOutput) -> Result<ProbeResult> {
         codec,
         audio_channels,
         audio_sample_rate,
+        keyframes_us: Vec::new(),
+        streams,
+        rotation_deg,
+        display_width,
+        display_height,
+        metadata: parse_metadata(probe),
+        frame_count,
+        color,
     })
 }
 
+/// Read a video stream's pixel format and color characteristics, deriving
+/// `is_hdr` from the transfer function and `bit_depth` from whichever of
+/// `bits_per_raw_sample`/`pix_fmt` actually reports it.
+fn stream_color_info(stream: &FfprobeStream) -> ColorInfo {
+    let bit_depth = stream
+        .bits_per_raw_sample
+        .as_deref()
+        .and_then(|b| b.parse::<u8>().ok())
+        .filter(|b| *b > 0)
+        .or_else(|| stream.pix_fmt.as_deref().and_then(bit_depth_from_pix_fmt));
+
+    let is_hdr = matches!(
+        stream.color_transfer.as_deref(),
+        Some("smpte2084") | Some("arib-std-b67")
+    );
+
+    ColorInfo {
+        pix_fmt: stream.pix_fmt.clone(),
+        bit_depth,
+        color_space: stream.color_space.clone(),
+        color_transfer: stream.color_transfer.clone(),
+        color_primaries: stream.color_primaries.clone(),
+        is_hdr,
+    }
+}
+
+/// Infer bit depth from an ffmpeg pixel format name, e.g. `yuv420p10le` → 10,
+/// `yuv420p` → 8 (no digit suffix means the 8-bit default). A heuristic, not
+/// a full pixel format table, but covers the planar YUV formats ffprobe
+/// reports for ordinary SDR/HDR video.
+fn bit_depth_from_pix_fmt(pix_fmt: &str) -> Option<u8> {
+    let without_endian = pix_fmt
+        .strip_suffix("le")
+        .or_else(|| pix_fmt.strip_suffix("be"))
+        .unwrap_or(pix_fmt);
+    let digits: String = without_endian
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    if digits.is_empty() {
+        Some(8)
+    } else {
+        digits.chars().rev().collect::<String>().parse().ok()
+    }
+}
+
+/// Read a stream's display rotation from its side-data display matrix (the
+/// modern way players/encoders signal rotation) or fall back to the legacy
+/// `tags.rotate` value, then normalize to one of {0, 90, 180, 270}.
+fn stream_rotation_deg(stream: &FfprobeStream) -> u16 {
+    let raw = stream
+        .side_data_list
+        .iter()
+        .find_map(|d| d.rotation)
+        .or_else(|| stream.tags.rotate.as_deref().and_then(|r| r.parse::<f64>().ok()))
+        .unwrap_or(0.0);
+
+    normalize_rotation_deg(raw)
+}
+
+/// Normalize a raw rotation angle (often negative, e.g. -90) to one of
+/// {0, 90, 180, 270}.
+fn normalize_rotation_deg(raw: f64) -> u16 {
+    let rounded = (raw.round() as i64).rem_euclid(360);
+    let snapped = ((rounded as f64 / 90.0).round() as i64 * 90).rem_euclid(360);
+    snapped as u16
+}
+
 /// Parse ffprobe frame rate string like "30000/1001" or "30/1" into f64.
-fn parse_frame_rate(rate: &str) -> Option<f64> {
+/// Parse ffprobe's `r_frame_rate`, e.g. `"30000/1001"` or `"30/1"`, into an
+/// exact [`FrameRate`] rather than a lossy `f64` -- ffprobe already reports
+/// broadcast rates as fractions, so no precision is lost by keeping them
+/// that way. A bare decimal string (not normally emitted by ffprobe, but
+/// accepted defensively) is scaled into a `/1000` fraction and reduced.
+fn parse_frame_rate(rate: &str) -> Option<FrameRate> {
     if let Some((num, den)) = rate.split_once('/') {
-        let n: f64 = num.parse().ok()?;
-        let d: f64 = den.parse().ok()?;
-        if d == 0.0 {
+        let n: u32 = num.parse().ok()?;
+        let d: u32 = den.parse().ok()?;
+        if d == 0 {
             return None;
         }
-        Some(n / d)
+        Some(FrameRate::new(n, d))
     } else {
-        rate.parse().ok()
+        let f: f64 = rate.parse().ok()?;
+        Some(FrameRate::new((f * 1000.0).round() as u32, 1000))
     }
 }
 
-/// Detect asset kind based on file extension and probe data.
-fn detect_asset_kind(path: &Path, probe: &ProbeResult) -> AssetKind {
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+/// Read the sync-sample (keyframe) decode timestamps out of an MP4/MOV
+/// container by walking `moov -> trak -> mdia -> minf -> stbl` and combining
+/// the `stss` sync-sample table with the `stts` time-to-sample table.
+///
+/// Returns `Ok(vec![])` if the container has no `stss` box, which per the MP4
+/// spec means every sample is a sync sample -- callers should treat that as
+/// "no snapping needed" rather than "no keyframes".
+fn read_keyframes_us(path: &Path) -> Result<Vec<TimeUs>> {
+    let data = std::fs::read(path)?;
+    let Some(moov) = find_box(&data, b"moov") else {
+        return Ok(Vec::new());
+    };
+    let Some(trak) = find_box(moov, b"trak") else {
+        return Ok(Vec::new());
+    };
+    let Some(mdia) = find_box(trak, b"mdia") else {
+        return Ok(Vec::new());
+    };
+    let Some(mdhd) = find_box(mdia, b"mdhd") else {
+        return Ok(Vec::new());
+    };
+    let Some(minf) = find_box(mdia, b"minf") else {
+        return Ok(Vec::new());
+    };
+    let Some(stbl) = find_box(minf, b"stbl") else {
+        return Ok(Vec::new());
+    };
 
-    match ext.as_str() {
-        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "tiff" | "svg" => AssetKind::Image,
-        "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" | "wma" => AssetKind::Audio,
-        _ => {
-            // If we have video dimensions, it's a video
-            if probe.width > 0 && probe.height > 0 {
-                AssetKind::Video
-            } else if probe.audio_channels > 0 {
-                AssetKind::Audio
-            } else {
-                AssetKind::Video // default fallback
+    let timescale = mdhd_timescale(mdhd).unwrap_or(1) as f64;
+
+    let Some(stss) = find_box(stbl, b"stss") else {
+        // No sync-sample table: every sample is a keyframe.
+        return Ok(Vec::new());
+    };
+    let Some(stts) = find_box(stbl, b"stts") else {
+        return Ok(Vec::new());
+    };
+
+    let sync_samples = parse_stss(stss);
+    let sample_times = build_sample_decode_times(stts);
+
+    let keyframes_us = sync_samples
+        .into_iter()
+        .filter_map(|sample_number| sample_times.get(sample_number.saturating_sub(1) as usize))
+        .map(|&decode_ticks| TimeUs::from_seconds(decode_ticks as f64 / timescale))
+        .collect();
+
+    Ok(keyframes_us)
+}
+
+/// Find the first top-level child box with the given four-character-code
+/// inside `data`, returning its payload (the bytes after the 8-byte header).
+fn find_box<'a>(data: &'a [u8], fourcc: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut offset = 0usize;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().ok()?) as usize;
+        let kind = &data[offset + 4..offset + 8];
+        let (header_len, box_len) = if size == 1 {
+            if offset + 16 > data.len() {
+                return None;
             }
+            let large = u64::from_be_bytes(data[offset + 8..offset + 16].try_into().ok()?);
+            (16, large as usize)
+        } else if size == 0 {
+            (8, data.len() - offset)
+        } else {
+            (8, size)
+        };
+        if box_len < header_len || offset + box_len > data.len() {
+            return None;
         }
+        if kind == fourcc {
+            return Some(&data[offset + header_len..offset + box_len]);
+        }
+        offset += box_len;
     }
+    None
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
+/// Like [`find_box`], but collects every top-level child box with the given
+/// fourcc instead of stopping at the first (a `moov` can hold several `trak`
+/// boxes, one per track).
+fn find_all_boxes<'a>(data: &'a [u8], fourcc: &[u8; 4]) -> Vec<&'a [u8]> {
+    let mut offset = 0usize;
+    let mut matches = Vec::new();
+    while offset + 8 <= data.len() {
+        let Some(size_bytes) = data.get(offset..offset + 4) else {
+            break;
+        };
+        let size = u32::from_be_bytes(size_bytes.try_into().unwrap()) as usize;
+        let kind = &data[offset + 4..offset + 8];
+        let (header_len, box_len) = if size == 1 {
+            if offset + 16 > data.len() {
+                break;
+            }
+            let large = u64::from_be_bytes(data[offset + 8..offset + 16].try_into().unwrap());
+            (16, large as usize)
+        } else if size == 0 {
+            (8, data.len() - offset)
+        } else {
+            (8, size)
+        };
+        if box_len < header_len || offset + box_len > data.len() {
+            break;
+        }
+        if kind == fourcc {
+            matches.push(&data[offset + header_len..offset + box_len]);
+        }
+        offset += box_len;
+    }
+    matches
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Read the `timescale` field out of an `mdhd` box (version 0 or 1 layout).
+fn mdhd_timescale(mdhd: &[u8]) -> Option<u32> {
+    let version = *mdhd.first()?;
+    let timescale_offset = if version == 1 { 4 + 8 + 8 } else { 4 + 4 + 4 };
+    let bytes = mdhd.get(timescale_offset..timescale_offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
 
-    #[test]
-    fn parse_frame_rate_fraction() {
-        assert!((parse_frame_rate("30000/1001").unwrap() - 29.97).abs() < 0.01);
-        assert!((parse_frame_rate("30/1").unwrap() - 30.0).abs() < f64::EPSILON);
-        assert!((parse_frame_rate("24/1").unwrap() - 24.0).abs() < f64::EPSILON);
+/// Parse an `stss` box into the 1-based sample numbers that are sync samples.
+fn parse_stss(stss: &[u8]) -> Vec<u32> {
+    let Some(count_bytes) = stss.get(4..8) else {
+        return Vec::new();
+    };
+    let count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut entries = Vec::with_capacity(count);
+    let mut offset = 8;
+    for _ in 0..count {
+        let Some(bytes) = stss.get(offset..offset + 4) else {
+            break;
+        };
+        entries.push(u32::from_be_bytes(bytes.try_into().unwrap()));
+        offset += 4;
     }
+    entries
+}
 
-    #[test]
-    fn parse_frame_rate_plain() {
-        assert!((parse_frame_rate("29.97").unwrap() - 29.97).abs() < 0.01);
+/// Expand an `stts` box's run-length (sample_count, sample_delta) entries
+/// into a per-sample vector of cumulative decode timestamps (in timescale
+/// ticks), so `sample_times[n]` is the decode time of sample `n + 1`.
+fn build_sample_decode_times(stts: &[u8]) -> Vec<u64> {
+    let Some(count_bytes) = stts.get(4..8) else {
+        return Vec::new();
+    };
+    let entry_count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut times = Vec::new();
+    let mut decode_time = 0u64;
+    let mut offset = 8;
+    for _ in 0..entry_count {
+        let Some(sample_count_bytes) = stts.get(offset..offset + 4) else {
+            break;
+        };
+        let Some(sample_delta_bytes) = stts.get(offset + 4..offset + 8) else {
+            break;
+        };
+        let sample_count = u32::from_be_bytes(sample_count_bytes.try_into().unwrap());
+        let sample_delta = u32::from_be_bytes(sample_delta_bytes.try_into().unwrap()) as u64;
+        for _ in 0..sample_count {
+            times.push(decode_time);
+            decode_time += sample_delta;
+        }
+        offset += 8;
     }
+    times
+}
 
-    #[test]
-    fn parse_frame_rate_zero_denominator() {
-        assert!(parse_frame_rate("30/0").is_none());
+/// Find the latest keyframe at or before `us`. If `keyframes_us` is empty
+/// (no index, or every sample is a keyframe), the requested time needs no
+/// snapping and is returned unchanged.
+pub fn nearest_keyframe_before(keyframes_us: &[TimeUs], us: TimeUs) -> TimeUs {
+    match keyframes_us.binary_search(&us) {
+        Ok(_) => us,
+        Err(0) => us,
+        Err(insert_at) => keyframes_us[insert_at - 1],
     }
+}
+
+// ---------------------------------------------------------------------------
+// Native (ffprobe-free) ISO-BMFF probing
+// ---------------------------------------------------------------------------
+
+/// Probe an mp4/mov/m4a file by walking its `moov` box tree directly,
+/// without shelling out to ffprobe.
+fn probe_iso_bmff(path: &Path) -> Result<ProbeResult> {
+    let data = std::fs::read(path)?;
+    probe_iso_bmff_bytes(&data)
+}
+
+/// Walk a `moov` box tree directly, without shelling out to ffprobe.
+/// Recovers duration from `mvhd`, and for each `trak` its dimensions from
+/// `tkhd`, codec from the `stsd` sample entry fourcc, and (for audio tracks)
+/// channel count/sample rate from the audio sample entry. The first video
+/// and first audio track populate the top-level convenience fields,
+/// mirroring `parse_probe_output`.
+fn probe_iso_bmff_bytes(data: &[u8]) -> Result<ProbeResult> {
+    let moov = find_box(data, b"moov")
+        .ok_or_else(|| RenderError::FfprobeFailed("not an ISO-BMFF container (no moov box)".into()))?;
+    let mvhd = find_box(moov, b"mvhd")
+        .ok_or_else(|| RenderError::FfprobeFailed("moov box has no mvhd box".into()))?;
+    let (movie_timescale, movie_duration) = mvhd_timescale_and_duration(mvhd)
+        .ok_or_else(|| RenderError::FfprobeFailed("malformed mvhd box".into()))?;
+    let duration_us = if movie_timescale > 0 {
+        TimeUs::from_seconds(movie_duration as f64 / movie_timescale as f64)
+    } else {
+        TimeUs::ZERO
+    };
+
+    let mut width = 0u32;
+    let mut height = 0u32;
+    let mut codec = String::new();
+    let mut audio_channels = 0u32;
+    let mut audio_sample_rate = 0u32;
+    let mut streams = Vec::new();
+
+    for (index, trak) in find_all_boxes(moov, b"trak").into_iter().enumerate() {
+        let Some(track) = probe_iso_bmff_track(trak, index as u32) else {
+            continue;
+        };
+        match track.info.codec_type.as_str() {
+            "video" if width == 0 && height == 0 => {
+                width = track.info.width.unwrap_or(0);
+                height = track.info.height.unwrap_or(0);
+                codec = track.info.codec.clone().unwrap_or_default();
+            }
+            "audio" if audio_channels == 0 => {
+                audio_channels = track.info.channels.unwrap_or(0);
+                audio_sample_rate = track.sample_rate;
+                if codec.is_empty() {
+                    codec = track.info.codec.clone().unwrap_or_default();
+                }
+            }
+            _ => {}
+        }
+        streams.push(track.info);
+    }
+
+    Ok(ProbeResult {
+        duration_us,
+        width,
+        height,
+        fps: FrameRate::new(0, 1),
+        codec,
+        audio_channels,
+        audio_sample_rate,
+        keyframes_us: Vec::new(),
+        streams,
+        rotation_deg: 0,
+        display_width: width,
+        display_height: height,
+        metadata: Metadata::default(),
+        frame_count: None,
+        color: ColorInfo::default(),
+    })
+}
+
+/// One track's details pulled out of a `trak` box, plus the `sample_rate`
+/// that (unlike the rest) has no home on [`StreamInfo`] because it's only
+/// meaningful for the top-level "primary audio" convenience field.
+struct IsoBmffTrack {
+    info: StreamInfo,
+    sample_rate: u32,
+}
+
+/// Read a single track's handler type, dimensions (video), and sample entry
+/// (codec/channels/sample rate) out of a `trak` box.
+fn probe_iso_bmff_track(trak: &[u8], index: u32) -> Option<IsoBmffTrack> {
+    let mdia = find_box(trak, b"mdia")?;
+    let hdlr = find_box(mdia, b"hdlr")?;
+    let handler_type = hdlr.get(8..12)?;
+    let codec_type = match handler_type {
+        b"vide" => "video",
+        b"soun" => "audio",
+        _ => "data",
+    }
+    .to_string();
+
+    let tkhd = find_box(trak, b"tkhd");
+    let (width, height) = match (codec_type.as_str(), tkhd) {
+        ("video", Some(tkhd)) => tkhd_dimensions(tkhd).map_or((None, None), |(w, h)| (Some(w), Some(h))),
+        _ => (None, None),
+    };
+
+    let minf = find_box(mdia, b"minf");
+    let stsd = minf.and_then(|minf| find_box(minf, b"stbl")).and_then(|stbl| find_box(stbl, b"stsd"));
+    let (codec, channels, sample_rate) = match stsd.and_then(stsd_first_entry) {
+        Some((fourcc, payload)) => {
+            let codec = String::from_utf8_lossy(fourcc).trim().to_string();
+            if codec_type == "audio" {
+                let channels = payload.get(8..10).map(|b| u16::from_be_bytes(b.try_into().unwrap()) as u32);
+                let sample_rate = payload
+                    .get(16..20)
+                    .map(|b| u32::from_be_bytes(b.try_into().unwrap()) >> 16)
+                    .unwrap_or(0);
+                (Some(codec), channels, sample_rate)
+            } else {
+                (Some(codec), None, 0)
+            }
+        }
+        None => (None, None, 0),
+    };
+
+    let info_sample_rate = (codec_type == "audio" && sample_rate > 0).then_some(sample_rate);
+
+    Some(IsoBmffTrack {
+        info: StreamInfo {
+            index,
+            codec_type,
+            codec,
+            width,
+            height,
+            channels,
+            is_cover_art: false,
+            frame_rate: None,
+            sample_rate: info_sample_rate,
+            bit_rate: None,
+            duration_us: None,
+            color: None,
+        },
+        sample_rate,
+    })
+}
+
+/// Read `timescale`/`duration` out of an `mvhd` box (version 0 or 1 layout).
+fn mvhd_timescale_and_duration(mvhd: &[u8]) -> Option<(u32, u64)> {
+    let version = *mvhd.first()?;
+    if version == 1 {
+        let timescale = u32::from_be_bytes(mvhd.get(20..24)?.try_into().ok()?);
+        let duration = u64::from_be_bytes(mvhd.get(24..32)?.try_into().ok()?);
+        Some((timescale, duration))
+    } else {
+        let timescale = u32::from_be_bytes(mvhd.get(12..16)?.try_into().ok()?);
+        let duration = u32::from_be_bytes(mvhd.get(16..20)?.try_into().ok()?) as u64;
+        Some((timescale, duration))
+    }
+}
+
+/// Read the fixed-point 16.16 `width`/`height` fields out of a `tkhd` box
+/// (version 0 or 1 layout), truncated to their integer part.
+fn tkhd_dimensions(tkhd: &[u8]) -> Option<(u32, u32)> {
+    let version = *tkhd.first()?;
+    let width_offset = if version == 1 { 88 } else { 76 };
+    let width = u32::from_be_bytes(tkhd.get(width_offset..width_offset + 4)?.try_into().ok()?) >> 16;
+    let height = u32::from_be_bytes(tkhd.get(width_offset + 4..width_offset + 8)?.try_into().ok()?) >> 16;
+    Some((width, height))
+}
+
+/// Read an `stsd` box's first sample entry, returning its format fourcc and
+/// the payload following it (the `SampleEntry` base fields onward), from
+/// which audio/video specific fields can be read at fixed offsets.
+fn stsd_first_entry(stsd: &[u8]) -> Option<(&[u8], &[u8])> {
+    let entry_count = u32::from_be_bytes(stsd.get(4..8)?.try_into().ok()?);
+    if entry_count == 0 {
+        return None;
+    }
+    let entry = stsd.get(8..)?;
+    let size = u32::from_be_bytes(entry.get(0..4)?.try_into().ok()?) as usize;
+    let fourcc = entry.get(4..8)?;
+    let payload = entry.get(8..size.min(entry.len()))?;
+    Some((fourcc, payload))
+}
+
+/// Detect asset kind based on file extension and probe data.
+fn detect_asset_kind(path: &Path, probe: &ProbeResult) -> AssetKind {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match ext.as_str() {
+        // GIF/PNG/WebP can all carry multiple frames (animated GIF, APNG,
+        // animated WebP); a frame count above one or a nonzero duration
+        // means it behaves like a short looping video, not a still image.
+        "gif" | "png" | "webp" if is_animated(probe) => AssetKind::AnimatedImage,
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "tiff" | "svg" => AssetKind::Image,
+        "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" | "wma" => AssetKind::Audio,
+        _ => {
+            // If we have video dimensions, it's a video
+            if probe.width > 0 && probe.height > 0 {
+                AssetKind::Video
+            } else if probe.audio_channels > 0 {
+                AssetKind::Audio
+            } else {
+                AssetKind::Video // default fallback
+            }
+        }
+    }
+}
+
+fn is_animated(probe: &ProbeResult) -> bool {
+    probe.frame_count.is_some_and(|n| n > 1) || probe.duration_us > TimeUs::ZERO
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_frame_rate_fraction() {
+        assert_eq!(parse_frame_rate("30000/1001").unwrap(), FrameRate::NTSC_30);
+        assert_eq!(parse_frame_rate("30/1").unwrap(), FrameRate::whole(30));
+        assert_eq!(parse_frame_rate("24/1").unwrap(), FrameRate::whole(24));
+    }
+
+    #[test]
+    fn parse_frame_rate_plain() {
+        let fps = parse_frame_rate("29.97").unwrap();
+        assert!((fps.as_f64() - 29.97).abs() < 0.01);
+    }
+
+    #[test]
+    fn import_asset_with_limits_rejects_oversized_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("forgecut_import_limits_test_{}.bin", Uuid::new_v4()));
+        std::fs::write(&path, vec![0u8; 1024]).unwrap();
+
+        let limits = ImportLimits {
+            max_file_size_bytes: Some(10),
+            ..Default::default()
+        };
+        let err = import_asset_with_limits(&path, &limits).unwrap_err();
+        assert!(matches!(err, RenderError::MediaRejected { .. }));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn import_asset_with_limits_rejects_disallowed_extension() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("forgecut_import_limits_test_{}.bin", Uuid::new_v4()));
+        std::fs::write(&path, b"not a real media file").unwrap();
+
+        let limits = ImportLimits {
+            allowed_extensions: Some(vec!["mp4".to_string()]),
+            ..Default::default()
+        };
+        let err = import_asset_with_limits(&path, &limits).unwrap_err();
+        assert!(matches!(err, RenderError::MediaRejected { .. }));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parse_frame_rate_zero_denominator() {
+        assert!(parse_frame_rate("30/0").is_none());
+    }
+
+    #[test]
+    fn detect_kind_by_extension() {
+        let probe = ProbeResult {
+            duration_us: TimeUs::ZERO,
+            width: 0,
+            height: 0,
+            fps: FrameRate::new(0, 1),
+            codec: String::new(),
+            audio_channels: 0,
+            audio_sample_rate: 0,
+            keyframes_us: vec![],
+            streams: vec![],
+            rotation_deg: 0,
+            display_width: 0,
+            display_height: 0,
+            metadata: Metadata::default(),
+            frame_count: None,
+            color: ColorInfo::default(),
+        };
 
-    #[test]
-    fn detect_kind_by_extension() {
-        let probe = ProbeResult {
-            duration_us: TimeUs::ZERO,
-            width: 0,
-            height: 0,
-            fps: 0.0,
-            codec: String::new(),
-            audio_channels: 0,
-            audio_sample_rate: 0,
-        };
-
         assert_eq!(
             detect_asset_kind(Path::new("photo.png"), &probe),
             AssetKind::Image
@@ -233,10 +1259,18 @@ mod tests {
             duration_us: TimeUs::ZERO,
             width: 1920,
             height: 1080,
-            fps: 30.0,
+            fps: FrameRate::whole(30),
             codec: "h264".into(),
             audio_channels: 2,
             audio_sample_rate: 48000,
+            keyframes_us: vec![],
+            streams: vec![],
+            rotation_deg: 0,
+            display_width: 0,
+            display_height: 0,
+            metadata: Metadata::default(),
+            frame_count: None,
+            color: ColorInfo::default(),
         };
         assert_eq!(
             detect_asset_kind(Path::new("clip.mkv"), &video_probe),
@@ -247,10 +1281,18 @@ mod tests {
             duration_us: TimeUs::ZERO,
             width: 0,
             height: 0,
-            fps: 0.0,
+            fps: FrameRate::new(0, 1),
             codec: "aac".into(),
             audio_channels: 2,
             audio_sample_rate: 44100,
+            keyframes_us: vec![],
+            streams: vec![],
+            rotation_deg: 0,
+            display_width: 0,
+            display_height: 0,
+            metadata: Metadata::default(),
+            frame_count: None,
+            color: ColorInfo::default(),
         };
         assert_eq!(
             detect_asset_kind(Path::new("track.unknown"), &audio_probe),
@@ -258,6 +1300,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detect_kind_distinguishes_static_from_animated_images() {
+        let mut probe = ProbeResult {
+            duration_us: TimeUs::ZERO,
+            width: 480,
+            height: 320,
+            fps: FrameRate::new(0, 1),
+            codec: "gif".into(),
+            audio_channels: 0,
+            audio_sample_rate: 0,
+            keyframes_us: vec![],
+            streams: vec![],
+            rotation_deg: 0,
+            display_width: 0,
+            display_height: 0,
+            metadata: Metadata::default(),
+            frame_count: Some(1),
+            color: ColorInfo::default(),
+        };
+        assert_eq!(
+            detect_asset_kind(Path::new("still.gif"), &probe),
+            AssetKind::Image
+        );
+
+        probe.frame_count = Some(30);
+        assert_eq!(
+            detect_asset_kind(Path::new("loop.gif"), &probe),
+            AssetKind::AnimatedImage
+        );
+    }
+
     #[test]
     fn parse_probe_output_video_and_audio() {
         let json = r#"{
@@ -285,11 +1358,287 @@ mod tests {
 
         assert_eq!(result.width, 1920);
         assert_eq!(result.height, 1080);
-        assert!((result.fps - 30.0).abs() < f64::EPSILON);
+        assert_eq!(result.fps, FrameRate::whole(30));
         assert_eq!(result.codec, "h264");
         assert_eq!(result.audio_channels, 2);
         assert_eq!(result.audio_sample_rate, 48000);
         assert_eq!(result.duration_us, TimeUs::from_seconds(10.5));
+        assert_eq!(result.streams.len(), 2);
+        assert_eq!(result.streams[0].codec_type, "video");
+        assert_eq!(result.streams[1].codec_type, "audio");
+    }
+
+    #[test]
+    fn parse_probe_output_populates_per_stream_metadata() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30000/1001",
+                    "bit_rate": "5000000",
+                    "duration": "10.5",
+                    "pix_fmt": "yuv420p"
+                },
+                {
+                    "index": 1,
+                    "codec_type": "audio",
+                    "codec_name": "aac",
+                    "channels": 2,
+                    "sample_rate": "48000",
+                    "bit_rate": "128000",
+                    "duration": "10.5"
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        let video = &result.streams[0];
+        assert_eq!(video.frame_rate, Some(FrameRate::new(30000, 1001)));
+        assert_eq!(video.bit_rate, Some(5_000_000));
+        assert_eq!(video.duration_us, Some(TimeUs::from_seconds(10.5)));
+        assert!(video.color.is_some());
+        assert_eq!(video.sample_rate, None);
+
+        let audio = &result.streams[1];
+        assert_eq!(audio.frame_rate, None);
+        assert_eq!(audio.sample_rate, Some(48000));
+        assert_eq!(audio.bit_rate, Some(128_000));
+        assert_eq!(audio.duration_us, Some(TimeUs::from_seconds(10.5)));
+        assert_eq!(audio.color, None);
+    }
+
+    #[test]
+    fn parse_probe_output_captures_every_stream() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30/1"
+                },
+                {
+                    "index": 1,
+                    "codec_type": "audio",
+                    "codec_name": "aac",
+                    "channels": 2,
+                    "sample_rate": "48000"
+                },
+                {
+                    "index": 2,
+                    "codec_type": "audio",
+                    "codec_name": "aac",
+                    "channels": 6,
+                    "sample_rate": "48000"
+                },
+                {
+                    "index": 3,
+                    "codec_type": "subtitle"
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        // The top-level convenience fields still reflect the first audio
+        // stream (stereo), but every stream is preserved in `streams`.
+        assert_eq!(result.audio_channels, 2);
+        assert_eq!(result.streams.len(), 4);
+        assert_eq!(result.streams[2].channels, Some(6));
+        assert_eq!(result.streams[3].codec_type, "subtitle");
+    }
+
+    #[test]
+    fn parse_probe_output_skips_cover_art_for_primary_video() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "mjpeg",
+                    "width": 300,
+                    "height": 300,
+                    "disposition": { "attached_pic": 1 }
+                },
+                {
+                    "index": 1,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "24/1"
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.width, 1920);
+        assert_eq!(result.height, 1080);
+        assert_eq!(result.codec, "h264");
+        assert_eq!(result.streams.len(), 2);
+        assert!(result.streams[0].is_cover_art);
+        assert!(!result.streams[1].is_cover_art);
+    }
+
+    #[test]
+    fn parse_probe_output_normalizes_negative_side_data_rotation() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30/1",
+                    "side_data_list": [
+                        { "side_data_type": "Display Matrix", "rotation": -90 }
+                    ]
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.rotation_deg, 270);
+        assert_eq!(result.width, 1920);
+        assert_eq!(result.height, 1080);
+        assert_eq!(result.display_width, 1080);
+        assert_eq!(result.display_height, 1920);
+    }
+
+    #[test]
+    fn parse_probe_output_reads_legacy_tags_rotate() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30/1",
+                    "tags": { "rotate": "270" }
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.rotation_deg, 270);
+        assert_eq!(result.display_width, 1080);
+        assert_eq!(result.display_height, 1920);
+    }
+
+    #[test]
+    fn parse_probe_output_no_rotation_keeps_dimensions_as_is() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30/1"
+                }
+            ],
+            "format": {
+                "duration": "10.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.rotation_deg, 0);
+        assert_eq!(result.display_width, 1920);
+        assert_eq!(result.display_height, 1080);
+    }
+
+    #[test]
+    fn parse_probe_output_reads_creation_time_encoder_and_audio_tags() {
+        let json = r#"{
+            "streams": [
+                {
+                    "index": 0,
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "r_frame_rate": "30/1"
+                },
+                {
+                    "index": 1,
+                    "codec_type": "audio",
+                    "codec_name": "aac",
+                    "channels": 2,
+                    "sample_rate": "48000",
+                    "tags": { "language": "eng", "title": "Commentary" }
+                }
+            ],
+            "format": {
+                "duration": "10.5",
+                "tags": {
+                    "encoder": "Lavf60.16.100",
+                    "creation_time": "2024-03-01T12:34:56.000000Z"
+                }
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.metadata.encoder.as_deref(), Some("Lavf60.16.100"));
+        assert_eq!(result.metadata.creation_time_unix_s, Some(1_709_296_496));
+        assert_eq!(result.metadata.audio_stream_tags.len(), 1);
+        assert_eq!(result.metadata.audio_stream_tags[0].stream_index, 1);
+        assert_eq!(
+            result.metadata.audio_stream_tags[0].language.as_deref(),
+            Some("eng")
+        );
+        assert_eq!(
+            result.metadata.audio_stream_tags[0].title.as_deref(),
+            Some("Commentary")
+        );
+    }
+
+    #[test]
+    fn parse_probe_output_missing_or_malformed_creation_time_is_none() {
+        let json = r#"{
+            "streams": [],
+            "format": {
+                "duration": "10.5",
+                "tags": { "creation_time": "not-a-timestamp" }
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.metadata.creation_time_unix_s, None);
+        assert_eq!(result.metadata.encoder, None);
+        assert!(result.metadata.audio_stream_tags.is_empty());
     }
 
     #[test]
@@ -312,12 +1661,106 @@ mod tests {
 
         assert_eq!(result.width, 0);
         assert_eq!(result.height, 0);
-        assert!((result.fps - 0.0).abs() < f64::EPSILON);
+        assert_eq!(result.fps, FrameRate::new(0, 1));
         assert_eq!(result.codec, "mp3");
         assert_eq!(result.audio_channels, 2);
         assert_eq!(result.audio_sample_rate, 44100);
     }
 
+    #[test]
+    fn parse_probe_output_reads_nb_frames() {
+        let json = r#"{
+            "streams": [
+                {
+                    "codec_type": "video",
+                    "codec_name": "gif",
+                    "width": 480,
+                    "height": 320,
+                    "nb_frames": "30"
+                }
+            ],
+            "format": {
+                "duration": "1.5"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.frame_count, Some(30));
+    }
+
+    #[test]
+    fn parse_probe_output_detects_hdr10_color() {
+        let json = r#"{
+            "streams": [
+                {
+                    "codec_type": "video",
+                    "codec_name": "hevc",
+                    "width": 3840,
+                    "height": 2160,
+                    "pix_fmt": "yuv420p10le",
+                    "bits_per_raw_sample": "10",
+                    "color_space": "bt2020nc",
+                    "color_transfer": "smpte2084",
+                    "color_primaries": "bt2020"
+                }
+            ],
+            "format": {
+                "duration": "10.0"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.color.bit_depth, Some(10));
+        assert_eq!(result.color.pix_fmt.as_deref(), Some("yuv420p10le"));
+        assert_eq!(result.color.color_transfer.as_deref(), Some("smpte2084"));
+        assert!(result.color.is_hdr);
+    }
+
+    #[test]
+    fn parse_probe_output_sdr_8bit_is_not_hdr() {
+        let json = r#"{
+            "streams": [
+                {
+                    "codec_type": "video",
+                    "codec_name": "h264",
+                    "width": 1920,
+                    "height": 1080,
+                    "pix_fmt": "yuv420p",
+                    "color_transfer": "bt709"
+                }
+            ],
+            "format": {
+                "duration": "10.0"
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.color.bit_depth, Some(8));
+        assert!(!result.color.is_hdr);
+    }
+
+    #[test]
+    fn parse_probe_output_missing_nb_frames_is_none() {
+        let json = r#"{
+            "streams": [
+                {
+                    "codec_type": "video",
+                    "codec_name": "png",
+                    "width": 480,
+                    "height": 320
+                }
+            ],
+            "format": {}
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let result = parse_probe_output(&output).unwrap();
+
+        assert_eq!(result.frame_count, None);
+    }
+
     #[test]
     fn parse_probe_output_missing_streams() {
         let json = r#"{
@@ -338,4 +1781,240 @@ mod tests {
         let result = probe_asset("/tmp/does_not_exist_forgecut_probe_test.mp4");
         assert!(result.is_err());
     }
+
+    fn make_box(fourcc: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let size = (payload.len() + 8) as u32;
+        let mut out = Vec::with_capacity(payload.len() + 8);
+        out.extend_from_slice(&size.to_be_bytes());
+        out.extend_from_slice(fourcc);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    /// Build a minimal `moov` box with one video and one audio track, for
+    /// exercising the native ISO-BMFF parser without a real media file.
+    fn synthetic_moov() -> Vec<u8> {
+        let mut mvhd_payload = vec![0u8; 100];
+        mvhd_payload[12..16].copy_from_slice(&1000u32.to_be_bytes()); // timescale
+        mvhd_payload[16..20].copy_from_slice(&5000u32.to_be_bytes()); // duration
+        let mvhd = make_box(b"mvhd", &mvhd_payload);
+
+        let mut tkhd_payload = vec![0u8; 84];
+        tkhd_payload[76..80].copy_from_slice(&(1920u32 << 16).to_be_bytes());
+        tkhd_payload[80..84].copy_from_slice(&(1080u32 << 16).to_be_bytes());
+        let tkhd = make_box(b"tkhd", &tkhd_payload);
+
+        let mut hdlr_video_payload = vec![0u8; 24];
+        hdlr_video_payload[8..12].copy_from_slice(b"vide");
+        let hdlr_video = make_box(b"hdlr", &hdlr_video_payload);
+
+        let mut video_entry = Vec::new();
+        video_entry.extend_from_slice(&16u32.to_be_bytes());
+        video_entry.extend_from_slice(b"avc1");
+        video_entry.extend_from_slice(&[0u8; 8]);
+        let mut stsd_video_payload = vec![0u8; 4];
+        stsd_video_payload.extend_from_slice(&1u32.to_be_bytes());
+        stsd_video_payload.extend_from_slice(&video_entry);
+        let stbl_video = make_box(b"stbl", &make_box(b"stsd", &stsd_video_payload));
+        let minf_video = make_box(b"minf", &stbl_video);
+        let mdia_video = make_box(
+            b"mdia",
+            &[hdlr_video.as_slice(), minf_video.as_slice()].concat(),
+        );
+        let trak_video = make_box(b"trak", &[tkhd.as_slice(), mdia_video.as_slice()].concat());
+
+        let mut hdlr_audio_payload = vec![0u8; 24];
+        hdlr_audio_payload[8..12].copy_from_slice(b"soun");
+        let hdlr_audio = make_box(b"hdlr", &hdlr_audio_payload);
+
+        let mut audio_entry = Vec::new();
+        audio_entry.extend_from_slice(&28u32.to_be_bytes());
+        audio_entry.extend_from_slice(b"mp4a");
+        audio_entry.extend_from_slice(&[0u8; 8]); // reserved + data_reference_index
+        audio_entry.extend_from_slice(&2u16.to_be_bytes()); // channelcount
+        audio_entry.extend_from_slice(&16u16.to_be_bytes()); // samplesize
+        audio_entry.extend_from_slice(&[0u8; 4]); // pre_defined + reserved
+        audio_entry.extend_from_slice(&(44100u32 << 16).to_be_bytes()); // samplerate
+        let mut stsd_audio_payload = vec![0u8; 4];
+        stsd_audio_payload.extend_from_slice(&1u32.to_be_bytes());
+        stsd_audio_payload.extend_from_slice(&audio_entry);
+        let stbl_audio = make_box(b"stbl", &make_box(b"stsd", &stsd_audio_payload));
+        let minf_audio = make_box(b"minf", &stbl_audio);
+        let trak_audio = make_box(
+            b"trak",
+            &make_box(
+                b"mdia",
+                &[hdlr_audio.as_slice(), minf_audio.as_slice()].concat(),
+            ),
+        );
+
+        make_box(
+            b"moov",
+            &[mvhd.as_slice(), trak_video.as_slice(), trak_audio.as_slice()].concat(),
+        )
+    }
+
+    #[test]
+    fn probe_iso_bmff_reads_duration_dimensions_and_audio_from_moov() {
+        let result = probe_iso_bmff_bytes(&synthetic_moov()).unwrap();
+
+        assert_eq!(result.duration_us, TimeUs::from_seconds(5.0));
+        assert_eq!(result.width, 1920);
+        assert_eq!(result.height, 1080);
+        assert_eq!(result.codec, "avc1");
+        assert_eq!(result.audio_channels, 2);
+        assert_eq!(result.audio_sample_rate, 44100);
+        assert_eq!(result.streams.len(), 2);
+        assert_eq!(result.streams[0].codec_type, "video");
+        assert_eq!(result.streams[1].codec_type, "audio");
+    }
+
+    #[test]
+    fn probe_iso_bmff_rejects_non_mp4_data() {
+        assert!(probe_iso_bmff_bytes(b"not an mp4 file").is_err());
+    }
+
+    #[test]
+    fn parse_stss_reads_sample_numbers() {
+        let mut stss = vec![0, 0, 0, 0]; // version/flags
+        stss.extend_from_slice(&3u32.to_be_bytes()); // entry_count
+        for n in [1u32, 5, 9] {
+            stss.extend_from_slice(&n.to_be_bytes());
+        }
+        assert_eq!(parse_stss(&stss), vec![1, 5, 9]);
+    }
+
+    #[test]
+    fn build_sample_decode_times_expands_runs() {
+        let mut stts = vec![0, 0, 0, 0]; // version/flags
+        stts.extend_from_slice(&2u32.to_be_bytes()); // entry_count
+        stts.extend_from_slice(&4u32.to_be_bytes()); // sample_count
+        stts.extend_from_slice(&1000u32.to_be_bytes()); // sample_delta
+        stts.extend_from_slice(&2u32.to_be_bytes());
+        stts.extend_from_slice(&500u32.to_be_bytes());
+
+        let times = build_sample_decode_times(&stts);
+        assert_eq!(times, vec![0, 1000, 2000, 3000, 4000, 4500]);
+    }
+
+    #[test]
+    fn nearest_keyframe_before_snaps_down() {
+        let keyframes = vec![TimeUs(0), TimeUs(2_000_000), TimeUs(4_000_000)];
+        assert_eq!(
+            nearest_keyframe_before(&keyframes, TimeUs(3_500_000)),
+            TimeUs(2_000_000)
+        );
+        assert_eq!(
+            nearest_keyframe_before(&keyframes, TimeUs(4_000_000)),
+            TimeUs(4_000_000)
+        );
+    }
+
+    #[test]
+    fn nearest_keyframe_before_empty_index_returns_exact_time() {
+        assert_eq!(
+            nearest_keyframe_before(&[], TimeUs(1_234_567)),
+            TimeUs(1_234_567)
+        );
+    }
+
+    #[test]
+    fn read_keyframes_missing_file_is_an_error() {
+        let result = read_keyframes_us(Path::new("/tmp/does_not_exist_forgecut_probe_test.mp4"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn handler_dispatch_matches_by_extension() {
+        assert!(Mp4Handler.can_handle(Path::new("clip.mp4")));
+        assert!(Mp4Handler.can_handle(Path::new("clip.MOV")));
+        assert!(!Mp4Handler.can_handle(Path::new("clip.mkv")));
+
+        assert!(AudioHandler.can_handle(Path::new("song.flac")));
+        assert!(!AudioHandler.can_handle(Path::new("clip.mp4")));
+
+        assert!(ImageHandler.can_handle(Path::new("photo.PNG")));
+        assert!(!ImageHandler.can_handle(Path::new("song.mp3")));
+
+        // The catch-all always matches, so unknown extensions still resolve.
+        assert!(GenericHandler.can_handle(Path::new("clip.mkv")));
+    }
+
+    #[test]
+    fn parse_tags_reads_title_artist_date() {
+        let json = r#"{
+            "streams": [],
+            "format": {
+                "tags": {
+                    "title": "My Clip",
+                    "artist": "Someone",
+                    "date": "2024-01-01"
+                }
+            }
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let tags = parse_tags(&output);
+
+        assert_eq!(tags.title.as_deref(), Some("My Clip"));
+        assert_eq!(tags.artist.as_deref(), Some("Someone"));
+        assert_eq!(tags.date.as_deref(), Some("2024-01-01"));
+    }
+
+    #[test]
+    fn parse_tags_missing_tags_are_none() {
+        let json = r#"{
+            "streams": [],
+            "format": {}
+        }"#;
+        let output: FfprobeOutput = serde_json::from_str(json).unwrap();
+        let tags = parse_tags(&output);
+
+        assert_eq!(tags.title, None);
+        assert_eq!(tags.artist, None);
+        assert_eq!(tags.date, None);
+    }
+
+    #[test]
+    fn is_video_platform_url_matches_known_hosts() {
+        assert!(is_video_platform_url("https://www.youtube.com/watch?v=abc"));
+        assert!(is_video_platform_url("https://youtu.be/abc"));
+        assert!(is_video_platform_url("https://vimeo.com/12345"));
+        assert!(!is_video_platform_url("https://example.com/clip.mp4"));
+    }
+
+    #[test]
+    fn cache_key_for_url_is_stable_and_distinct() {
+        let a = cache_key_for_url("https://example.com/a.mp4");
+        let b = cache_key_for_url("https://example.com/a.mp4");
+        let c = cache_key_for_url("https://example.com/b.mp4");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn find_cached_file_matches_by_stem_regardless_of_extension() {
+        let dir = std::env::temp_dir().join("forgecut-test-find-cached-file");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("deadbeef.mp4"), b"data").unwrap();
+
+        assert_eq!(
+            find_cached_file(&dir, "deadbeef"),
+            Some(dir.join("deadbeef.mp4"))
+        );
+        assert_eq!(find_cached_file(&dir, "missing"), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_remote_asset_rejects_unreachable_host() {
+        let dir = std::env::temp_dir().join("forgecut-test-import-remote-asset");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let result = import_remote_asset("https://does-not-exist.invalid/clip.mp4", &dir, None);
+        assert!(result.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
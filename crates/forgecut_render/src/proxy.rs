@@ -1,6 +1,22 @@
 use std::path::{Path, PathBuf};
 
+use forgecut_core::types::ColorInfo;
+
 use crate::error::{RenderError, Result};
+use crate::quality_search::score_vmaf;
+
+/// Length of the representative sample [`generate_proxy_target_quality`]
+/// probes at each candidate CRF.
+const SAMPLE_DURATION_SECONDS: f64 = 10.0;
+
+/// Integer CRF bounds the binary search in [`generate_proxy_target_quality`]
+/// stays within.
+const MIN_CRF: i32 = 18;
+const MAX_CRF: i32 = 35;
+
+/// How close the measured VMAF must land to the target before the search
+/// accepts the current CRF.
+const VMAF_TOLERANCE: f64 = 1.0;
 
 /// Generate a 720p H.264 proxy for a video asset.
 /// Proxy stored at `<proxy_dir>/<asset_id>.mp4`.
@@ -41,6 +57,240 @@ pub fn generate_proxy(source_path: &Path, proxy_dir: &Path, asset_id: &str) -> R
     Ok(output)
 }
 
+/// Generate a 720p H.264 proxy for `asset_id`, preserving `color`'s HDR
+/// metadata instead of [`generate_proxy`]'s plain `scale=-2:720`, which
+/// silently strips it and turns graded HDR footage into a washed-out SDR
+/// proxy. When `color.is_hdr` (PQ/`smpte2084` or HLG/`arib-std-b67`
+/// transfer), scales with `zscale` into a 10-bit pixel format and tags the
+/// output with the source's own `-color_primaries`/`-color_trc`/
+/// `-colorspace` so players keep rendering it as HDR. Falls back to
+/// [`generate_proxy`]'s plain SDR pipeline when the source carries no valid
+/// transfer characteristic.
+pub fn generate_proxy_preserving_color(
+    source_path: &Path,
+    proxy_dir: &Path,
+    asset_id: &str,
+    color: &ColorInfo,
+) -> Result<PathBuf> {
+    if !color.is_hdr {
+        return generate_proxy(source_path, proxy_dir, asset_id);
+    }
+
+    std::fs::create_dir_all(proxy_dir).map_err(RenderError::Io)?;
+    let output = proxy_dir.join(format!("{asset_id}.mp4"));
+
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-i".into(),
+        source_path.to_string_lossy().into_owned(),
+        "-vf".into(),
+        "zscale=w=-2:h=720,format=yuv420p10le".into(),
+        "-c:v".into(),
+        "libx264".into(),
+        "-preset".into(),
+        "ultrafast".into(),
+        "-crf".into(),
+        "28".into(),
+        "-pix_fmt".into(),
+        "yuv420p10le".into(),
+    ];
+    if let Some(primaries) = &color.color_primaries {
+        args.extend(["-color_primaries".into(), primaries.clone()]);
+    }
+    if let Some(transfer) = &color.color_transfer {
+        args.extend(["-color_trc".into(), transfer.clone()]);
+    }
+    if let Some(space) = &color.color_space {
+        args.extend(["-colorspace".into(), space.clone()]);
+    }
+    args.extend([
+        "-c:a".into(),
+        "aac".into(),
+        "-b:a".into(),
+        "128k".into(),
+        output.to_string_lossy().into_owned(),
+    ]);
+
+    let status = std::process::Command::new("ffmpeg")
+        .args(&args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "HDR proxy generation failed".into(),
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Generate a 720p H.264 proxy for `asset_id` whose CRF is chosen to land a
+/// `target_vmaf` mean VMAF score instead of [`generate_proxy`]'s fixed
+/// `-crf 28`. A [`SAMPLE_DURATION_SECONDS`] window from the start of the
+/// source is encoded at each candidate CRF and scored against a lossless
+/// copy of the same window via ffmpeg's `libvmaf` filter, binary-searching
+/// the integer range `[`MIN_CRF`, `MAX_CRF`]` until the measured VMAF is
+/// within [`VMAF_TOLERANCE`] of `target_vmaf` or the range collapses. The
+/// full proxy is then encoded once at the chosen CRF.
+///
+/// Errors if `libvmaf` isn't available in the local ffmpeg build -- scoring
+/// the first probe sample fails immediately, so callers can fall back to
+/// [`generate_proxy`]'s fixed-CRF path.
+pub fn generate_proxy_target_quality(
+    source_path: &Path,
+    proxy_dir: &Path,
+    asset_id: &str,
+    target_vmaf: f64,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(proxy_dir).map_err(RenderError::Io)?;
+
+    let work_dir = proxy_dir.join(format!("{asset_id}-quality-probe"));
+    std::fs::create_dir_all(&work_dir).map_err(RenderError::Io)?;
+    let reference_path = work_dir.join("reference.mp4");
+    extract_reference_sample(source_path, &reference_path)?;
+
+    let crf = (|| -> Result<i32> {
+        let mut lo = MIN_CRF;
+        let mut hi = MAX_CRF;
+        let mut best = lo;
+        while lo <= hi {
+            let mid = (lo + hi) / 2;
+            let distorted_path = work_dir.join(format!("probe_{mid}.mp4"));
+            encode_candidate_sample(source_path, mid, &distorted_path)?;
+            let vmaf = score_vmaf(&reference_path, &distorted_path)?;
+            let _ = std::fs::remove_file(&distorted_path);
+
+            best = mid;
+            match next_crf_bounds(lo, hi, mid, vmaf, target_vmaf) {
+                Some((next_lo, next_hi)) => {
+                    lo = next_lo;
+                    hi = next_hi;
+                }
+                None => break,
+            }
+        }
+        Ok(best)
+    })();
+
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let crf = crf?;
+
+    let output = proxy_dir.join(format!("{asset_id}.mp4"));
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-i",
+            &source_path.to_string_lossy(),
+            "-vf",
+            "scale=-2:720",
+            "-c:v",
+            "libx264",
+            "-preset",
+            "ultrafast",
+            "-crf",
+            &crf.to_string(),
+            "-c:a",
+            "aac",
+            "-b:a",
+            "128k",
+            &output.to_string_lossy(),
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed("Proxy generation failed".into()));
+    }
+
+    Ok(output)
+}
+
+/// One step of the CRF binary search: `None` once `mid`'s measured `vmaf` is
+/// within [`VMAF_TOLERANCE`] of `target_vmaf` (search converged), otherwise
+/// the narrowed `(lo, hi)` bracket to probe next. Higher CRF means lower
+/// quality, so an over-target `vmaf` raises the floor and an under-target
+/// `vmaf` lowers the ceiling.
+fn next_crf_bounds(lo: i32, hi: i32, mid: i32, vmaf: f64, target_vmaf: f64) -> Option<(i32, i32)> {
+    if (vmaf - target_vmaf).abs() <= VMAF_TOLERANCE {
+        return None;
+    }
+    if vmaf > target_vmaf {
+        Some((mid + 1, hi))
+    } else {
+        Some((lo, mid - 1))
+    }
+}
+
+/// Losslessly copy the first [`SAMPLE_DURATION_SECONDS`] of `source_path` to
+/// `out_path`, used as the VMAF reference for each candidate CRF.
+fn extract_reference_sample(source_path: &Path, out_path: &Path) -> Result<()> {
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-ss",
+            "0",
+            "-t",
+            &SAMPLE_DURATION_SECONDS.to_string(),
+            "-i",
+            &source_path.to_string_lossy(),
+            "-c",
+            "copy",
+            &out_path.to_string_lossy(),
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "VMAF reference sample extraction failed".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Encode the same [`SAMPLE_DURATION_SECONDS`] window as
+/// [`extract_reference_sample`] at `crf`, scaled to the proxy's 720p target.
+fn encode_candidate_sample(source_path: &Path, crf: i32, out_path: &Path) -> Result<()> {
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-ss",
+            "0",
+            "-t",
+            &SAMPLE_DURATION_SECONDS.to_string(),
+            "-i",
+            &source_path.to_string_lossy(),
+            "-vf",
+            "scale=-2:720",
+            "-c:v",
+            "libx264",
+            "-preset",
+            "ultrafast",
+            "-crf",
+            &crf.to_string(),
+            "-an",
+            &out_path.to_string_lossy(),
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "quality-search proxy probe encode failed".into(),
+        ));
+    }
+    Ok(())
+}
+
 /// Check if a proxy exists for the given asset.
 pub fn proxy_path(proxy_dir: &Path, asset_id: &str) -> Option<PathBuf> {
     let path = proxy_dir.join(format!("{asset_id}.mp4"));
@@ -62,6 +312,79 @@ mod tests {
         assert!(proxy_path(&dir, "no-such-asset").is_none());
     }
 
+    #[test]
+    fn generate_proxy_preserving_color_falls_back_to_sdr_path_for_non_hdr_source() {
+        // Skip if ffmpeg is not available
+        let ffmpeg_available = std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+
+        if !ffmpeg_available {
+            eprintln!("Skipping generate_proxy_preserving_color test: ffmpeg not available");
+            return;
+        }
+
+        let temp_dir = std::env::temp_dir().join("forgecut-test-proxy-sdr-fallback");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let test_source = temp_dir.join("test_input.mp4");
+        let gen = std::process::Command::new("ffmpeg")
+            .args([
+                "-y",
+                "-f",
+                "lavfi",
+                "-i",
+                "color=c=black:s=1920x1080:d=1",
+                "-c:v",
+                "libx264",
+                "-t",
+                "1",
+                &test_source.to_string_lossy(),
+            ])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+
+        if gen.is_err() || !gen.unwrap().success() {
+            eprintln!("Skipping generate_proxy_preserving_color test: could not create test video");
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return;
+        }
+
+        let proxy_dir = temp_dir.join("proxies");
+        let sdr_color = ColorInfo::default();
+        let result =
+            generate_proxy_preserving_color(&test_source, &proxy_dir, "test-asset", &sdr_color);
+        assert!(
+            result.is_ok(),
+            "generate_proxy_preserving_color failed: {:?}",
+            result.err()
+        );
+        assert!(result.unwrap().exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn next_crf_bounds_converges_within_tolerance() {
+        assert_eq!(next_crf_bounds(18, 35, 26, 92.5, 93.0), None);
+    }
+
+    #[test]
+    fn next_crf_bounds_raises_crf_when_quality_above_target() {
+        assert_eq!(next_crf_bounds(18, 35, 26, 97.0, 93.0), Some((27, 35)));
+    }
+
+    #[test]
+    fn next_crf_bounds_lowers_crf_when_quality_below_target() {
+        assert_eq!(next_crf_bounds(18, 35, 26, 80.0, 93.0), Some((18, 25)));
+    }
+
     #[test]
     fn generate_proxy_with_valid_input() {
         // Skip if ffmpeg is not available
@@ -0,0 +1,352 @@
+//! Automatic CRF selection for a target visual quality, so a user can ask
+//! for e.g. "VMAF 93" instead of guessing a CRF number -- the same
+//! probe-a-few-samples idea chunked AV1 encoders use for per-scene CRF
+//! selection. [`select_crf`] extracts a handful of short windows from the
+//! compiled timeline, encodes each at a candidate CRF, scores the result
+//! against a near-lossless encode of the same window with ffmpeg's
+//! `libvmaf` filter, and bisects/interpolates over CRF until the measured
+//! mean VMAF converges on the target.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use forgecut_core::types::TimeUs;
+
+use crate::error::{RenderError, Result};
+use crate::render::RenderPlan;
+
+/// A target mean VMAF score (0-100) to hit via [`select_crf`], in place of a
+/// fixed CRF.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetQuality {
+    pub vmaf: f64,
+}
+
+/// Bounds and budget for the CRF search in [`select_crf`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrfSearchConfig {
+    pub min_crf: u32,
+    pub max_crf: u32,
+    /// Length of each probe sample window.
+    pub sample_window_us: TimeUs,
+    /// How many non-overlapping sample windows to probe per candidate CRF
+    /// (their VMAF scores are averaged).
+    pub samples_per_probe: usize,
+    /// Interpolation probes allowed beyond the two bracketing endpoints
+    /// (`min_crf`/`max_crf`) before giving up and returning the best
+    /// bracket found so far.
+    pub max_probes: u32,
+}
+
+impl Default for CrfSearchConfig {
+    fn default() -> Self {
+        Self {
+            min_crf: 10,
+            max_crf: 40,
+            sample_window_us: TimeUs::from_seconds(4.0),
+            samples_per_probe: 3,
+            max_probes: 4,
+        }
+    }
+}
+
+/// CRF used as the "near-lossless" reference encode that probe samples are
+/// scored against, since the composited timeline has no single source frame
+/// sequence of its own to diff against.
+const REFERENCE_CRF: u32 = 0;
+
+/// One measured (CRF, mean VMAF) point from a probe encode+score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Probe {
+    crf: u32,
+    vmaf: f64,
+}
+
+/// Choose a CRF for `plan` that lands `target`'s VMAF score, searching within
+/// `config`'s CRF bounds. Skips probing (returning `config.max_crf.min(23)`
+/// clamped to the bounds) when `total_duration_us` is shorter than one
+/// sample window -- too little footage to extract a representative sample
+/// from. `work_dir` holds the scratch probe-encode files, which are removed
+/// before returning.
+pub fn select_crf(
+    plan: &RenderPlan,
+    total_duration_us: TimeUs,
+    target: TargetQuality,
+    config: &CrfSearchConfig,
+    work_dir: &Path,
+) -> Result<u32> {
+    if total_duration_us < config.sample_window_us {
+        return Ok(23u32.clamp(config.min_crf, config.max_crf));
+    }
+
+    std::fs::create_dir_all(work_dir).map_err(RenderError::Io)?;
+    let windows = sample_windows(
+        total_duration_us,
+        config.sample_window_us,
+        config.samples_per_probe,
+    );
+
+    let probe_at = |crf: u32| -> Result<Probe> {
+        let vmaf = average_vmaf_at_crf(plan, &windows, crf, work_dir)?;
+        Ok(Probe { crf, vmaf })
+    };
+
+    let mut lo = probe_at(config.min_crf)?;
+    let mut hi = probe_at(config.max_crf)?;
+
+    // Even the highest quality the caller allows doesn't reach the target --
+    // nothing to search for, use it anyway.
+    if lo.vmaf < target.vmaf {
+        let _ = std::fs::remove_dir_all(work_dir);
+        return Ok(lo.crf);
+    }
+    // Even the most compressed CRF the caller allows still clears the
+    // target -- no need to spend bitrate getting there.
+    if hi.vmaf >= target.vmaf {
+        let _ = std::fs::remove_dir_all(work_dir);
+        return Ok(hi.crf);
+    }
+
+    for _ in 0..config.max_probes {
+        if hi.crf <= lo.crf + 1 {
+            break;
+        }
+        let mid_crf = next_crf_by_interpolation(lo, hi, target.vmaf);
+        let mid = probe_at(mid_crf)?;
+        if mid.vmaf >= target.vmaf {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(work_dir);
+    Ok(lo.crf)
+}
+
+/// Evenly space up to `count` non-overlapping `window_us`-long windows
+/// across `[0, total_us)`.
+fn sample_windows(total_us: TimeUs, window_us: TimeUs, count: usize) -> Vec<(TimeUs, TimeUs)> {
+    // At most as many windows as fit without overlapping.
+    let fit = ((total_us.0 - window_us.0) / window_us.0.max(1)).max(1) as usize;
+    let count = count.max(1).min(fit);
+    let stride_us = (total_us.0 - window_us.0) as f64 / count.max(2) as f64;
+    (0..count)
+        .map(|i| {
+            let start = TimeUs((i as f64 * stride_us).round() as i64);
+            (start, TimeUs(start.0 + window_us.0))
+        })
+        .collect()
+}
+
+/// Interpolate the next CRF to probe between two bracketing measurements,
+/// assuming VMAF decreases monotonically as CRF increases. Solves for where
+/// the line through `lo` and `hi` crosses `target_vmaf`, clamped strictly
+/// inside the bracket so every probe narrows it.
+fn next_crf_by_interpolation(lo: Probe, hi: Probe, target_vmaf: f64) -> u32 {
+    if hi.crf <= lo.crf + 1 {
+        // No integer CRF strictly between the bracket's endpoints left to try.
+        return lo.crf + 1;
+    }
+    if (lo.vmaf - hi.vmaf).abs() < f64::EPSILON {
+        return (lo.crf + hi.crf) / 2;
+    }
+    let t = (lo.vmaf - target_vmaf) / (lo.vmaf - hi.vmaf);
+    let raw = lo.crf as f64 + t * (hi.crf as f64 - lo.crf as f64);
+    (raw.round() as u32).clamp(lo.crf + 1, hi.crf - 1)
+}
+
+/// Encode every window in `windows` at `crf` and at [`REFERENCE_CRF`], score
+/// each pair with [`score_vmaf`], and return the mean.
+fn average_vmaf_at_crf(
+    plan: &RenderPlan,
+    windows: &[(TimeUs, TimeUs)],
+    crf: u32,
+    work_dir: &Path,
+) -> Result<f64> {
+    let mut scores = Vec::with_capacity(windows.len());
+    for (i, &(start_us, end_us)) in windows.iter().enumerate() {
+        let reference_path = work_dir.join(format!("ref_{i}.mp4"));
+        let distorted_path = work_dir.join(format!("probe_{crf}_{i}.mp4"));
+        encode_sample(plan, start_us, end_us, REFERENCE_CRF, &reference_path)?;
+        encode_sample(plan, start_us, end_us, crf, &distorted_path)?;
+        scores.push(score_vmaf(&reference_path, &distorted_path)?);
+        let _ = std::fs::remove_file(&reference_path);
+        let _ = std::fs::remove_file(&distorted_path);
+    }
+    Ok(scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+/// Encode the `[start_us, end_us)` window of `plan`'s timeline at `crf` to
+/// `out_path`, trimming with `-ss`/`-to` on the shared filter graph the same
+/// way [`crate::chunked_export`] trims segments.
+fn encode_sample(
+    plan: &RenderPlan,
+    start_us: TimeUs,
+    end_us: TimeUs,
+    crf: u32,
+    out_path: &Path,
+) -> Result<()> {
+    let mut args: Vec<String> = vec!["-y".to_string()];
+    for input in &plan.inputs {
+        args.extend(input.pre_args.clone());
+        args.push("-i".to_string());
+        args.push(input.path.to_string_lossy().to_string());
+    }
+    args.push("-filter_complex".to_string());
+    args.push(plan.filter_graph.clone());
+    args.push("-ss".to_string());
+    args.push(start_us.as_seconds().to_string());
+    args.push("-to".to_string());
+    args.push(end_us.as_seconds().to_string());
+    args.push("-map".to_string());
+    // `compile`'s output_args always starts with `-map [<final_video_label>]`
+    // (see render::compile) -- reuse it rather than assuming the label is
+    // always "outv", which isn't true once subtitles or a bumper are
+    // compiled in.
+    args.push(
+        plan.output_args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| "[outv]".to_string()),
+    );
+    args.push("-c:v".to_string());
+    args.push("libx264".to_string());
+    args.push("-preset".to_string());
+    args.push("veryfast".to_string());
+    args.push("-crf".to_string());
+    args.push(crf.to_string());
+    args.push("-an".to_string());
+    args.push(out_path.to_string_lossy().to_string());
+
+    let status = Command::new("ffmpeg")
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(format!(
+            "quality-search probe encode at crf {crf} failed"
+        )));
+    }
+    Ok(())
+}
+
+/// Score `distorted_path` against `reference_path` with ffmpeg's `libvmaf`
+/// filter and parse the reported mean VMAF from its stderr log.
+pub(crate) fn score_vmaf(reference_path: &Path, distorted_path: &Path) -> Result<f64> {
+    let output = Command::new("ffmpeg")
+        .args([
+            "-i",
+            &distorted_path.to_string_lossy(),
+            "-i",
+            &reference_path.to_string_lossy(),
+            "-lavfi",
+            "libvmaf",
+            "-f",
+            "null",
+            "-",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(RenderError::Io)?;
+
+    parse_vmaf_score(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Parse the mean VMAF score out of an ffmpeg `libvmaf` filter log line,
+/// e.g. `[libvmaf @ 0x...] VMAF score: 95.652324`.
+fn parse_vmaf_score(log: &str) -> Result<f64> {
+    log.lines()
+        .find_map(|line| {
+            let (_, after) = line.split_once("VMAF score:")?;
+            after.trim().split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .ok_or_else(|| RenderError::VmafParse(format!("no VMAF score found in: {log}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_windows_spaces_evenly_across_timeline() {
+        let windows = sample_windows(TimeUs::from_seconds(10.0), TimeUs::from_seconds(2.0), 3);
+        assert_eq!(windows.len(), 3);
+        for (start, end) in &windows {
+            assert_eq!(*end - *start, TimeUs::from_seconds(2.0));
+            assert!(*end <= TimeUs::from_seconds(10.0));
+        }
+        assert_eq!(windows[0].0, TimeUs::ZERO);
+    }
+
+    #[test]
+    fn next_crf_by_interpolation_converges_toward_target() {
+        let lo = Probe {
+            crf: 10,
+            vmaf: 98.0,
+        };
+        let hi = Probe {
+            crf: 40,
+            vmaf: 80.0,
+        };
+        let mid = next_crf_by_interpolation(lo, hi, 93.0);
+        // Target 93 is 5/18ths of the way from 98 down to 80, so the
+        // interpolated CRF should land a bit below the bracket midpoint.
+        assert!(mid > 10 && mid < 25);
+    }
+
+    #[test]
+    fn next_crf_by_interpolation_stays_inside_bracket() {
+        let lo = Probe {
+            crf: 20,
+            vmaf: 90.0,
+        };
+        let hi = Probe {
+            crf: 25,
+            vmaf: 85.0,
+        };
+        let mid = next_crf_by_interpolation(lo, hi, 82.0);
+        assert!(mid >= lo.crf + 1 && mid <= hi.crf - 1);
+    }
+
+    #[test]
+    fn next_crf_by_interpolation_steps_by_one_when_bracket_is_adjacent() {
+        let lo = Probe {
+            crf: 20,
+            vmaf: 90.0,
+        };
+        let hi = Probe {
+            crf: 21,
+            vmaf: 89.0,
+        };
+        assert_eq!(next_crf_by_interpolation(lo, hi, 85.0), 21);
+    }
+
+    #[test]
+    fn next_crf_by_interpolation_splits_evenly_when_vmaf_ties() {
+        let lo = Probe {
+            crf: 10,
+            vmaf: 95.0,
+        };
+        let hi = Probe {
+            crf: 30,
+            vmaf: 95.0,
+        };
+        assert_eq!(next_crf_by_interpolation(lo, hi, 93.0), 20);
+    }
+
+    #[test]
+    fn parse_vmaf_score_reads_mean_score_line() {
+        let log = "frame=  100\n[libvmaf @ 0x55f1] VMAF score: 95.652324\n";
+        assert!((parse_vmaf_score(log).unwrap() - 95.652324).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parse_vmaf_score_errors_without_a_score_line() {
+        assert!(parse_vmaf_score("no score here").is_err());
+    }
+}
@@ -3,6 +3,7 @@ use forgecut_core::types::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use uuid::Uuid;
 
 /// A compiled render plan ready for ffmpeg execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +12,101 @@ pub struct RenderPlan {
     pub filter_graph: String,
     pub output_args: Vec<String>,
     pub output_path: PathBuf,
+    /// Additional renditions [`compile_multi_output`] taps off the same
+    /// filter graph via `split`/`scale`. When non-empty, [`build_ffmpeg_args`]
+    /// emits one `-map`/`-c:v`/`-crf`/path group per entry here instead of
+    /// using `output_args`/`output_path`.
+    #[serde(default)]
+    pub outputs: Vec<OutputTarget>,
+    /// Set by [`compile_hls`] in place of `output_args`/`outputs`: packages
+    /// the compiled timeline as an HLS adaptive-bitrate ladder instead of a
+    /// progressive file. See [`build_ffmpeg_args`].
+    #[serde(default)]
+    pub hls: Option<HlsPackage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderInput {
+    /// A file path for an ordinary asset input, or an `-f lavfi` source
+    /// descriptor (e.g. `"color=c=black:s=1920x1080:d=5"`) for a
+    /// synthesized [`Bumper`] with no asset on disk.
     pub path: PathBuf,
     pub index: usize,
+    /// Extra ffmpeg args inserted immediately before this input's `-i`, e.g.
+    /// `["-f", "lavfi"]` for a `color`/`anullsrc` source or `["-loop", "1",
+    /// "-t", "5"]` to loop a still image for a bumper's duration.
+    #[serde(default)]
+    pub pre_args: Vec<String>,
+}
+
+/// Crossfade duration used at a clip boundary that has no [`Transition`]
+/// recorded, or whose recorded `region_us` can't be trusted as a duration
+/// (i.e. it's `0` or negative) -- matches the fixed transition length used elsewhere in
+/// the project for "just crossfade it" cases.
+const DEFAULT_TRANSITION_DURATION_US: TimeUs = TimeUs(200_000);
+
+/// Floor a crossfade is never shortened below, so a run of back-to-back
+/// clamped transitions never collapses to a zero- or negative-length
+/// `xfade`/`acrossfade` (which ffmpeg rejects).
+const MIN_TRANSITION_DURATION_S: f64 = 0.04;
+
+/// Map a [`TransitionKind`] to the `xfade` filter's `transition` name.
+fn xfade_transition_name(kind: TransitionKind) -> &'static str {
+    match kind {
+        TransitionKind::CrossDissolve => "dissolve",
+        TransitionKind::Fade => "fade",
+        TransitionKind::WipeLeft => "wipeleft",
+        TransitionKind::SlideLeft => "slideleft",
+    }
+}
+
+/// The [`Transition`] on `track` linking `a` and `b`'s ids, in either order,
+/// if one exists.
+fn transition_between(track: &Track, a: Uuid, b: Uuid) -> Option<&Transition> {
+    track
+        .transitions
+        .iter()
+        .find(|t| (t.out_item == a && t.in_item == b) || (t.out_item == b && t.in_item == a))
+}
+
+/// Build an `atempo` filter chain re-timing audio by `speed`.
+///
+/// ffmpeg's `atempo` only accepts factors in `[0.5, 2.0]`, so a speed
+/// outside that range is decomposed into a product of in-range factors
+/// chained with commas (e.g. `speed=8.0` becomes
+/// `atempo=2,atempo=2,atempo=2`).
+///
+/// `Item::speed` is a plain `f64` field, so a hand-edited or otherwise
+/// corrupted project file can reach here with a `speed` that bypasses
+/// [`forgecut_core::editing::Timeline::set_speed`]'s validation --
+/// notably `0.0`, which would spin the halving loop below forever since
+/// `0.0 / 0.5` never climbs back above the threshold. Treat anything
+/// non-finite or non-positive as a no-op speed instead.
+fn atempo_chain(speed: f64) -> String {
+    let mut remaining = if speed.is_finite() && speed > 0.0 {
+        speed
+    } else {
+        1.0
+    };
+    let mut factors: Vec<f64> = Vec::new();
+
+    while remaining > 2.0 + f64::EPSILON {
+        factors.push(2.0);
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 - f64::EPSILON {
+        factors.push(0.5);
+        remaining /= 0.5;
+    }
+    if factors.is_empty() || (remaining - 1.0).abs() > f64::EPSILON {
+        factors.push(remaining);
+    }
+
+    factors
+        .iter()
+        .map(|f| format!("atempo={f}"))
+        .collect::<Vec<_>>()
+        .join(",")
 }
 
 /// Progress update during rendering.
@@ -27,6 +117,11 @@ pub struct RenderProgress {
     pub fps: f64,
     pub speed: String,
     pub eta_seconds: Option<f64>,
+    /// Encoded output size in bytes so far. Only populated by
+    /// [`parse_progress_block`] (ffmpeg's structured `-progress` stream
+    /// reports `total_size=`); the legacy stderr scraper in [`parse_progress`]
+    /// leaves this `None`.
+    pub total_size: Option<u64>,
 }
 
 /// Build PiP overlay filter chains for video clips on non-primary tracks.
@@ -54,14 +149,21 @@ fn compile_pip_overlays(
     let mut current_label = base_label.to_string();
 
     for (i, clip) in pip_clips.iter().enumerate() {
-        let (asset_id, source_in_us, source_out_us, timeline_start_us) = match clip {
+        let (asset_id, source_in_us, source_out_us, timeline_start_us, speed) = match clip {
             Item::VideoClip {
                 asset_id,
                 source_in_us,
                 source_out_us,
                 timeline_start_us,
+                speed,
                 ..
-            } => (*asset_id, *source_in_us, *source_out_us, *timeline_start_us),
+            } => (
+                *asset_id,
+                *source_in_us,
+                *source_out_us,
+                *timeline_start_us,
+                *speed,
+            ),
             _ => continue,
         };
 
@@ -71,7 +173,7 @@ fn compile_pip_overlays(
         let start_s = source_in_us.as_seconds();
         let end_s = source_out_us.as_seconds();
         let tl_start_s = timeline_start_us.as_seconds();
-        let clip_duration = end_s - start_s;
+        let clip_duration = (end_s - start_s) / speed;
         let tl_end_s = tl_start_s + clip_duration;
 
         let scaled_label = format!("pip_scaled_{i}");
@@ -81,9 +183,15 @@ fn compile_pip_overlays(
             format!("pip_{i}")
         };
 
-        // Trim and scale the PiP input
+        let pip_pts_filter = if (speed - 1.0).abs() > f64::EPSILON {
+            format!("setpts=(PTS-STARTPTS)/{speed}")
+        } else {
+            "setpts=PTS-STARTPTS".to_string()
+        };
+
+        // Trim, re-time by the clip's playback speed, and scale the PiP input
         filters.push(format!(
-            "[{input_idx}:v]trim=start={start_s}:end={end_s},setpts=PTS-STARTPTS,scale={pip_w}:{pip_h}[{scaled_label}]"
+            "[{input_idx}:v]trim=start={start_s}:end={end_s},{pip_pts_filter},scale={pip_w}:{pip_h}[{scaled_label}]"
         ));
 
         // Overlay on base video with time-scoped enable
@@ -97,11 +205,131 @@ fn compile_pip_overlays(
     (filters.join(";"), current_label)
 }
 
-/// Compile a project into an ffmpeg render plan.
+/// Escapes text for a single-quoted ffmpeg `drawtext` `text=` value:
+/// backslashes and colons (the filter option separator) are backslash-
+/// escaped, embedded single quotes use the standard close-quote/escape/
+/// reopen-quote trick since the whole value is wrapped in single quotes,
+/// and literal newlines (from a multi-line subtitle cue) become drawtext's
+/// `\n` line-break escape.
+fn escape_drawtext_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(':', "\\:")
+        .replace('\'', "'\\''")
+        .replace('\n', "\\n")
+}
+
+/// Converts a `#RRGGBB` hex color or an ffmpeg color name into the form
+/// `drawtext`'s `fontcolor`/`boxcolor` options expect (`0x{hex}`, or the
+/// name unchanged).
+fn ffmpeg_drawtext_color(color: &str) -> String {
+    match color.strip_prefix('#') {
+        Some(hex) => format!("0x{hex}"),
+        None => color.to_string(),
+    }
+}
+
+/// Builds the `drawtext` filter for one subtitle [`crate::subtitles::Cue`]:
+/// an opaque background box behind the text, anchored per `style.anchor`.
+fn subtitle_cue_filter(cue: &crate::subtitles::Cue, style: &CaptionStyle) -> String {
+    let start_s = cue.start_us.as_seconds();
+    let end_s = cue.end_us.as_seconds();
+    let escaped_text = escape_drawtext_text(&cue.text);
+    let box_color = ffmpeg_drawtext_color(&style.box_color);
+    let (x, y) = match style.anchor {
+        CaptionAnchor::BottomCenter => ("(w-text_w)/2", "h-text_h-40"),
+        CaptionAnchor::TopCenter => ("(w-text_w)/2", "40"),
+    };
+    format!(
+        "drawtext=text='{escaped_text}':fontsize={}:fontcolor=white:box=1:boxcolor={box_color}@{}:boxborderw={}:x={x}:y={y}:enable='between(t,{start_s},{end_s})'",
+        style.font_size, style.box_opacity, style.box_border_width
+    )
+}
+
+/// Synthesize a [`Bumper`]'s `color`/`anullsrc` (or looped-image) lavfi
+/// inputs and its `drawtext` title card, registering the inputs at the end
+/// of `inputs` and emitting filters that land on `video_label`/`audio_label`.
 ///
-/// For v0.1: concatenate video clips with trim/setpts/atrim/asetpts/concat filters.
-/// PiP: additional video tracks overlay on the primary video track.
-pub fn compile(project: &Project) -> Result<RenderPlan> {
+/// Returns the filters to append to the graph and the bumper's duration in
+/// seconds (used to place it in the outer concat's timing).
+fn compile_bumper(
+    project: &Project,
+    bumper: &Bumper,
+    video_label: &str,
+    audio_label: &str,
+    inputs: &mut Vec<RenderInput>,
+) -> Result<(Vec<String>, f64)> {
+    let proj_w = project.settings.width;
+    let proj_h = project.settings.height;
+    let fps = project.settings.fps;
+    let sample_rate = project.settings.sample_rate;
+    let duration_s = bumper.duration_us.as_seconds();
+
+    let video_idx = inputs.len();
+    let scale_filter = match &bumper.background {
+        BumperBackground::Color(color) => {
+            let ffmpeg_color = ffmpeg_drawtext_color(color);
+            inputs.push(RenderInput {
+                path: PathBuf::from(format!(
+                    "color=c={ffmpeg_color}:s={proj_w}x{proj_h}:r={fps}:d={duration_s}"
+                )),
+                index: video_idx,
+                pre_args: vec!["-f".to_string(), "lavfi".to_string()],
+            });
+            String::new()
+        }
+        BumperBackground::Image { asset_id } => {
+            let asset = project
+                .assets
+                .iter()
+                .find(|a| a.id == *asset_id)
+                .ok_or(RenderError::AssetNotFound(*asset_id))?;
+            inputs.push(RenderInput {
+                path: asset.path.clone(),
+                index: video_idx,
+                pre_args: vec![
+                    "-loop".to_string(),
+                    "1".to_string(),
+                    "-t".to_string(),
+                    duration_s.to_string(),
+                ],
+            });
+            format!(
+                ",scale={proj_w}:{proj_h}:force_original_aspect_ratio=decrease,pad={proj_w}:{proj_h}:(ow-iw)/2:(oh-ih)/2"
+            )
+        }
+    };
+
+    let audio_idx = inputs.len();
+    inputs.push(RenderInput {
+        path: PathBuf::from(format!("anullsrc=r={sample_rate}:cl=stereo:d={duration_s}")),
+        index: audio_idx,
+        pre_args: vec!["-f".to_string(), "lavfi".to_string()],
+    });
+
+    let escaped_text = escape_drawtext_text(&bumper.text);
+
+    let filters = vec![
+        format!(
+            "[{video_idx}:v]format=yuv420p,fps={fps}{scale_filter},drawtext=text='{escaped_text}':fontsize=64:fontcolor=white:x=(w-text_w)/2:y=(h-text_h)/2[{video_label}]"
+        ),
+        format!("[{audio_idx}:a]anull[{audio_label}]"),
+    ];
+
+    Ok((filters, duration_s))
+}
+
+/// Compile a project's filter graph, shared by [`compile`] and
+/// [`compile_multi_output`]: deduplicated inputs, the `;`-joined filter
+/// string, and the label the final composited video lands on (`outv` or
+/// `outv_txt` when text overlays are present, or the bumper-wrapped
+/// `outv_bumper` when `Project.intro`/`outro` are set -- the final audio
+/// label is always `outa`).
+///
+/// `intro`/`outro` splice in as plain concat entries around the fully
+/// composited core (clips, PiP, overlays, text) rather than into the
+/// pairwise `xfade`/`acrossfade` chain between clips -- a bumper has no
+/// `Transition` of its own to crossfade against.
+fn compile_graph(project: &Project) -> Result<(Vec<RenderInput>, String, String)> {
     // Find the primary (first) video track
     let video_tracks: Vec<&Track> = project
         .timeline
@@ -161,6 +389,7 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             inputs.push(RenderInput {
                 path: asset.path.clone(),
                 index: idx,
+                pre_args: vec![],
             });
         }
     }
@@ -180,6 +409,7 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             inputs.push(RenderInput {
                 path: asset.path.clone(),
                 index: idx,
+                pre_args: vec![],
             });
         }
     }
@@ -199,6 +429,7 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             inputs.push(RenderInput {
                 path: asset.path.clone(),
                 index: idx,
+                pre_args: vec![],
             });
         }
     }
@@ -206,18 +437,20 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
     // Build filter graph
     let mut filters: Vec<String> = Vec::new();
     let clip_count = video_clips.len();
+    let mut clip_durations_s: Vec<f64> = Vec::with_capacity(clip_count);
 
     let proj_w = project.settings.width;
     let proj_h = project.settings.height;
 
     for (i, clip) in video_clips.iter().enumerate() {
-        let (asset_id, source_in_us, source_out_us) = match clip {
+        let (asset_id, source_in_us, source_out_us, speed) = match clip {
             Item::VideoClip {
                 asset_id,
                 source_in_us,
                 source_out_us,
+                speed,
                 ..
-            } => (*asset_id, *source_in_us, *source_out_us),
+            } => (*asset_id, *source_in_us, *source_out_us, *speed),
             _ => unreachable!(),
         };
 
@@ -241,12 +474,26 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             String::new()
         };
 
+        // `setpts`/`atempo` re-time the trimmed segment by the clip's
+        // playback speed factor -- left as the plain PTS reset at speed 1.0.
+        let video_pts_filter = if (speed - 1.0).abs() > f64::EPSILON {
+            format!("setpts=(PTS-STARTPTS)/{speed}")
+        } else {
+            "setpts=PTS-STARTPTS".to_string()
+        };
+        let audio_tempo_filter = if (speed - 1.0).abs() > f64::EPSILON {
+            format!(",{}", atempo_chain(speed))
+        } else {
+            String::new()
+        };
+
         filters.push(format!(
-            "[{input_idx}:v]trim=start={start_s}:end={end_s},setpts=PTS-STARTPTS{scale_filter}[v{i}]"
+            "[{input_idx}:v]trim=start={start_s}:end={end_s},{video_pts_filter}{scale_filter}[v{i}]"
         ));
         filters.push(format!(
-            "[{input_idx}:a]atrim=start={start_s}:end={end_s},asetpts=PTS-STARTPTS[a{i}]"
+            "[{input_idx}:a]atrim=start={start_s}:end={end_s},asetpts=PTS-STARTPTS{audio_tempo_filter}[a{i}]"
         ));
+        clip_durations_s.push((end_s - start_s) / speed);
     }
 
     // Collect audio clips from audio tracks
@@ -276,22 +523,26 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             inputs.push(RenderInput {
                 path: asset.path.clone(),
                 index: idx,
+                pre_args: vec![],
             });
         }
     }
 
-    // Build concat filter
-    let mut concat_inputs = String::new();
-    for i in 0..clip_count {
-        concat_inputs.push_str(&format!("[v{i}][a{i}]"));
-    }
-
     let has_audio_overlay = !audio_clips.is_empty();
     let has_image_overlay = !image_overlays.is_empty();
     let has_pip = !pip_clips.is_empty();
-    let video_audio_out = if has_audio_overlay { "concat_a" } else { "outa" };
+    let has_bumpers = project.intro.is_some() || project.outro.is_some();
+    // When a bumper wraps the output, the core's audio lands on `outa_core`
+    // instead of the literal `outa` -- the bumper-wrapping concat at the end
+    // of this function is what produces the final `outa`.
+    let core_audio_label = if has_bumpers { "outa_core" } else { "outa" };
+    let video_audio_out = if has_audio_overlay {
+        "concat_a"
+    } else {
+        core_audio_label
+    };
 
-    // Determine concat video output label based on downstream stages
+    // Determine concat/chain video output label based on downstream stages
     let concat_video_label = if has_pip {
         "concatv"
     } else if has_image_overlay {
@@ -300,9 +551,66 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
         "outv"
     };
 
-    filters.push(format!(
-        "{concat_inputs}concat=n={clip_count}:v=1:a=1[{concat_video_label}][{video_audio_out}]"
-    ));
+    // Once the primary track records any `Transition`, every clip boundary
+    // is crossfaded instead of hard-cut: the trimmed `[v{i}]`/`[a{i}]` labels
+    // are chained pairwise through `xfade`/`acrossfade` rather than fed to
+    // `concat`. A boundary with no recorded `Transition` uses
+    // `DEFAULT_TRANSITION_DURATION_US` and the default transition kind.
+    if !primary_track.transitions.is_empty() && clip_count > 1 {
+        let mut prev_v = "v0".to_string();
+        let mut prev_a = "a0".to_string();
+        let mut accumulated_s = clip_durations_s[0];
+
+        for k in 1..clip_count {
+            let transition =
+                transition_between(primary_track, video_clips[k - 1].id(), video_clips[k].id());
+
+            let duration_s = transition
+                .map(|t| (t.region_us.1 - t.region_us.0).as_seconds())
+                .filter(|d| *d > 0.0)
+                .unwrap_or_else(|| DEFAULT_TRANSITION_DURATION_US.as_seconds());
+            let kind = transition.map(|t| t.kind).unwrap_or_default();
+
+            // Clamp so the crossfade never outlasts either adjacent clip.
+            let max_duration_s = clip_durations_s[k - 1].min(clip_durations_s[k]);
+            let duration_s = duration_s
+                .min(max_duration_s)
+                .max(MIN_TRANSITION_DURATION_S);
+
+            let offset_s = accumulated_s - duration_s;
+            let is_last = k == clip_count - 1;
+            let next_v = if is_last {
+                concat_video_label.to_string()
+            } else {
+                format!("vt{k}")
+            };
+            let next_a = if is_last {
+                video_audio_out.to_string()
+            } else {
+                format!("at{k}")
+            };
+
+            let transition_name = xfade_transition_name(kind);
+            filters.push(format!(
+                "[{prev_v}][v{k}]xfade=transition={transition_name}:duration={duration_s}:offset={offset_s}[{next_v}]"
+            ));
+            filters.push(format!(
+                "[{prev_a}][a{k}]acrossfade=d={duration_s}[{next_a}]"
+            ));
+
+            accumulated_s += clip_durations_s[k] - duration_s;
+            prev_v = next_v;
+            prev_a = next_a;
+        }
+    } else {
+        let mut concat_inputs = String::new();
+        for i in 0..clip_count {
+            concat_inputs.push_str(&format!("[v{i}][a{i}]"));
+        }
+        filters.push(format!(
+            "{concat_inputs}concat=n={clip_count}:v=1:a=1[{concat_video_label}][{video_audio_out}]"
+        ));
+    }
 
     // Apply PiP overlay filters
     if has_pip {
@@ -320,14 +628,15 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
     // Process audio overlay clips
     if has_audio_overlay {
         for (i, clip) in audio_clips.iter().enumerate() {
-            let (asset_id, source_in_us, source_out_us, volume) = match clip {
+            let (asset_id, source_in_us, source_out_us, volume, speed) = match clip {
                 Item::AudioClip {
                     asset_id,
                     source_in_us,
                     source_out_us,
                     volume,
+                    speed,
                     ..
-                } => (*asset_id, *source_in_us, *source_out_us, *volume),
+                } => (*asset_id, *source_in_us, *source_out_us, *volume, *speed),
                 _ => unreachable!(),
             };
 
@@ -335,13 +644,18 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
             let input_idx = path_to_index[&asset.path];
             let start_s = source_in_us.as_seconds();
             let end_s = source_out_us.as_seconds();
-            let duration_s = end_s - start_s;
+            let duration_s = (end_s - start_s) / speed;
             let delay_ms = clip.timeline_start_us().0 / 1000;
+            let tempo_filter = if (speed - 1.0).abs() > f64::EPSILON {
+                format!(",{}", atempo_chain(speed))
+            } else {
+                String::new()
+            };
 
-            // Trim, adjust volume, apply short fades, and delay to timeline position
+            // Trim, re-time, adjust volume, apply short fades, and delay to timeline position
             let fade_out_start = (duration_s - 0.1).max(0.0);
             filters.push(format!(
-                "[{input_idx}:a]atrim=start={start_s}:end={end_s},asetpts=PTS-STARTPTS,volume={volume},afade=t=in:d=0.1,afade=t=out:st={fade_out_start}:d=0.1,adelay={delay_ms}|{delay_ms}[ovla{i}]"
+                "[{input_idx}:a]atrim=start={start_s}:end={end_s},asetpts=PTS-STARTPTS{tempo_filter},volume={volume},afade=t=in:d=0.1,afade=t=out:st={fade_out_start}:d=0.1,adelay={delay_ms}|{delay_ms}[ovla{i}]"
             ));
         }
 
@@ -353,7 +667,7 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
         }
         let total_inputs = audio_overlay_count + 1;
         filters.push(format!(
-            "{amix_inputs}amix=inputs={total_inputs}:duration=longest:dropout_transition=0[outa]"
+            "{amix_inputs}amix=inputs={total_inputs}:duration=longest:dropout_transition=0[{core_audio_label}]"
         ));
     }
 
@@ -373,11 +687,7 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
                 ..
             } = overlay
             {
-                let asset = project
-                    .assets
-                    .iter()
-                    .find(|a| a.id == *asset_id)
-                    .unwrap();
+                let asset = project.assets.iter().find(|a| a.id == *asset_id).unwrap();
                 let input_idx = path_to_index[&asset.path];
 
                 let start_s = timeline_start_us.as_seconds();
@@ -422,44 +732,106 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
         .filter(|item| matches!(item, Item::TextOverlay { .. }))
         .collect();
 
+    let mut drawtext_filters = Vec::new();
+
     // Apply drawtext filters for text overlays
-    if !text_overlays.is_empty() {
-        let mut drawtext_filters = Vec::new();
-        for overlay in &text_overlays {
-            if let Item::TextOverlay {
-                text,
-                font_size,
-                color,
-                x,
-                y,
-                timeline_start_us,
-                duration_us,
-                ..
-            } = overlay
-            {
-                let start_s = timeline_start_us.as_seconds();
-                let end_s = (TimeUs(timeline_start_us.0 + duration_us.0)).as_seconds();
-                // Escape single quotes in text for ffmpeg
-                let escaped_text = text.replace('\'', "'\\''");
-                // Strip leading '#' from color for ffmpeg
-                let ffmpeg_color = color.strip_prefix('#').unwrap_or(color);
-                drawtext_filters.push(format!(
-                    "drawtext=text='{escaped_text}':fontsize={font_size}:fontcolor=0x{ffmpeg_color}:x={x}:y={y}:enable='between(t,{start_s},{end_s})'"
-                ));
-            }
+    for overlay in &text_overlays {
+        if let Item::TextOverlay {
+            text,
+            font_size,
+            color,
+            x,
+            y,
+            timeline_start_us,
+            duration_us,
+            ..
+        } = overlay
+        {
+            let start_s = timeline_start_us.as_seconds();
+            let end_s = (TimeUs(timeline_start_us.0 + duration_us.0)).as_seconds();
+            let escaped_text = escape_drawtext_text(text);
+            drawtext_filters.push(format!(
+                "drawtext=text='{escaped_text}':fontsize={font_size}:fontcolor={}:x={x}:y={y}:enable='between(t,{start_s},{end_s})'",
+                ffmpeg_drawtext_color(color)
+            ));
+        }
+    }
+
+    // Burn in cues from subtitle tracks as drawtext entries with an opaque
+    // background box, appended to the same drawtext chain as text overlays.
+    for track in &project.timeline.tracks {
+        if track.kind != TrackKind::Subtitles {
+            continue;
+        }
+        let Some(subtitle_track) = &track.subtitles else {
+            continue;
+        };
+        let contents = std::fs::read_to_string(&subtitle_track.path)?;
+        let cues = crate::subtitles::parse_subtitles(&contents)?;
+        for cue in cues {
+            drawtext_filters.push(subtitle_cue_filter(&cue, &subtitle_track.style));
         }
+    }
+
+    if !drawtext_filters.is_empty() {
         let drawtext_chain = drawtext_filters.join(",");
         filters.push(format!("[outv]{drawtext_chain}[outv_txt]"));
     }
 
-    let final_video_label = if !text_overlays.is_empty() {
+    let core_video_label = if !drawtext_filters.is_empty() {
         "outv_txt"
     } else {
         "outv"
     };
 
+    // Splice the intro/outro bumpers, if any, around the composited core as
+    // plain concat entries -- producing the `outv_bumper`/`outa` labels that
+    // `compile`'s output args then map to.
+    let final_video_label = if has_bumpers {
+        let mut segment_labels: Vec<(String, String)> = Vec::new();
+
+        if let Some(intro) = &project.intro {
+            let (bumper_filters, _duration_s) =
+                compile_bumper(project, intro, "vintro", "aintro", &mut inputs)?;
+            filters.extend(bumper_filters);
+            segment_labels.push(("vintro".to_string(), "aintro".to_string()));
+        }
+
+        segment_labels.push((core_video_label.to_string(), core_audio_label.to_string()));
+
+        if let Some(outro) = &project.outro {
+            let (bumper_filters, _duration_s) =
+                compile_bumper(project, outro, "voutro", "aoutro", &mut inputs)?;
+            filters.extend(bumper_filters);
+            segment_labels.push(("voutro".to_string(), "aoutro".to_string()));
+        }
+
+        let mut concat_inputs = String::new();
+        for (v, a) in &segment_labels {
+            concat_inputs.push_str(&format!("[{v}][{a}]"));
+        }
+        let n = segment_labels.len();
+        filters.push(format!(
+            "{concat_inputs}concat=n={n}:v=1:a=1[outv_bumper][outa]"
+        ));
+
+        "outv_bumper"
+    } else {
+        core_video_label
+    };
+
     let filter_graph = filters.join(";");
 
+    Ok((inputs, filter_graph, final_video_label.to_string()))
+}
+
+/// Compile a project into an ffmpeg render plan.
+///
+/// For v0.1: concatenate video clips with trim/setpts/atrim/asetpts/concat filters.
+/// PiP: additional video tracks overlay on the primary video track.
+pub fn compile(project: &Project) -> Result<RenderPlan> {
+    let (inputs, filter_graph, final_video_label) = compile_graph(project)?;
+
     // Build output args
     let fps = project.settings.fps;
     let output_args = vec![
@@ -490,14 +862,142 @@ pub fn compile(project: &Project) -> Result<RenderPlan> {
         filter_graph,
         output_args,
         output_path: PathBuf::from("output.mp4"),
+        outputs: Vec::new(),
+        hls: None,
+    })
+}
+
+/// One rendition to emit alongside its siblings from [`compile_multi_output`]
+/// -- a resolution/quality target tapped off the same shared filter graph
+/// via `split`/`scale`, rather than re-running the whole graph per
+/// resolution the way re-invoking [`compile`] per target would.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputTarget {
+    pub width: u32,
+    pub height: u32,
+    pub crf: u32,
+    /// Target video bitrate (e.g. `"2500k"`), for a bitrate-capped rendition
+    /// in addition to (or instead of) the `crf` above. `None` leaves the
+    /// encoder unconstrained beyond `crf`.
+    pub video_bitrate: Option<String>,
+    pub output_path: PathBuf,
+}
+
+/// Compile a project into a render plan that emits every target in
+/// `outputs` from a single shared filter graph and one ffmpeg invocation:
+/// the final composited video is split `outputs.len()` ways, each branch
+/// scaled to its target's dimensions, and each gets its own `-map`/`-c:v`/
+/// `-crf`/output-path group in [`build_ffmpeg_args`]. All outputs share the
+/// project's audio mix and the same input frame timeline, so [`execute`]
+/// only has one progress stream to track but must confirm every output file
+/// actually landed.
+///
+/// Falls back to plain [`compile`] (single implicit output, `crf` 23) when
+/// `outputs` is empty or has exactly one entry -- `split`ting into a single
+/// branch would just be a no-op indirection.
+pub fn compile_multi_output(project: &Project, outputs: &[OutputTarget]) -> Result<RenderPlan> {
+    if outputs.len() <= 1 {
+        let mut plan = compile(project)?;
+        if let Some(target) = outputs.first() {
+            plan.output_path = target.output_path.clone();
+        }
+        return Ok(plan);
+    }
+
+    let (inputs, mut filter_graph, final_video_label) = compile_graph(project)?;
+
+    let n = outputs.len();
+    let split_outputs: String = (0..n).map(|i| format!("[split{i}]")).collect();
+    filter_graph.push_str(&format!(";[{final_video_label}]split={n}{split_outputs}"));
+    for (i, target) in outputs.iter().enumerate() {
+        filter_graph.push_str(&format!(
+            ";[split{i}]scale={}:{}[scaled{i}]",
+            target.width, target.height
+        ));
+    }
+
+    Ok(RenderPlan {
+        inputs,
+        filter_graph,
+        output_args: Vec::new(),
+        output_path: PathBuf::new(),
+        outputs: outputs.to_vec(),
+        hls: None,
+    })
+}
+
+/// One rendition ("rung") of an HLS adaptive-bitrate ladder -- a
+/// resolution/bitrate tier the player switches between based on available
+/// bandwidth. See [`compile_hls`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HlsRung {
+    pub width: u32,
+    pub height: u32,
+    /// e.g. `"5000k"`.
+    pub video_bitrate: String,
+    /// e.g. `"128k"`.
+    pub audio_bitrate: String,
+}
+
+/// An HLS package request carried on [`RenderPlan::hls`] -- see
+/// [`compile_hls`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HlsPackage {
+    pub ladder: Vec<HlsRung>,
+    /// Directory the segments, per-rendition media playlists, and
+    /// `master.m3u8` are written to.
+    pub output_dir: PathBuf,
+}
+
+/// Compile a project into an HLS (`.m3u8`) adaptive-bitrate package instead
+/// of a single progressive file: one `-map`/`-c:v:N`/`-b:v:N` group per
+/// `ladder` rung, each scaled off the same shared filter graph with the same
+/// `scale=...:force_original_aspect_ratio=decrease,pad=...` letterboxing
+/// [`compile_graph`] uses for source clips, packaged by a single `-f hls`
+/// muxer invocation (`-var_stream_map`, fragmented-MP4 segments, one media
+/// playlist per rung, and a `master.m3u8` tying them together) -- see
+/// [`build_ffmpeg_args`]. `ladder` should be non-empty; the caller picks the
+/// rungs (e.g. 1080p/720p/480p).
+pub fn compile_hls(
+    project: &Project,
+    ladder: Vec<HlsRung>,
+    output_dir: PathBuf,
+) -> Result<RenderPlan> {
+    let (inputs, mut filter_graph, final_video_label) = compile_graph(project)?;
+
+    let n = ladder.len();
+    let split_outputs: String = (0..n).map(|i| format!("[hls_split{i}]")).collect();
+    filter_graph.push_str(&format!(";[{final_video_label}]split={n}{split_outputs}"));
+    for (i, rung) in ladder.iter().enumerate() {
+        let (w, h) = (rung.width, rung.height);
+        filter_graph.push_str(&format!(
+            ";[hls_split{i}]scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2[hls{i}]"
+        ));
+    }
+
+    Ok(RenderPlan {
+        inputs,
+        filter_graph,
+        output_args: Vec::new(),
+        output_path: PathBuf::new(),
+        outputs: Vec::new(),
+        hls: Some(HlsPackage { ladder, output_dir }),
     })
 }
 
 /// Build ffmpeg args from a render plan.
+///
+/// When `plan.hls` is set (a [`compile_hls`] plan), emits one
+/// `-map [hls{i}] -map [outa] -c:v:i ... -b:v:i ...` group per ladder rung
+/// followed by the `-var_stream_map`/`-f hls` muxer args instead of the
+/// single `output_args`/`output_path` group. Otherwise, when `plan.outputs`
+/// is non-empty (a [`compile_multi_output`] plan), emits one
+/// `-map [scaled{i}] -map [outa] -c:v ... -crf ... <path>` group per target.
 pub fn build_ffmpeg_args(plan: &RenderPlan) -> Vec<String> {
     let mut args = vec!["-y".to_string()];
 
     for input in &plan.inputs {
+        args.extend(input.pre_args.clone());
         args.push("-i".to_string());
         args.push(input.path.to_string_lossy().to_string());
     }
@@ -505,62 +1005,270 @@ pub fn build_ffmpeg_args(plan: &RenderPlan) -> Vec<String> {
     args.push("-filter_complex".to_string());
     args.push(plan.filter_graph.clone());
 
-    args.extend(plan.output_args.clone());
+    if let Some(hls) = &plan.hls {
+        for (i, rung) in hls.ladder.iter().enumerate() {
+            args.push("-map".to_string());
+            args.push(format!("[hls{i}]"));
+            args.push("-map".to_string());
+            args.push("[outa]".to_string());
+            args.push(format!("-c:v:{i}"));
+            args.push("libx264".to_string());
+            args.push(format!("-b:v:{i}"));
+            args.push(rung.video_bitrate.clone());
+            args.push(format!("-c:a:{i}"));
+            args.push("aac".to_string());
+            args.push(format!("-b:a:{i}"));
+            args.push(rung.audio_bitrate.clone());
+        }
 
-    args.push(plan.output_path.to_string_lossy().to_string());
+        let var_stream_map = hls
+            .ladder
+            .iter()
+            .enumerate()
+            .map(|(i, _)| format!("v:{i},a:{i},name:v{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        args.push("-var_stream_map".to_string());
+        args.push(var_stream_map);
+        args.push("-f".to_string());
+        args.push("hls".to_string());
+        args.push("-hls_segment_type".to_string());
+        args.push("fmp4".to_string());
+        args.push("-master_pl_name".to_string());
+        args.push("master.m3u8".to_string());
+        args.push("-hls_segment_filename".to_string());
+        args.push(
+            hls.output_dir
+                .join("v%v/seg%d.m4s")
+                .to_string_lossy()
+                .to_string(),
+        );
+        args.push(
+            hls.output_dir
+                .join("v%v/playlist.m3u8")
+                .to_string_lossy()
+                .to_string(),
+        );
+    } else if plan.outputs.is_empty() {
+        args.extend(plan.output_args.clone());
+        args.push(plan.output_path.to_string_lossy().to_string());
+    } else {
+        for (i, target) in plan.outputs.iter().enumerate() {
+            args.push("-map".to_string());
+            args.push(format!("[scaled{i}]"));
+            args.push("-map".to_string());
+            args.push("[outa]".to_string());
+            args.push("-c:v".to_string());
+            args.push("libx264".to_string());
+            args.push("-crf".to_string());
+            args.push(target.crf.to_string());
+            if let Some(bitrate) = &target.video_bitrate {
+                args.push("-b:v".to_string());
+                args.push(bitrate.clone());
+            }
+            args.push("-c:a".to_string());
+            args.push("aac".to_string());
+            args.push("-pix_fmt".to_string());
+            args.push("yuv420p".to_string());
+            args.push(target.output_path.to_string_lossy().to_string());
+        }
+    }
 
     args
 }
 
+/// Resource limits applied to the ffmpeg child process spawned by
+/// [`execute_with_options`], so a long or malformed filter graph can't take
+/// down a shared server/multi-tenant host. Every field is optional and
+/// leaves that dimension unconstrained when `None`.
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteOptions {
+    /// Memory ceiling (e.g. `"2G"`) passed to `systemd-run --scope -p
+    /// MemoryMax=...`. Ignored -- falling back to a plain spawn -- when
+    /// `systemd-run` isn't on `PATH` (non-Linux or minimal containers).
+    pub memory_max: Option<String>,
+    /// Wall-clock timeout for the whole ffmpeg run. On expiry the child is
+    /// killed and `execute_with_options` returns [`RenderError::Timeout`].
+    pub timeout: Option<std::time::Duration>,
+    /// CPU/thread limit, passed through to ffmpeg as `-threads N`.
+    pub cpu_limit: Option<u32>,
+    /// Run ffmpeg with `-progress pipe:1 -nostats` and parse the structured
+    /// `key=value` blocks on stdout via [`parse_progress_block`], instead of
+    /// scraping the human-readable `\r`-delimited stderr stats line with
+    /// [`parse_progress`]. Off by default so existing callers keep the
+    /// stderr path; the stderr stream is still drained (and kept for error
+    /// diagnostics on failure) either way.
+    pub structured_progress: bool,
+}
+
 /// Execute a render plan by spawning ffmpeg.
 /// Sends progress updates via the channel.
 pub async fn execute(
     plan: &RenderPlan,
     progress_tx: tokio::sync::watch::Sender<RenderProgress>,
     total_duration_us: TimeUs,
+) -> Result<()> {
+    execute_with_options(
+        plan,
+        progress_tx,
+        total_duration_us,
+        ExecuteOptions::default(),
+    )
+    .await
+}
+
+/// Check whether `systemd-run` is available on `PATH`, to decide whether an
+/// [`ExecuteOptions::memory_max`] ceiling can be enforced via a cgroup scope.
+fn systemd_run_available() -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join("systemd-run").is_file()))
+        .unwrap_or(false)
+}
+
+/// Execute a render plan by spawning ffmpeg, same as [`execute`] but with
+/// the resource limits in `options` applied: a memory ceiling enforced via
+/// `systemd-run --scope -p MemoryMax=...` when available, a thread cap
+/// passed to ffmpeg as `-threads`, and a wall-clock timeout around the
+/// stderr-reading loop that kills the child and returns
+/// [`RenderError::Timeout`] on expiry.
+pub async fn execute_with_options(
+    plan: &RenderPlan,
+    progress_tx: tokio::sync::watch::Sender<RenderProgress>,
+    total_duration_us: TimeUs,
+    options: ExecuteOptions,
 ) -> Result<()> {
     use std::process::Stdio;
     use tokio::io::{AsyncBufReadExt, BufReader};
     use tokio::process::Command;
 
+    let mut args = build_ffmpeg_args(plan);
+    if let Some(threads) = options.cpu_limit {
+        args.splice(1..1, ["-threads".to_string(), threads.to_string()]);
+    }
+    if options.structured_progress {
+        args.splice(
+            1..1,
+            [
+                "-progress".to_string(),
+                "pipe:1".to_string(),
+                "-nostats".to_string(),
+            ],
+        );
+    }
 
-    let args = build_ffmpeg_args(plan);
+    let stdout_mode = if options.structured_progress {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
 
-    let mut child = Command::new("ffmpeg")
-        .args(&args)
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                RenderError::FfmpegNotFound
-            } else {
-                RenderError::Io(e)
-            }
-        })?;
+    let use_cgroup = options.memory_max.is_some() && systemd_run_available();
+    let mut child = if use_cgroup {
+        let memory_max = options.memory_max.as_deref().unwrap();
+        Command::new("systemd-run")
+            .arg("--scope")
+            .arg("-p")
+            .arg(format!("MemoryMax={memory_max}"))
+            .arg("--")
+            .arg("ffmpeg")
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(stdout_mode)
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(RenderError::Io)?
+    } else {
+        Command::new("ffmpeg")
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(stdout_mode)
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    RenderError::FfmpegNotFound
+                } else {
+                    RenderError::Io(e)
+                }
+            })?
+    };
 
     let stderr = child.stderr.take().unwrap();
-    let mut reader = BufReader::new(stderr);
-
+    let mut stderr_reader = BufReader::new(stderr);
     let total_secs = total_duration_us.as_seconds();
 
-    let mut buf = Vec::new();
-    loop {
-        buf.clear();
-        let n = reader
-            .read_until(b'\r', &mut buf)
-            .await
-            .map_err(RenderError::Io)?;
-        if n == 0 {
-            break;
+    // The stderr pipe must always be drained, even when progress is read
+    // from the structured stdout stream instead, or ffmpeg blocks once the
+    // OS pipe buffer fills.
+    let stderr_drain = async {
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let n = stderr_reader
+                .read_until(b'\r', &mut buf)
+                .await
+                .map_err(RenderError::Io)?;
+            if n == 0 {
+                break;
+            }
+            if !options.structured_progress {
+                let chunk = String::from_utf8_lossy(&buf);
+                for segment in chunk.split(['\r', '\n']) {
+                    if let Some(progress) = parse_progress(segment.trim(), total_secs) {
+                        let _ = progress_tx.send(progress);
+                    }
+                }
+            }
         }
-        let chunk = String::from_utf8_lossy(&buf);
-        for segment in chunk.split(['\r', '\n']) {
-            if let Some(progress) = parse_progress(segment.trim(), total_secs) {
-                let _ = progress_tx.send(progress);
+        Ok::<(), RenderError>(())
+    };
+
+    let stdout_read = async {
+        if !options.structured_progress {
+            return Ok::<(), RenderError>(());
+        }
+        let stdout = child.stdout.take().unwrap();
+        let mut stdout_reader = BufReader::new(stdout);
+        let mut block = String::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let n = stdout_reader
+                .read_line(&mut line)
+                .await
+                .map_err(RenderError::Io)?;
+            if n == 0 {
+                break;
+            }
+            let is_boundary = line.trim() == "progress=continue" || line.trim() == "progress=end";
+            block.push_str(&line);
+            if is_boundary {
+                if let Some(progress) = parse_progress_block(&block, total_secs) {
+                    let _ = progress_tx.send(progress);
+                }
+                block.clear();
+            }
+        }
+        Ok(())
+    };
+
+    // Stdout (structured progress) and stderr (always drained, either as the
+    // legacy progress source or just to avoid blocking ffmpeg) are read
+    // concurrently so neither pipe's buffer can back up and stall the child.
+    let read_loop = async {
+        let (stdout_result, stderr_result) = tokio::join!(stdout_read, stderr_drain);
+        stdout_result?;
+        stderr_result
+    };
+
+    match options.timeout {
+        Some(duration) => {
+            if tokio::time::timeout(duration, read_loop).await.is_err() {
+                let _ = child.kill().await;
+                return Err(RenderError::Timeout(duration));
             }
         }
+        None => read_loop.await?,
     }
 
     let status = child.wait().await.map_err(RenderError::Io)?;
@@ -570,6 +1278,15 @@ pub async fn execute(
         )));
     }
 
+    for target in &plan.outputs {
+        if !target.output_path.exists() {
+            return Err(RenderError::FfmpegFailed(format!(
+                "ffmpeg reported success but {} was not produced",
+                target.output_path.display()
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -618,6 +1335,7 @@ pub fn parse_progress(line: &str, total_secs: f64) -> Option<RenderProgress> {
         fps,
         speed: speed_str,
         eta_seconds,
+        total_size: None,
     })
 }
 
@@ -649,6 +1367,71 @@ fn parse_time_str(s: &str) -> Option<f64> {
     Some(hours * 3600.0 + mins * 60.0 + secs)
 }
 
+/// Parse one block of ffmpeg's machine-readable `-progress pipe:1` output:
+/// newline-separated `key=value` pairs terminated by a `progress=continue` or
+/// `progress=end` line. Unlike [`parse_progress`] this format is stable
+/// across ffmpeg versions/locales, so it's preferred when wired up via
+/// [`ExecuteOptions::structured_progress`].
+///
+/// Example block:
+/// ```text
+/// frame=123
+/// fps=60.00
+/// out_time_us=1062050
+/// total_size=456789
+/// speed=1.5x
+/// progress=continue
+/// ```
+pub fn parse_progress_block(block: &str, total_secs: f64) -> Option<RenderProgress> {
+    if !block.contains("progress=") {
+        return None;
+    }
+
+    let value_of =
+        |key: &str| -> Option<&str> { block.lines().find_map(|line| line.strip_prefix(key)) };
+
+    let frame = value_of("frame=")
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0);
+    let fps = value_of("fps=")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    let speed = value_of("speed=").unwrap_or_default().to_string();
+    let total_size = value_of("total_size=").and_then(|v| v.parse::<u64>().ok());
+
+    let time_secs = value_of("out_time_us=")
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|us| us / 1_000_000.0)
+        .or_else(|| {
+            value_of("out_time_ms=")
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|ms| ms / 1_000.0)
+        })
+        .unwrap_or(0.0);
+
+    let percent = if total_secs > 0.0 {
+        (time_secs / total_secs * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+
+    let speed_factor = speed.trim_end_matches('x').parse::<f64>().unwrap_or(0.0);
+    let eta_seconds = if speed_factor > 0.0 && total_secs > time_secs {
+        Some((total_secs - time_secs) / speed_factor)
+    } else {
+        None
+    };
+
+    Some(RenderProgress {
+        percent,
+        frame,
+        fps,
+        speed,
+        eta_seconds,
+        total_size,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -668,11 +1451,21 @@ mod tests {
                 duration_us: TimeUs::from_seconds(30.0),
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 codec: "h264".to_string(),
                 audio_channels: 2,
                 audio_sample_rate: 48000,
+                keyframes_us: vec![],
+                streams: vec![],
+                rotation_deg: 0,
+                display_width: 0,
+                display_height: 0,
+                metadata: Default::default(),
+                frame_count: None,
+                color: Default::default(),
             }),
+            tags: Default::default(),
+            source_url: None,
         }
     }
 
@@ -683,7 +1476,7 @@ mod tests {
             settings: ProjectSettings {
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 sample_rate: 48000,
             },
             assets,
@@ -692,9 +1485,15 @@ mod tests {
                     id: Uuid::new_v4(),
                     kind: TrackKind::Video,
                     items: clips,
+                    transitions: vec![],
+                    subtitles: None,
                 }],
                 markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
             },
+            intro: None,
+            outro: None,
         }
     }
 
@@ -706,14 +1505,18 @@ mod tests {
             settings: ProjectSettings {
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 sample_rate: 48000,
             },
             assets: vec![],
             timeline: Timeline {
                 tracks: vec![],
                 markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
             },
+            intro: None,
+            outro: None,
         };
         let result = compile(&project);
         assert!(result.is_err());
@@ -735,6 +1538,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(1.0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![asset]);
@@ -765,6 +1571,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(3.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let clip2 = Item::VideoClip {
@@ -774,6 +1583,9 @@ mod tests {
             timeline_start_us: TimeUs::from_seconds(3.0),
             source_in_us: TimeUs::from_seconds(2.0),
             source_out_us: TimeUs::from_seconds(7.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip1, clip2], vec![asset1, asset2]);
@@ -785,17 +1597,201 @@ mod tests {
     }
 
     #[test]
-    fn compile_preserves_trim_ranges() {
-        let asset_id = Uuid::new_v4();
-        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+    fn compile_with_transition_uses_xfade_chain_instead_of_concat() {
+        let asset_id_1 = Uuid::new_v4();
+        let asset_id_2 = Uuid::new_v4();
+        let asset1 = make_asset(asset_id_1, "/tmp/clip1.mp4");
+        let asset2 = make_asset(asset_id_2, "/tmp/clip2.mp4");
 
-        let clip = Item::VideoClip {
-            id: Uuid::new_v4(),
-            asset_id,
+        let clip1_id = Uuid::new_v4();
+        let clip2_id = Uuid::new_v4();
+
+        let clip1 = Item::VideoClip {
+            id: clip1_id,
+            asset_id: asset_id_1,
             track_id: Uuid::new_v4(),
             timeline_start_us: TimeUs(0),
-            source_in_us: TimeUs::from_seconds(2.5),
-            source_out_us: TimeUs::from_seconds(8.75),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(3.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::from_seconds(0.5),
+        };
+
+        let clip2 = Item::VideoClip {
+            id: clip2_id,
+            asset_id: asset_id_2,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs::from_seconds(2.5),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(4.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::from_seconds(0.5),
+            fade_out_us: TimeUs::ZERO,
+        };
+
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            settings: ProjectSettings {
+                width: 1920,
+                height: 1080,
+                fps: FrameRate::whole(30),
+                sample_rate: 48000,
+            },
+            assets: vec![asset1, asset2],
+            timeline: Timeline {
+                tracks: vec![Track {
+                    id: Uuid::new_v4(),
+                    kind: TrackKind::Video,
+                    items: vec![clip1, clip2],
+                    transitions: vec![Transition {
+                        out_item: clip1_id,
+                        in_item: clip2_id,
+                        region_us: (TimeUs::from_seconds(2.5), TimeUs::from_seconds(3.0)),
+                        kind: TransitionKind::Fade,
+                    }],
+                    subtitles: None,
+                }],
+                markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
+            },
+            intro: None,
+            outro: None,
+        };
+
+        let plan = compile(&project).unwrap();
+
+        assert!(!plan.filter_graph.contains("concat="));
+        // duration = 0.5s, offset = clip1's 3s duration - 0.5s = 2.5
+        assert!(plan
+            .filter_graph
+            .contains("[v0][v1]xfade=transition=fade:duration=0.5:offset=2.5[outv]"));
+        assert!(plan.filter_graph.contains("[a0][a1]acrossfade=d=0.5[outa]"));
+    }
+
+    #[test]
+    fn compile_chains_three_clips_through_consecutive_xfades() {
+        let asset_id_1 = Uuid::new_v4();
+        let asset_id_2 = Uuid::new_v4();
+        let asset_id_3 = Uuid::new_v4();
+        let asset1 = make_asset(asset_id_1, "/tmp/clip1.mp4");
+        let asset2 = make_asset(asset_id_2, "/tmp/clip2.mp4");
+        let asset3 = make_asset(asset_id_3, "/tmp/clip3.mp4");
+
+        let clip1_id = Uuid::new_v4();
+        let clip2_id = Uuid::new_v4();
+        let clip3_id = Uuid::new_v4();
+
+        let clip1 = Item::VideoClip {
+            id: clip1_id,
+            asset_id: asset_id_1,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(3.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::from_seconds(0.5),
+        };
+        let clip2 = Item::VideoClip {
+            id: clip2_id,
+            asset_id: asset_id_2,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs::from_seconds(2.5),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(4.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::from_seconds(0.5),
+            fade_out_us: TimeUs::from_seconds(0.5),
+        };
+        let clip3 = Item::VideoClip {
+            id: clip3_id,
+            asset_id: asset_id_3,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs::from_seconds(6.0),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(3.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::from_seconds(0.5),
+            fade_out_us: TimeUs::ZERO,
+        };
+
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            settings: ProjectSettings {
+                width: 1920,
+                height: 1080,
+                fps: FrameRate::whole(30),
+                sample_rate: 48000,
+            },
+            assets: vec![asset1, asset2, asset3],
+            timeline: Timeline {
+                tracks: vec![Track {
+                    id: Uuid::new_v4(),
+                    kind: TrackKind::Video,
+                    items: vec![clip1, clip2, clip3],
+                    transitions: vec![
+                        Transition {
+                            out_item: clip1_id,
+                            in_item: clip2_id,
+                            region_us: (TimeUs::from_seconds(2.5), TimeUs::from_seconds(3.0)),
+                            kind: TransitionKind::Fade,
+                        },
+                        Transition {
+                            out_item: clip2_id,
+                            in_item: clip3_id,
+                            region_us: (TimeUs::from_seconds(6.0), TimeUs::from_seconds(6.5)),
+                            kind: TransitionKind::Fade,
+                        },
+                    ],
+                    subtitles: None,
+                }],
+                markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
+            },
+            intro: None,
+            outro: None,
+        };
+
+        let plan = compile(&project).unwrap();
+
+        assert!(!plan.filter_graph.contains("concat="));
+        // First xfade: offset = clip1's 3s duration - 0.5s = 2.5, landing on
+        // an intermediate label rather than `outv`/`outa` since a second
+        // clip still follows.
+        assert!(plan
+            .filter_graph
+            .contains("[v0][v1]xfade=transition=fade:duration=0.5:offset=2.5[vt1]"));
+        assert!(plan.filter_graph.contains("[a0][a1]acrossfade=d=0.5[at1]"));
+        // Second xfade chains off the first: offset = 3 (clip1's run so
+        // far) + (clip2's 4s duration - the 0.5s already consumed) - 0.5s.
+        assert!(plan
+            .filter_graph
+            .contains("[vt1][v2]xfade=transition=fade:duration=0.5:offset=6[outv]"));
+        assert!(plan
+            .filter_graph
+            .contains("[at1][a2]acrossfade=d=0.5[outa]"));
+    }
+
+    #[test]
+    fn compile_preserves_trim_ranges() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(2.5),
+            source_out_us: TimeUs::from_seconds(8.75),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![asset]);
@@ -817,6 +1813,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(3.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let clip2 = Item::VideoClip {
@@ -826,6 +1825,9 @@ mod tests {
             timeline_start_us: TimeUs::from_seconds(3.0),
             source_in_us: TimeUs::from_seconds(5.0),
             source_out_us: TimeUs::from_seconds(8.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip1, clip2], vec![asset]);
@@ -849,11 +1851,21 @@ mod tests {
                 duration_us: TimeUs::from_seconds(30.0),
                 width: 1280,
                 height: 720,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 codec: "h264".to_string(),
                 audio_channels: 2,
                 audio_sample_rate: 48000,
+                keyframes_us: vec![],
+                streams: vec![],
+                rotation_deg: 0,
+                display_width: 0,
+                display_height: 0,
+                metadata: Default::default(),
+                frame_count: None,
+                color: Default::default(),
             }),
+            tags: Default::default(),
+            source_url: None,
         };
 
         let clip = Item::VideoClip {
@@ -863,6 +1875,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![asset]);
@@ -885,6 +1900,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![asset]);
@@ -907,6 +1925,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = Project {
@@ -915,7 +1936,7 @@ mod tests {
             settings: ProjectSettings {
                 width: 3840,
                 height: 2160,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 sample_rate: 48000,
             },
             assets: vec![asset],
@@ -924,9 +1945,15 @@ mod tests {
                     id: Uuid::new_v4(),
                     kind: TrackKind::Video,
                     items: vec![clip],
+                    transitions: vec![],
+                    subtitles: None,
                 }],
                 markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
             },
+            intro: None,
+            outro: None,
         };
 
         let plan = compile(&project).unwrap();
@@ -952,11 +1979,21 @@ mod tests {
                 duration_us: TimeUs::from_seconds(60.0),
                 width: 0,
                 height: 0,
-                fps: 0.0,
+                fps: FrameRate::new(0, 1),
                 codec: "mp3".to_string(),
                 audio_channels: 2,
                 audio_sample_rate: 44100,
+                keyframes_us: vec![],
+                streams: vec![],
+                rotation_deg: 0,
+                display_width: 0,
+                display_height: 0,
+                metadata: Default::default(),
+                frame_count: None,
+                color: Default::default(),
             }),
+            tags: Default::default(),
+            source_url: None,
         };
 
         let video_clip = Item::VideoClip {
@@ -966,6 +2003,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(10.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let audio_clip = Item::AudioClip {
@@ -976,6 +2016,9 @@ mod tests {
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(8.0),
             volume: 0.5,
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = Project {
@@ -984,7 +2027,7 @@ mod tests {
             settings: ProjectSettings {
                 width: 1920,
                 height: 1080,
-                fps: 30.0,
+                fps: FrameRate::whole(30),
                 sample_rate: 48000,
             },
             assets: vec![video_asset, audio_asset],
@@ -994,15 +2037,23 @@ mod tests {
                         id: video_track_id,
                         kind: TrackKind::Video,
                         items: vec![video_clip],
+                        transitions: vec![],
+                        subtitles: None,
                     },
                     Track {
                         id: audio_track_id,
                         kind: TrackKind::Audio,
                         items: vec![audio_clip],
+                        transitions: vec![],
+                        subtitles: None,
                     },
                 ],
                 markers: vec![],
+                config: TimelineConfig::default(),
+                groups: vec![],
             },
+            intro: None,
+            outro: None,
         };
 
         let plan = compile(&project).unwrap();
@@ -1039,6 +2090,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs::from_seconds(0.0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![asset]);
@@ -1058,6 +2112,9 @@ mod tests {
             timeline_start_us: TimeUs(0),
             source_in_us: TimeUs(0),
             source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
         };
 
         let project = make_project_with_clips(vec![clip], vec![]);
@@ -1072,19 +2129,28 @@ mod tests {
                 RenderInput {
                     path: PathBuf::from("/tmp/a.mp4"),
                     index: 0,
+                    pre_args: vec![],
                 },
                 RenderInput {
                     path: PathBuf::from("/tmp/b.mp4"),
                     index: 1,
+                    pre_args: vec![],
                 },
             ],
-            filter_graph: "[0:v]trim=0:5[v0];[0:a]atrim=0:5[a0];[v0][a0]concat=n=1:v=1:a=1[outv][outa]".to_string(),
+            filter_graph:
+                "[0:v]trim=0:5[v0];[0:a]atrim=0:5[a0];[v0][a0]concat=n=1:v=1:a=1[outv][outa]"
+                    .to_string(),
             output_args: vec![
-                "-map".to_string(), "[outv]".to_string(),
-                "-map".to_string(), "[outa]".to_string(),
-                "-c:v".to_string(), "libx264".to_string(),
+                "-map".to_string(),
+                "[outv]".to_string(),
+                "-map".to_string(),
+                "[outa]".to_string(),
+                "-c:v".to_string(),
+                "libx264".to_string(),
             ],
             output_path: PathBuf::from("/tmp/out.mp4"),
+            outputs: vec![],
+            hls: None,
         };
 
         let args = build_ffmpeg_args(&plan);
@@ -1102,6 +2168,345 @@ mod tests {
         assert_eq!(args.last().unwrap(), "/tmp/out.mp4");
     }
 
+    #[test]
+    fn build_ffmpeg_args_emits_one_map_group_per_output_target() {
+        let plan = RenderPlan {
+            inputs: vec![RenderInput {
+                path: PathBuf::from("/tmp/a.mp4"),
+                index: 0,
+                pre_args: vec![],
+            }],
+            filter_graph: "[0:v]null[base];[base]split=2[split0][split1];\
+                 [split0]scale=1920:1080[scaled0];[split1]scale=1280:720[scaled1]"
+                .to_string(),
+            output_args: vec![],
+            output_path: PathBuf::new(),
+            outputs: vec![
+                OutputTarget {
+                    width: 1920,
+                    height: 1080,
+                    crf: 20,
+                    video_bitrate: None,
+                    output_path: PathBuf::from("/tmp/out-1080p.mp4"),
+                },
+                OutputTarget {
+                    width: 1280,
+                    height: 720,
+                    crf: 23,
+                    video_bitrate: Some("2500k".to_string()),
+                    output_path: PathBuf::from("/tmp/out-720p.mp4"),
+                },
+            ],
+            hls: None,
+        };
+
+        let args = build_ffmpeg_args(&plan);
+
+        assert!(args.contains(&"[scaled0]".to_string()));
+        assert!(args.contains(&"[scaled1]".to_string()));
+        assert!(args.contains(&"/tmp/out-1080p.mp4".to_string()));
+        assert!(args.contains(&"/tmp/out-720p.mp4".to_string()));
+        assert!(args.contains(&"-b:v".to_string()));
+        assert!(args.contains(&"2500k".to_string()));
+        // No bitrate flag should precede the first (bitrate-less) target's path.
+        let first_crf_pos = args.iter().position(|a| a == "20").unwrap();
+        let bv_pos = args.iter().position(|a| a == "-b:v").unwrap();
+        assert!(bv_pos > first_crf_pos);
+    }
+
+    #[test]
+    fn compile_multi_output_falls_back_to_compile_for_single_target() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(1.0),
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let project = make_project_with_clips(vec![clip], vec![asset]);
+
+        let targets = vec![OutputTarget {
+            width: 1920,
+            height: 1080,
+            crf: 20,
+            video_bitrate: None,
+            output_path: PathBuf::from("/tmp/solo.mp4"),
+        }];
+
+        let plan = compile_multi_output(&project, &targets).unwrap();
+
+        assert!(plan.outputs.is_empty());
+        assert_eq!(plan.output_path, PathBuf::from("/tmp/solo.mp4"));
+        assert!(!plan.filter_graph.contains("split="));
+    }
+
+    #[test]
+    fn compile_multi_output_splits_and_scales_each_target() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(1.0),
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let project = make_project_with_clips(vec![clip], vec![asset]);
+
+        let targets = vec![
+            OutputTarget {
+                width: 1920,
+                height: 1080,
+                crf: 20,
+                video_bitrate: None,
+                output_path: PathBuf::from("/tmp/out-1080p.mp4"),
+            },
+            OutputTarget {
+                width: 1280,
+                height: 720,
+                crf: 23,
+                video_bitrate: None,
+                output_path: PathBuf::from("/tmp/out-720p.mp4"),
+            },
+        ];
+
+        let plan = compile_multi_output(&project, &targets).unwrap();
+
+        assert_eq!(plan.outputs.len(), 2);
+        assert!(plan.filter_graph.contains("split=2"));
+        assert!(plan.filter_graph.contains("scale=1920:1080[scaled0]"));
+        assert!(plan.filter_graph.contains("scale=1280:720[scaled1]"));
+
+        let args = build_ffmpeg_args(&plan);
+        assert!(args.contains(&"/tmp/out-1080p.mp4".to_string()));
+        assert!(args.contains(&"/tmp/out-720p.mp4".to_string()));
+    }
+
+    #[test]
+    fn compile_hls_splits_and_scales_each_rung() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(1.0),
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let project = make_project_with_clips(vec![clip], vec![asset]);
+
+        let ladder = vec![
+            HlsRung {
+                width: 1920,
+                height: 1080,
+                video_bitrate: "5000k".to_string(),
+                audio_bitrate: "192k".to_string(),
+            },
+            HlsRung {
+                width: 1280,
+                height: 720,
+                video_bitrate: "2500k".to_string(),
+                audio_bitrate: "128k".to_string(),
+            },
+            HlsRung {
+                width: 854,
+                height: 480,
+                video_bitrate: "1000k".to_string(),
+                audio_bitrate: "96k".to_string(),
+            },
+        ];
+
+        let plan = compile_hls(&project, ladder, PathBuf::from("/tmp/hls")).unwrap();
+
+        assert!(plan.filter_graph.contains("split=3"));
+        assert!(plan
+            .filter_graph
+            .contains("scale=1920:1080:force_original_aspect_ratio=decrease,pad=1920:1080:(ow-iw)/2:(oh-ih)/2[hls0]"));
+        assert!(plan
+            .filter_graph
+            .contains("scale=854:480:force_original_aspect_ratio=decrease,pad=854:480:(ow-iw)/2:(oh-ih)/2[hls2]"));
+
+        let args = build_ffmpeg_args(&plan);
+        assert!(args.contains(&"[hls0]".to_string()));
+        assert!(args.contains(&"[hls2]".to_string()));
+        assert!(args.contains(&"-c:v:0".to_string()));
+        assert!(args.contains(&"5000k".to_string()));
+        assert!(args.contains(&"-var_stream_map".to_string()));
+        assert!(args.contains(&"v:0,a:0,name:v0 v:1,a:1,name:v1 v:2,a:2,name:v2".to_string()));
+        assert!(args.contains(&"-hls_segment_type".to_string()));
+        assert!(args.contains(&"fmp4".to_string()));
+        assert!(args.contains(&"master.m3u8".to_string()));
+        let master_pl_pos = args.iter().position(|a| a == "master.m3u8").unwrap();
+        assert!(args[master_pl_pos - 1] == "-master_pl_name");
+    }
+
+    #[test]
+    fn compile_without_bumpers_is_unaffected() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(1.0),
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let project = make_project_with_clips(vec![clip], vec![asset]);
+
+        let plan = compile(&project).unwrap();
+
+        assert!(!plan.filter_graph.contains("outv_bumper"));
+        assert!(plan.output_args.contains(&"[outv]".to_string()));
+    }
+
+    #[test]
+    fn compile_with_intro_and_outro_splices_lavfi_bumpers() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(1.0),
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let mut project = make_project_with_clips(vec![clip], vec![asset]);
+        project.intro = Some(Bumper {
+            text: "My Project".to_string(),
+            background: BumperBackground::Color("#000000".to_string()),
+            duration_us: TimeUs::from_seconds(2.0),
+        });
+        project.outro = Some(Bumper {
+            text: "Thanks for watching".to_string(),
+            background: BumperBackground::Color("black".to_string()),
+            duration_us: TimeUs::from_seconds(3.0),
+        });
+
+        let plan = compile(&project).unwrap();
+
+        // Two extra lavfi inputs per bumper (video + anullsrc audio).
+        assert_eq!(plan.inputs.len(), 5);
+        assert!(plan.inputs[1].pre_args.contains(&"-f".to_string()));
+        assert!(plan.inputs[1]
+            .path
+            .to_string_lossy()
+            .contains("color=c=0x000000"));
+        assert!(plan.inputs[2]
+            .path
+            .to_string_lossy()
+            .contains("anullsrc=r=48000"));
+        assert!(plan.inputs[3]
+            .path
+            .to_string_lossy()
+            .contains("color=c=black"));
+
+        assert!(plan.filter_graph.contains("drawtext=text='My Project'"));
+        assert!(plan
+            .filter_graph
+            .contains("drawtext=text='Thanks for watching'"));
+        assert!(plan
+            .filter_graph
+            .contains("[vintro][aintro][outv][outa_core][voutro][aoutro]"));
+        assert!(plan
+            .filter_graph
+            .contains("concat=n=3:v=1:a=1[outv_bumper][outa]"));
+        assert!(plan.output_args.contains(&"[outv_bumper]".to_string()));
+        assert!(plan.output_args.contains(&"[outa]".to_string()));
+    }
+
+    #[test]
+    fn compile_burns_in_subtitle_cues_with_background_box() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let srt_path = dir.path().join("captions.srt");
+        std::fs::write(&srt_path, "1\n00:00:01,000 --> 00:00:02,000\nHello there\n").unwrap();
+
+        let asset_id = Uuid::new_v4();
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs::ZERO,
+            source_in_us: TimeUs::ZERO,
+            source_out_us: TimeUs::from_seconds(5.0),
+            speed: 1.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+        let asset = Asset {
+            id: asset_id,
+            name: "clip.mp4".to_string(),
+            path: PathBuf::from("/media/clip.mp4"),
+            kind: AssetKind::Video,
+            probe: None,
+            tags: Default::default(),
+            source_url: None,
+        };
+        let mut project = make_project_with_clips(vec![clip], vec![asset]);
+        project.timeline.tracks.push(Track {
+            id: Uuid::new_v4(),
+            kind: TrackKind::Subtitles,
+            items: vec![],
+            transitions: vec![],
+            subtitles: Some(SubtitleTrack {
+                path: srt_path,
+                style: CaptionStyle::default(),
+            }),
+        });
+
+        let plan = compile(&project).unwrap();
+
+        assert!(plan.filter_graph.contains("drawtext=text='Hello there'"));
+        assert!(plan.filter_graph.contains("box=1"));
+        assert!(plan.filter_graph.contains("boxcolor=black@0.6"));
+        assert!(plan.filter_graph.contains("enable='between(t,1,2)'"));
+        assert!(plan.filter_graph.contains("[outv]"));
+        assert!(plan.filter_graph.contains("[outv_txt]"));
+    }
+
+    #[test]
+    fn escape_drawtext_text_handles_quotes_colons_and_newlines() {
+        let escaped = escape_drawtext_text("What's this: a \"box\"?\nSecond line");
+        assert_eq!(escaped, "What'\\''s this\\: a \"box\"?\\nSecond line");
+    }
+
+    #[test]
+    fn execute_options_default_is_unconstrained() {
+        let options = ExecuteOptions::default();
+        assert!(options.memory_max.is_none());
+        assert!(options.timeout.is_none());
+        assert!(options.cpu_limit.is_none());
+    }
+
+    #[test]
+    fn systemd_run_available_matches_a_real_path_lookup() {
+        let found = std::env::var_os("PATH")
+            .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join("systemd-run").is_file()))
+            .unwrap_or(false);
+        assert_eq!(systemd_run_available(), found);
+    }
+
     #[test]
     fn parse_progress_extracts_time_and_calculates_percent() {
         let line =
@@ -1132,6 +2537,42 @@ mod tests {
         assert!((progress.percent - 0.0).abs() < 0.01);
     }
 
+    #[test]
+    fn parse_progress_block_extracts_fields_and_calculates_percent() {
+        let block = "frame=150\nfps=30.00\nout_time_us=5000000\ntotal_size=102400\nspeed=1.50x\nprogress=continue\n";
+
+        let progress = parse_progress_block(block, 10.0).unwrap();
+
+        assert_eq!(progress.frame, 150);
+        assert!((progress.fps - 30.0).abs() < 0.01);
+        assert!((progress.percent - 50.0).abs() < 0.1);
+        assert_eq!(progress.speed, "1.50x");
+        assert_eq!(progress.total_size, Some(102400));
+        // ETA: (10 - 5) / 1.5 = 3.33s
+        assert!((progress.eta_seconds.unwrap() - 3.33).abs() < 0.1);
+    }
+
+    #[test]
+    fn parse_progress_block_falls_back_to_out_time_ms() {
+        let block = "frame=60\nfps=30.00\nout_time_ms=2000\nspeed=1.00x\nprogress=continue\n";
+
+        let progress = parse_progress_block(block, 10.0).unwrap();
+
+        assert!((progress.percent - 20.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn parse_progress_block_recognizes_end_marker() {
+        let block = "frame=300\nout_time_us=10000000\nprogress=end\n";
+        assert!(parse_progress_block(block, 10.0).is_some());
+    }
+
+    #[test]
+    fn parse_progress_block_returns_none_without_progress_marker() {
+        assert!(parse_progress_block("frame=10\nfps=30.00\n", 10.0).is_none());
+        assert!(parse_progress_block("", 10.0).is_none());
+    }
+
     #[test]
     fn parse_time_str_valid() {
         assert!((parse_time_str("00:01:02.05").unwrap() - 62.05).abs() < 0.001);
@@ -1145,6 +2586,57 @@ mod tests {
         assert!(parse_time_str("00:00").is_none());
     }
 
+    #[test]
+    fn atempo_chain_passes_through_in_range_factors() {
+        assert_eq!(atempo_chain(1.5), "atempo=1.5");
+        assert_eq!(atempo_chain(0.5), "atempo=0.5");
+        assert_eq!(atempo_chain(2.0), "atempo=2");
+    }
+
+    #[test]
+    fn atempo_chain_decomposes_speed_up_beyond_two() {
+        assert_eq!(atempo_chain(8.0), "atempo=2,atempo=2,atempo=2");
+    }
+
+    #[test]
+    fn atempo_chain_decomposes_slow_down_below_half() {
+        assert_eq!(atempo_chain(0.25), "atempo=0.5,atempo=0.5");
+    }
+
+    #[test]
+    fn atempo_chain_treats_non_finite_or_non_positive_speed_as_identity() {
+        // A speed of exactly 0.0 used to spin the halving loop forever
+        // (0.0 / 0.5 == 0.0, never climbing back above the threshold).
+        assert_eq!(atempo_chain(0.0), "atempo=1");
+        assert_eq!(atempo_chain(-2.0), "atempo=1");
+        assert_eq!(atempo_chain(f64::NAN), "atempo=1");
+        assert_eq!(atempo_chain(f64::INFINITY), "atempo=1");
+    }
+
+    #[test]
+    fn compile_uses_decomposed_atempo_chain_for_out_of_range_speed() {
+        let asset_id = Uuid::new_v4();
+        let asset = make_asset(asset_id, "/tmp/clip.mp4");
+
+        let clip = Item::VideoClip {
+            id: Uuid::new_v4(),
+            asset_id,
+            track_id: Uuid::new_v4(),
+            timeline_start_us: TimeUs(0),
+            source_in_us: TimeUs::from_seconds(0.0),
+            source_out_us: TimeUs::from_seconds(8.0),
+            speed: 4.0,
+            fade_in_us: TimeUs::ZERO,
+            fade_out_us: TimeUs::ZERO,
+        };
+
+        let project = make_project_with_clips(vec![clip], vec![asset]);
+        let plan = compile(&project).unwrap();
+
+        assert!(plan.filter_graph.contains("setpts=(PTS-STARTPTS)/4"));
+        assert!(plan.filter_graph.contains("atempo=2,atempo=2"));
+    }
+
     #[test]
     fn extract_value_works() {
         let line = "frame=  150 fps= 30.0 time=00:00:05.00 speed=1.50x";
@@ -1208,7 +2700,10 @@ mod tests {
             let s = time_s % 60.0;
             raw.push_str(&format!(
                 "frame={:4} fps= 60 time={:02}:{:02}:{:05.2} speed=2.00x\r",
-                i * 6, h, m, s
+                i * 6,
+                h,
+                m,
+                s
             ));
         }
         let results = parse_chunk(&raw, 30.0);
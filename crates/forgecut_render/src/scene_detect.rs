@@ -0,0 +1,246 @@
+//! Automatic scene-cut detection, so an imported asset's internal cuts show
+//! up as [`Marker`]s without the editor having to scrub manually. Detected
+//! cuts flow straight into [`collect_snap_points`](forgecut_core::snapping::collect_snap_points)
+//! once pushed onto the timeline as markers, so they become snap targets and
+//! candidates for auto-splitting.
+
+use std::path::Path;
+
+use forgecut_core::types::{Asset, ProjectSettings, TimeUs};
+
+use crate::error::{RenderError, Result};
+
+/// Downscale grid side length each decoded frame is reduced to before
+/// comparison. Small enough that per-frame cost is cheap, large enough that
+/// a hard cut still stands out from a pan or a flash.
+const GRID_SIDE: u32 = 32;
+
+/// Sliding window (in frames) the adaptive threshold's mean/stddev are
+/// computed over.
+const ADAPTIVE_WINDOW: usize = 30;
+
+/// Standard deviations above the rolling mean a frame's cost must exceed to
+/// be flagged as a cut.
+const THRESHOLD_K: f64 = 5.0;
+
+/// Detect hard scene cuts in `asset`'s video stream, returning cut
+/// timestamps in microseconds (always including `0`, never including a
+/// timestamp beyond the probed duration).
+///
+/// Decodes the asset to a grayscale `GRID_SIDE`x`GRID_SIDE` raw stream via
+/// ffmpeg (the same shell-out-and-parse-raw-bytes approach as
+/// [`crate::waveform::extract_waveform`]), then scores each frame by the sum
+/// of absolute differences against the previous frame's grid, normalized to
+/// `[0, 1]`. A frame is a cut when its cost exceeds the mean plus
+/// `THRESHOLD_K` standard deviations over the trailing `ADAPTIVE_WINDOW`
+/// frames, and at least `min_scene_len_frames` frames (the caller's count,
+/// at `settings.fps`) have elapsed since the last cut -- without that gate, a
+/// couple of flickering frames would register as several cuts in a row.
+pub fn detect_scene_cuts(
+    asset: &Asset,
+    settings: &ProjectSettings,
+    min_scene_len_frames: u32,
+) -> Result<Vec<TimeUs>> {
+    let probe = asset
+        .probe
+        .as_ref()
+        .ok_or_else(|| RenderError::MediaRejected {
+            reason: "asset has not been probed".into(),
+        })?;
+
+    let grids = decode_luma_grids(&asset.path, settings.fps.as_f64())?;
+    let frame_duration_us = TimeUs::from_seconds(1.0 / settings.fps.as_f64().max(1.0));
+    let min_scene_len_frames = min_scene_len_frames.max(1);
+
+    let mut cuts = vec![TimeUs::ZERO];
+    let mut costs_window: Vec<f64> = Vec::with_capacity(ADAPTIVE_WINDOW);
+    let mut last_cut_frame: i64 = 0;
+
+    for (frame_index, cost) in frame_costs(&grids).enumerate() {
+        let frame_index = frame_index as i64 + 1; // cost[i] compares frame i+1 to frame i
+        let since_last_cut = frame_index - last_cut_frame;
+
+        let is_cut = since_last_cut >= min_scene_len_frames as i64
+            && exceeds_adaptive_threshold(cost, &costs_window);
+
+        if is_cut {
+            cuts.push(frame_duration_us * frame_index);
+            last_cut_frame = frame_index;
+        }
+
+        costs_window.push(cost);
+        if costs_window.len() > ADAPTIVE_WINDOW {
+            costs_window.remove(0);
+        }
+    }
+
+    if let Some(&last) = cuts.last() {
+        if last > probe.duration_us {
+            *cuts.last_mut().unwrap() = probe.duration_us;
+        }
+    }
+
+    Ok(cuts)
+}
+
+/// Timestamps within this many seconds of each other are considered the same
+/// boundary -- ffmpeg's `showinfo` can emit a `pts_time:` a hair off from the
+/// true frame boundary due to rounding.
+const DEDUPE_EPSILON_SECONDS: f64 = 1e-3;
+
+/// Detect scene-change timestamps (in seconds) in `source_path` using
+/// ffmpeg's own `scene` frame-difference metric, rather than
+/// [`detect_scene_cuts`]'s grid-SAD approach -- a quick, dependency-free way
+/// to get a first cut of shot boundaries straight from `ffmpeg`'s `select`
+/// filter without decoding raw frames ourselves.
+///
+/// Runs `ffmpeg -i <src> -vf "select='gt(scene,{threshold})',showinfo" -f
+/// null -` and scrapes each `showinfo` line's `pts_time:` field. `0.0` is
+/// always the first boundary; results are deduped within
+/// [`DEDUPE_EPSILON_SECONDS`] and returned sorted.
+pub fn detect_scenes(source_path: &Path, threshold: f64) -> Result<Vec<f64>> {
+    let output = std::process::Command::new("ffmpeg")
+        .args([
+            "-i",
+            &source_path.to_string_lossy(),
+            "-vf",
+            &format!("select='gt(scene,{threshold})',showinfo"),
+            "-f",
+            "null",
+            "-",
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .output()
+        .map_err(RenderError::Io)?;
+
+    if !output.status.success() {
+        return Err(RenderError::FfmpegFailed("scene detection failed".into()));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut timestamps: Vec<f64> = vec![0.0];
+    for line in stderr.lines() {
+        if let Some(pts_time) = parse_pts_time(line) {
+            timestamps.push(pts_time);
+        }
+    }
+
+    timestamps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    timestamps.dedup_by(|a, b| (*a - *b).abs() < DEDUPE_EPSILON_SECONDS);
+
+    Ok(timestamps)
+}
+
+/// Pull the `pts_time:<float>` field out of one `showinfo` stderr line.
+fn parse_pts_time(line: &str) -> Option<f64> {
+    let (_, rest) = line.split_once("pts_time:")?;
+    let value = rest.split_whitespace().next()?;
+    value.parse::<f64>().ok()
+}
+
+/// `true` when `cost` exceeds `mean(window) + THRESHOLD_K * stddev(window)`.
+/// An empty or single-sample window (not enough history yet) never flags a
+/// cut -- there's nothing to be adaptive relative to.
+fn exceeds_adaptive_threshold(cost: f64, window: &[f64]) -> bool {
+    if window.len() < 2 {
+        return false;
+    }
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / window.len() as f64;
+    let stddev = variance.sqrt();
+    cost > mean + THRESHOLD_K * stddev
+}
+
+/// Sum of absolute differences between consecutive grids, normalized to
+/// `[0, 1]` by the maximum possible per-pixel difference.
+fn frame_costs(grids: &[Vec<u8>]) -> impl Iterator<Item = f64> + '_ {
+    grids.windows(2).map(|pair| {
+        let sad: u64 = pair[0]
+            .iter()
+            .zip(pair[1].iter())
+            .map(|(a, b)| (*a as i32 - *b as i32).unsigned_abs() as u64)
+            .sum();
+        sad as f64 / (pair[0].len() as f64 * 255.0)
+    })
+}
+
+/// Decode `source_path` to a sequence of `GRID_SIDE`x`GRID_SIDE` grayscale
+/// frames at `fps`, one `Vec<u8>` of `GRID_SIDE * GRID_SIDE` luma samples per
+/// frame, via ffmpeg's raw video muxer.
+fn decode_luma_grids(source_path: &std::path::Path, fps: f64) -> Result<Vec<Vec<u8>>> {
+    let output = std::process::Command::new("ffmpeg")
+        .args([
+            "-i",
+            &source_path.to_string_lossy(),
+            "-vf",
+            &format!("fps={fps},scale={GRID_SIDE}:{GRID_SIDE},format=gray"),
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "gray",
+            "-",
+        ])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .map_err(RenderError::Io)?;
+
+    if !output.status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "scene-cut frame decode failed".into(),
+        ));
+    }
+
+    let frame_size = (GRID_SIDE * GRID_SIDE) as usize;
+    Ok(output
+        .stdout
+        .chunks_exact(frame_size)
+        .map(|chunk| chunk.to_vec())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exceeds_adaptive_threshold_needs_at_least_two_samples() {
+        assert!(!exceeds_adaptive_threshold(0.9, &[]));
+        assert!(!exceeds_adaptive_threshold(0.9, &[0.1]));
+    }
+
+    #[test]
+    fn exceeds_adaptive_threshold_flags_large_spike() {
+        let window = vec![0.01, 0.02, 0.015, 0.01, 0.02];
+        assert!(exceeds_adaptive_threshold(0.9, &window));
+        assert!(!exceeds_adaptive_threshold(0.02, &window));
+    }
+
+    #[test]
+    fn frame_costs_scores_identical_frames_as_zero() {
+        let grids = vec![vec![10u8; 4], vec![10u8; 4], vec![10u8; 4]];
+        let costs: Vec<f64> = frame_costs(&grids).collect();
+        assert_eq!(costs, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn frame_costs_scores_full_black_to_white_as_one() {
+        let grids = vec![vec![0u8; 4], vec![255u8; 4]];
+        let costs: Vec<f64> = frame_costs(&grids).collect();
+        assert_eq!(costs.len(), 1);
+        assert!((costs[0] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_pts_time_extracts_field_from_showinfo_line() {
+        let line = "[Parsed_showinfo_1 @ 0x55d1] n:   4 pts:  12345 pts_time:4.11   \
+                    pos:123456 fmt:yuv420p sar:1/1 s:1920x1080";
+        assert_eq!(parse_pts_time(line), Some(4.11));
+    }
+
+    #[test]
+    fn parse_pts_time_returns_none_without_field() {
+        assert_eq!(parse_pts_time("frame=  100 fps=30 q=-1.0 size=..."), None);
+    }
+}
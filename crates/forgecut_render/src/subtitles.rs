@@ -0,0 +1,151 @@
+//! Parses burned-in subtitle cues from an SRT or WebVTT file for
+//! [`crate::render`]'s `drawtext`-with-background-box overlay stage. Both
+//! formats share the same `HH:MM:SS{,|.}mmm --> HH:MM:SS{,|.}mmm` timing line
+//! and blank-line-separated cue blocks; WebVTT additionally allows a
+//! `WEBVTT` header and cue settings after the arrow, both of which are
+//! skipped since only the timing and text matter for burning the cues in.
+
+use forgecut_core::types::TimeUs;
+
+use crate::error::{RenderError, Result};
+
+/// A single subtitle cue: the text to show and the timeline window (assumed
+/// to be absolute project time, matching the final render) during which
+/// it's visible.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_us: TimeUs,
+    pub end_us: TimeUs,
+    pub text: String,
+}
+
+/// Parses all cues out of `contents`, which may be SRT or WebVTT.
+pub fn parse_subtitles(contents: &str) -> Result<Vec<Cue>> {
+    let mut lines = contents.lines().peekable();
+
+    if let Some(first) = lines.peek() {
+        if first.trim_start().starts_with("WEBVTT") {
+            lines.next();
+            for line in lines.by_ref() {
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut cues = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in lines {
+        if line.trim().is_empty() {
+            flush_block(&mut block, &mut cues)?;
+        } else {
+            block.push(line);
+        }
+    }
+    flush_block(&mut block, &mut cues)?;
+
+    Ok(cues)
+}
+
+/// Parses one blank-line-delimited block (an optional index/identifier
+/// line, a timing line, then one or more lines of cue text) and pushes the
+/// resulting [`Cue`] onto `cues`. A no-op on an empty block, so callers can
+/// flush unconditionally at each blank line and at end of input.
+fn flush_block<'a>(block: &mut Vec<&'a str>, cues: &mut Vec<Cue>) -> Result<()> {
+    if block.is_empty() {
+        return Ok(());
+    }
+
+    let timing_idx = if block[0].contains("-->") { 0 } else { 1 };
+    let timing_line = block.get(timing_idx).ok_or_else(|| {
+        RenderError::SubtitleParse(format!("cue block has no timing line: {block:?}"))
+    })?;
+    let (start_us, end_us) = parse_timing_line(timing_line)?;
+    let text = block[timing_idx + 1..].join("\n");
+
+    cues.push(Cue {
+        start_us,
+        end_us,
+        text,
+    });
+    block.clear();
+    Ok(())
+}
+
+fn parse_timing_line(line: &str) -> Result<(TimeUs, TimeUs)> {
+    let (start, end) = line.split_once("-->").ok_or_else(|| {
+        RenderError::SubtitleParse(format!("invalid subtitle timing line: {line}"))
+    })?;
+    // WebVTT allows cue settings (e.g. "align:center line:90%") after the
+    // end timestamp, separated by whitespace -- only the timestamp matters.
+    let end_token = end.trim().split_whitespace().next().unwrap_or("");
+    Ok((parse_timestamp(start.trim())?, parse_timestamp(end_token)?))
+}
+
+fn parse_timestamp(s: &str) -> Result<TimeUs> {
+    let normalized = s.replace(',', ".");
+    let (hms, millis) = normalized
+        .split_once('.')
+        .ok_or_else(|| RenderError::SubtitleParse(format!("invalid subtitle timestamp: {s}")))?;
+
+    let parts: Vec<&str> = hms.split(':').collect();
+    let (h, m, sec) = match parts.as_slice() {
+        [h, m, sec] => (*h, *m, *sec),
+        [m, sec] => ("0", *m, *sec),
+        _ => {
+            return Err(RenderError::SubtitleParse(format!(
+                "invalid subtitle timestamp: {s}"
+            )))
+        }
+    };
+
+    let parse_part = |part: &str| -> Result<i64> {
+        part.parse()
+            .map_err(|_| RenderError::SubtitleParse(format!("invalid subtitle timestamp: {s}")))
+    };
+    let total_ms = ((parse_part(h)? * 60 + parse_part(m)?) * 60 + parse_part(sec)?) * 1000
+        + parse_part(millis)?;
+
+    Ok(TimeUs(total_ms * 1000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_srt_with_comma_millis() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,500\nHello there\n\n2\n00:00:03,000 --> 00:00:04,000\nSecond cue\n";
+        let cues = parse_subtitles(srt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].start_us, TimeUs(1_000_000));
+        assert_eq!(cues[0].end_us, TimeUs(2_500_000));
+        assert_eq!(cues[0].text, "Hello there");
+        assert_eq!(cues[1].start_us, TimeUs(3_000_000));
+        assert_eq!(cues[1].text, "Second cue");
+    }
+
+    #[test]
+    fn parses_vtt_with_dot_millis_and_header() {
+        let vtt =
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.000 align:center line:90%\nFirst\nSecond line\n";
+        let cues = parse_subtitles(vtt).unwrap();
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].start_us, TimeUs(1_000_000));
+        assert_eq!(cues[0].end_us, TimeUs(2_000_000));
+        assert_eq!(cues[0].text, "First\nSecond line");
+    }
+
+    #[test]
+    fn parses_timestamp_without_hours() {
+        let srt = "00:01,000 --> 00:02,000\nShort form\n";
+        let cues = parse_subtitles(srt).unwrap();
+        assert_eq!(cues[0].start_us, TimeUs(1_000_000));
+    }
+
+    #[test]
+    fn rejects_invalid_timing_line() {
+        assert!(parse_subtitles("not a timing line\ntext\n").is_err());
+    }
+}
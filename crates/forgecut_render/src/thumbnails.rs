@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
 
+use forgecut_core::types::TimeUs;
+
 use crate::error::{RenderError, Result};
 
 /// Extract a single thumbnail at a specific time from a video file.
@@ -43,6 +45,14 @@ pub fn extract_thumbnail(
 
 /// Extract multiple thumbnails at regular intervals.
 /// Returns list of (time_seconds, path) pairs.
+///
+/// Scrubbing a long clip can need hundreds of thumbnails; spawning one
+/// `ffmpeg -ss ...` process per frame re-opens and seeks the source every
+/// time, which is catastrophically slow. When more than one thumbnail is
+/// actually missing from the cache, [`extract_thumbnails_batch`] decodes
+/// them all in a single linear pass instead. A lone missing thumbnail still
+/// goes through the seek-based [`extract_thumbnail`] -- not worth a full
+/// decode pass for one frame.
 pub fn extract_thumbnails(
     source_path: &Path,
     cache_dir: &Path,
@@ -54,23 +64,100 @@ pub fn extract_thumbnails(
     let asset_dir = cache_dir.join(asset_id);
     std::fs::create_dir_all(&asset_dir).map_err(RenderError::Io)?;
 
-    let mut results = Vec::new();
+    let mut times = Vec::new();
     let mut t = 0.0;
     while t < duration_seconds {
-        let time_us = (t * 1_000_000.0) as i64;
-        let thumb_path = asset_dir.join(format!("{time_us}.jpg"));
+        times.push(t);
+        t += interval_seconds;
+    }
 
+    let missing_count = times
+        .iter()
+        .filter(|t| {
+            !asset_dir
+                .join(format!("{}.jpg", thumb_time_us(**t)))
+                .exists()
+        })
+        .count();
+
+    if missing_count > 1 {
+        extract_thumbnails_batch(source_path, &asset_dir, interval_seconds, thumb_width)?;
+    }
+
+    let mut results = Vec::with_capacity(times.len());
+    for t in times {
+        let thumb_path = asset_dir.join(format!("{}.jpg", thumb_time_us(t)));
         if !thumb_path.exists() {
             extract_thumbnail(source_path, &thumb_path, t, thumb_width)?;
         }
-
         results.push((t, thumb_path));
-        t += interval_seconds;
     }
 
     Ok(results)
 }
 
+/// The `{time_us}.jpg` cache key for a thumbnail at `time_seconds`.
+fn thumb_time_us(time_seconds: f64) -> i64 {
+    (time_seconds * 1_000_000.0) as i64
+}
+
+/// Extract every thumbnail for `asset_dir` in one ffmpeg decode pass: `-vf
+/// "fps=1/{interval},scale={width}:-1"` emits one JPEG per interval as the
+/// source streams past, turning what would be O(n) process spawns into a
+/// single linear scan. ffmpeg numbers its `batch_%06d.jpg` output 1-based, so
+/// output frame `n` is renamed to the `{time_us}.jpg` cache convention using
+/// `time_us = (n - 1) * interval_seconds`.
+fn extract_thumbnails_batch(
+    source_path: &Path,
+    asset_dir: &Path,
+    interval_seconds: f64,
+    thumb_width: u32,
+) -> Result<()> {
+    let pattern = asset_dir.join("batch_%06d.jpg");
+
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-i",
+            &source_path.to_string_lossy(),
+            "-vf",
+            &format!("fps=1/{interval_seconds},scale={thumb_width}:-1"),
+            "-q:v",
+            "5",
+            &pattern.to_string_lossy(),
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(RenderError::Io)?;
+
+    if !status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "batch thumbnail extraction failed".into(),
+        ));
+    }
+
+    let mut numbered: Vec<(u32, PathBuf)> = std::fs::read_dir(asset_dir)
+        .map_err(RenderError::Io)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let n: u32 = path.file_stem()?.to_str()?.strip_prefix("batch_")?.parse().ok()?;
+            Some((n, path))
+        })
+        .collect();
+    numbered.sort_by_key(|(n, _)| *n);
+
+    for (n, path) in numbered {
+        let frame_index = n.saturating_sub(1);
+        let time_us = thumb_time_us(frame_index as f64 * interval_seconds);
+        let dest = asset_dir.join(format!("{time_us}.jpg"));
+        std::fs::rename(&path, &dest).map_err(RenderError::Io)?;
+    }
+
+    Ok(())
+}
+
 /// Extract multiple thumbnails at regular intervals, returning base64-encoded JPEG data URIs.
 /// Returns list of (time_seconds, data_uri) pairs.
 pub fn extract_thumbnails_base64(
@@ -102,8 +189,94 @@ pub fn extract_thumbnails_base64(
     Ok(results)
 }
 
+/// Generate an evenly-spaced filmstrip PNG for the clip of `source_path`
+/// trimmed to `[source_in_us, source_out_us)`, compositing `frame_count`
+/// decoded frames side by side with the `image` crate. Cached on disk keyed
+/// by asset ID plus the source range, so trimming a clip (which changes the
+/// range) regenerates the strip lazily instead of reusing a stale one.
+pub fn generate_filmstrip(
+    source_path: &Path,
+    cache_dir: &Path,
+    asset_id: &str,
+    source_in_us: TimeUs,
+    source_out_us: TimeUs,
+    frame_count: u32,
+    frame_width: u32,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(cache_dir).map_err(RenderError::Io)?;
+
+    let strip_path = cache_dir.join(format!(
+        "{asset_id}_{}_{}_{frame_count}x{frame_width}.png",
+        source_in_us.0, source_out_us.0
+    ));
+    if strip_path.exists() {
+        return Ok(strip_path);
+    }
+
+    let frame_count = frame_count.max(1);
+    let frame_height = frame_width * 9 / 16;
+    let mut strip = image::RgbImage::new(frame_width * frame_count, frame_height.max(1));
+
+    let span_us = (source_out_us.0 - source_in_us.0).max(0) as f64;
+    let step_us = if frame_count > 1 {
+        span_us / frame_count as f64
+    } else {
+        0.0
+    };
+
+    let frame_dir = cache_dir.join(format!("{asset_id}-filmstrip-frames"));
+    std::fs::create_dir_all(&frame_dir).map_err(RenderError::Io)?;
+
+    for i in 0..frame_count {
+        let offset_us = source_in_us.0 as f64 + step_us * i as f64;
+        let frame_path = frame_dir.join(format!("frame_{i}.jpg"));
+        extract_thumbnail(source_path, &frame_path, offset_us / 1_000_000.0, frame_width)?;
+
+        let frame = image::open(&frame_path)
+            .map_err(|e| RenderError::Image(format!("decode frame {i}: {e}")))?
+            .resize_exact(
+                frame_width,
+                frame_height.max(1),
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgb8();
+
+        image::imageops::replace(&mut strip, &frame, (i * frame_width) as i64, 0);
+    }
+
+    let _ = std::fs::remove_dir_all(&frame_dir);
+
+    strip
+        .save(&strip_path)
+        .map_err(|e| RenderError::Image(format!("save filmstrip: {e}")))?;
+
+    Ok(strip_path)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_filmstrip_cache_path_is_keyed_by_asset_and_range() {
+        let cache_dir = std::path::Path::new("/tmp/test-filmstrips");
+        let path = cache_dir.join(format!(
+            "{}_{}_{}_{}x{}.png",
+            "asset123", 0, 5_000_000, 10, 160
+        ));
+        assert_eq!(
+            path,
+            std::path::PathBuf::from("/tmp/test-filmstrips/asset123_0_5000000_10x160.png")
+        );
+    }
+
+    #[test]
+    fn thumb_time_us_matches_time_us_jpg_cache_convention() {
+        assert_eq!(thumb_time_us(0.0), 0);
+        assert_eq!(thumb_time_us(1.0), 1_000_000);
+        assert_eq!(thumb_time_us(2.5), 2_500_000);
+    }
+
     #[test]
     fn thumbnail_path_structure() {
         let cache_dir = std::path::Path::new("/tmp/test-thumbs");
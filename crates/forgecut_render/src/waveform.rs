@@ -1,15 +1,92 @@
 use std::path::Path;
 
+use forgecut_core::types::TimeUs;
+
 use crate::error::{RenderError, Result};
 
-/// Peak data for waveform display: pairs of (min, max) for each sample window.
+/// A pyramid level shrinks to this many peaks or fewer before the pyramid
+/// stops growing coarser -- past that point there's nothing left worth
+/// zooming further out to.
+const MIN_PYRAMID_LEVEL_PEAKS: usize = 8;
+
+/// Peak data for waveform display, as a mipmap-style pyramid: `levels[0]` is
+/// `samples_per_peak`-resolution pairs of (min, max) for each sample window,
+/// and each subsequent level halves the peak count of the one before it by
+/// combining adjacent pairs (`new min = min(min_a, min_b)`,
+/// `new max = max(max_a, max_b)`). Zooming the timeline out no longer needs a
+/// fresh ffmpeg decode -- [`WaveformData::level_for_zoom`] just picks a
+/// coarser level already sitting in this same cached struct.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WaveformData {
-    pub peaks: Vec<(f32, f32)>,
+    pub levels: Vec<Vec<(f32, f32)>>,
     pub sample_rate: u32,
+    /// `samples_per_peak` of `levels[0]`, the finest level. Level `n`'s
+    /// effective `samples_per_peak` is this value times `2^n`.
     pub samples_per_peak: u32,
 }
 
+impl WaveformData {
+    /// The finest-resolution peaks, equivalent to what `extract_waveform`
+    /// returned before the pyramid existed.
+    pub fn peaks(&self) -> &[(f32, f32)] {
+        self.levels.first().map_or(&[], Vec::as_slice)
+    }
+
+    /// The coarsest pyramid level that still resolves at least one peak per
+    /// pixel at `pixels_per_second`, so the UI renders no more detail than
+    /// the current zoom can show. Falls back to the finest level if even
+    /// that isn't fine enough.
+    pub fn level_for_zoom(&self, pixels_per_second: f64) -> &[(f32, f32)] {
+        if self.levels.is_empty() {
+            return &[];
+        }
+        let target_peak_duration = 1.0 / pixels_per_second.max(f64::MIN_POSITIVE);
+        self.levels
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(i, _)| self.level_peak_duration(*i) <= target_peak_duration)
+            .map_or(self.levels[0].as_slice(), |(_, level)| level.as_slice())
+    }
+
+    /// Seconds spanned by one peak at pyramid level `index` (`0` = finest).
+    fn level_peak_duration(&self, index: usize) -> f64 {
+        let samples_per_peak = self.samples_per_peak as u64 * (1u64 << index);
+        samples_per_peak as f64 / self.sample_rate.max(1) as f64
+    }
+}
+
+/// Build a mipmap-style peak pyramid from `finest`, repeatedly halving the
+/// peak count until a level has [`MIN_PYRAMID_LEVEL_PEAKS`] or fewer.
+fn build_pyramid(finest: Vec<(f32, f32)>) -> Vec<Vec<(f32, f32)>> {
+    let mut levels = vec![finest];
+    loop {
+        let current = levels.last().unwrap();
+        if current.len() <= MIN_PYRAMID_LEVEL_PEAKS {
+            break;
+        }
+        let coarser = downsample_peak_level(current);
+        if coarser.len() == current.len() {
+            break;
+        }
+        levels.push(coarser);
+    }
+    levels
+}
+
+/// Combine each adjacent pair of peaks in `level` into one, halving its
+/// length (rounding up for an odd final pair).
+fn downsample_peak_level(level: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            let min = pair.iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+            let max = pair.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max);
+            (min, max)
+        })
+        .collect()
+}
+
 /// Extract audio peaks from a media file using ffmpeg.
 /// Outputs raw PCM, then computes min/max peaks in Rust.
 pub fn extract_waveform(
@@ -22,8 +99,12 @@ pub fn extract_waveform(
 
     // Return cached if exists
     if cache_path.exists() {
-        let data = std::fs::read_to_string(&cache_path).map_err(RenderError::Io)?;
-        return serde_json::from_str(&data).map_err(RenderError::Json);
+        let raw = std::fs::read_to_string(&cache_path).map_err(RenderError::Io)?;
+        if let Ok(data) = serde_json::from_str(&raw) {
+            return Ok(data);
+        }
+        // Cache predates the current WaveformData schema (or is otherwise
+        // corrupt) -- fall through and regenerate instead of hard-erroring.
     }
 
     std::fs::create_dir_all(cache_dir).map_err(RenderError::Io)?;
@@ -61,10 +142,10 @@ pub fn extract_waveform(
         .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
         .collect();
 
-    let peaks = compute_peaks(&samples, samples_per_peak);
+    let levels = build_pyramid(compute_peaks(&samples, samples_per_peak));
 
     let data = WaveformData {
-        peaks,
+        levels,
         sample_rate: 8000,
         samples_per_peak,
     };
@@ -76,6 +157,88 @@ pub fn extract_waveform(
     Ok(data)
 }
 
+/// Extract audio peaks for a clip trimmed to `[source_in_us, source_out_us)`,
+/// downsampled to approximately `peak_width` buckets. Cached on disk keyed
+/// by asset ID plus the source range, so trimming a clip (which changes the
+/// range) regenerates the peaks lazily instead of reusing stale whole-file
+/// data.
+pub fn extract_waveform_range(
+    source_path: &Path,
+    cache_dir: &Path,
+    asset_id: &str,
+    source_in_us: TimeUs,
+    source_out_us: TimeUs,
+    peak_width: u32,
+) -> Result<WaveformData> {
+    let cache_path = cache_dir.join(format!(
+        "{asset_id}_{}_{}.json",
+        source_in_us.0, source_out_us.0
+    ));
+
+    if cache_path.exists() {
+        let raw = std::fs::read_to_string(&cache_path).map_err(RenderError::Io)?;
+        if let Ok(data) = serde_json::from_str(&raw) {
+            return Ok(data);
+        }
+        // Cache predates the current WaveformData schema (or is otherwise
+        // corrupt) -- fall through and regenerate instead of hard-erroring.
+    }
+
+    std::fs::create_dir_all(cache_dir).map_err(RenderError::Io)?;
+
+    let start_seconds = source_in_us.as_seconds();
+    let duration_seconds = ((source_out_us.0 - source_in_us.0).max(0) as f64) / 1_000_000.0;
+
+    let output = std::process::Command::new("ffmpeg")
+        .args([
+            "-ss",
+            &format!("{start_seconds:.3}"),
+            "-i",
+            &source_path.to_string_lossy(),
+            "-t",
+            &format!("{duration_seconds:.3}"),
+            "-f",
+            "s16le",
+            "-ac",
+            "1",
+            "-ar",
+            "8000",
+            "-acodec",
+            "pcm_s16le",
+            "-",
+        ])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .map_err(RenderError::Io)?;
+
+    if !output.status.success() {
+        return Err(RenderError::FfmpegFailed(
+            "Waveform extraction failed".into(),
+        ));
+    }
+
+    let samples: Vec<i16> = output
+        .stdout
+        .chunks_exact(2)
+        .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+
+    let samples_per_peak = ((samples.len() as u32) / peak_width.max(1)).max(1);
+    let levels = build_pyramid(compute_peaks(&samples, samples_per_peak));
+
+    let data = WaveformData {
+        levels,
+        sample_rate: 8000,
+        samples_per_peak,
+    };
+
+    let json = serde_json::to_string(&data).map_err(RenderError::Json)?;
+    let _ = std::fs::write(&cache_path, json);
+
+    Ok(data)
+}
+
 fn compute_peaks(samples: &[i16], samples_per_peak: u32) -> Vec<(f32, f32)> {
     samples
         .chunks(samples_per_peak as usize)
@@ -111,6 +274,78 @@ mod tests {
         assert!(peaks.is_empty());
     }
 
+    #[test]
+    fn extract_waveform_range_cache_path_is_keyed_by_asset_and_range() {
+        let cache_dir = std::path::Path::new("/tmp/test-waveform-range");
+        let path = cache_dir.join(format!("{}_{}_{}.json", "asset123", 0, 5_000_000));
+        assert_eq!(
+            path,
+            std::path::PathBuf::from("/tmp/test-waveform-range/asset123_0_5000000.json")
+        );
+    }
+
+    #[test]
+    fn build_pyramid_halves_each_level_until_minimum() {
+        let finest: Vec<(f32, f32)> = (0..40).map(|i| (i as f32, i as f32 + 1.0)).collect();
+        let levels = build_pyramid(finest);
+        assert_eq!(levels[0].len(), 40);
+        assert_eq!(levels[1].len(), 20);
+        assert_eq!(levels[2].len(), 10);
+        assert!(levels.last().unwrap().len() <= MIN_PYRAMID_LEVEL_PEAKS);
+        // Every level stays within the min/max bounds of the finest level.
+        let overall_min = levels[0].iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+        let overall_max = levels[0]
+            .iter()
+            .map(|p| p.1)
+            .fold(f32::NEG_INFINITY, f32::max);
+        for level in &levels {
+            for &(min, max) in level {
+                assert!(min >= overall_min && max <= overall_max);
+            }
+        }
+    }
+
+    #[test]
+    fn downsample_peak_level_combines_adjacent_pairs() {
+        let level = vec![(-1.0, 0.5), (-0.2, 0.8), (-0.5, 0.3)];
+        let coarser = downsample_peak_level(&level);
+        assert_eq!(coarser, vec![(-1.0, 0.8), (-0.5, 0.3)]);
+    }
+
+    #[test]
+    fn level_for_zoom_picks_coarsest_level_fine_enough_for_pixels_per_second() {
+        let data = WaveformData {
+            levels: build_pyramid(compute_peaks(&vec![0i16; 4000], 10)),
+            sample_rate: 8000,
+            samples_per_peak: 10,
+        };
+        // Level 0's peak duration is 10/8000 = 1.25ms, i.e. ~800 peaks/sec.
+        // Zooming out to 50 pixels/sec should land on a much coarser level.
+        let zoomed_out = data.level_for_zoom(50.0);
+        let finest = data.peaks();
+        assert!(zoomed_out.len() < finest.len());
+    }
+
+    #[test]
+    fn level_for_zoom_falls_back_to_finest_when_nothing_is_fine_enough() {
+        let data = WaveformData {
+            levels: build_pyramid(compute_peaks(&vec![0i16; 40], 10)),
+            sample_rate: 8000,
+            samples_per_peak: 10,
+        };
+        assert_eq!(data.level_for_zoom(1_000_000.0), data.peaks());
+    }
+
+    #[test]
+    fn peaks_returns_finest_level() {
+        let data = WaveformData {
+            levels: vec![vec![(-0.5, 0.5)], vec![(-0.25, 0.25)]],
+            sample_rate: 8000,
+            samples_per_peak: 10,
+        };
+        assert_eq!(data.peaks(), &[(-0.5, 0.5)]);
+    }
+
     #[test]
     fn compute_peaks_partial_chunk() {
         let samples: Vec<i16> = vec![1000, -1000, 500];